@@ -375,6 +375,31 @@ pub fn calculate_min_amount_out(
     expected_amount_out.big_mul_up(Percentage::from_integer(1u8) - slippage)
 }
 
+pub fn calculate_sqrt_price_after_slippage(
+    sqrt_price: SqrtPrice,
+    slippage: Percentage,
+    up: bool,
+) -> TrackableResult<SqrtPrice> {
+    if slippage.is_zero() {
+        return Ok(sqrt_price);
+    }
+
+    let multiplier = if up {
+        from_result!(Percentage::from_integer(1u8).checked_add(slippage))?
+    } else {
+        from_result!(Percentage::from_integer(1u8).checked_sub(slippage))?
+    };
+
+    // square root of the price multiplier, moved to the sqrt price scale
+    let sqrt_multiplier = SqrtPrice::new(
+        (U256::from(multiplier.get()) * U256::from(10u128.pow(36)))
+            .integer_sqrt()
+            .as_u128(),
+    );
+
+    Ok(sqrt_price.big_mul(sqrt_multiplier))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -453,6 +478,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_calculate_sqrt_price_after_slippage() {
+        // no slippage
+        {
+            let sqrt_price = SqrtPrice::from_integer(1);
+            let slippage = Percentage::from_integer(0);
+            let result = calculate_sqrt_price_after_slippage(sqrt_price, slippage, true).unwrap();
+            assert_eq!(result, sqrt_price);
+        }
+        // 1% up
+        {
+            let sqrt_price = SqrtPrice::from_integer(1);
+            let slippage = Percentage::from_scale(1, 2);
+            let result = calculate_sqrt_price_after_slippage(sqrt_price, slippage, true).unwrap();
+            assert_eq!(result, SqrtPrice::new(1004987562112089027021926));
+        }
+        // 1% down
+        {
+            let sqrt_price = SqrtPrice::from_integer(1);
+            let slippage = Percentage::from_scale(1, 2);
+            let result = calculate_sqrt_price_after_slippage(sqrt_price, slippage, false).unwrap();
+            assert_eq!(result, SqrtPrice::new(994987437106619954734479));
+        }
+        // 1% up from a higher price
+        {
+            let sqrt_price = SqrtPrice::from_integer(2);
+            let slippage = Percentage::from_scale(1, 2);
+            let result = calculate_sqrt_price_after_slippage(sqrt_price, slippage, true).unwrap();
+            assert_eq!(result, SqrtPrice::new(2009975124224178054043852));
+        }
+        // more than 100% down
+        {
+            let sqrt_price = SqrtPrice::from_integer(1);
+            let slippage = Percentage::from_integer(2);
+            let result = calculate_sqrt_price_after_slippage(sqrt_price, slippage, false);
+            assert!(result.is_err());
+        }
+    }
+
     #[test]
     fn test_domain_calculate_min_amount_out() {
         let min_amount = TokenAmount::new(U256::from(0));
@@ -0,0 +1,143 @@
+use crate::IndexerError;
+use io::InvariantEvent;
+use parity_scale_codec::{Decode, DecodeAll, Encode};
+use scale_info::{TypeDef, TypeInfo};
+
+// Events of the other services share the dump but aren't a part of the protocol state
+pub const SERVICE_ROUTE: &str = "Service";
+
+// Sails prefixes the fields of an event with its name instead of the variant index
+pub fn decode_event(payload: &[u8]) -> Result<Option<InvariantEvent>, IndexerError> {
+    let mut input = payload;
+    let (route, name) =
+        <(String, String)>::decode(&mut input).map_err(|_| IndexerError::InvalidPayload)?;
+
+    if route != SERVICE_ROUTE {
+        return Ok(None);
+    }
+
+    let index = variant_index(&name).ok_or(IndexerError::UnknownEvent(name))?;
+    let encoded = [&[index][..], input].concat();
+
+    InvariantEvent::decode_all(&mut encoded.as_slice())
+        .map(Some)
+        .map_err(|_| IndexerError::InvalidPayload)
+}
+
+pub fn encode_event(event: &InvariantEvent) -> Vec<u8> {
+    let encoded = event.encode();
+    let name = variant_name(encoded[0]).expect("event variant exists");
+
+    [(SERVICE_ROUTE, name).encode(), encoded[1..].to_vec()].concat()
+}
+
+// Scale dump is a sequence of encoded payloads, so it can be appended to while capturing
+pub fn read_scale_dump(dump: &[u8]) -> Result<Vec<Vec<u8>>, IndexerError> {
+    let mut input = dump;
+    let mut payloads = vec![];
+
+    while !input.is_empty() {
+        let payload = Vec::<u8>::decode(&mut input)
+            .map_err(|_| IndexerError::InvalidDumpEntry(payloads.len()))?;
+        payloads.push(payload);
+    }
+
+    Ok(payloads)
+}
+
+pub fn write_scale_dump(payloads: &[Vec<u8>]) -> Vec<u8> {
+    payloads
+        .iter()
+        .flat_map(|payload| payload.encode())
+        .collect()
+}
+
+// Every line holds an object with the hex encoded payload as it's returned by the node
+pub fn read_json_lines(dump: &str) -> Result<Vec<Vec<u8>>, IndexerError> {
+    dump.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let entry: serde_json::Value =
+                serde_json::from_str(line).map_err(|_| IndexerError::InvalidDumpEntry(index))?;
+
+            entry["payload"]
+                .as_str()
+                .and_then(|payload| hex::decode(payload.trim_start_matches("0x")).ok())
+                .ok_or(IndexerError::InvalidDumpEntry(index))
+        })
+        .collect()
+}
+
+fn variant_index(name: &str) -> Option<u8> {
+    let TypeDef::Variant(events) = InvariantEvent::type_info().type_def else {
+        return None;
+    };
+
+    events
+        .variants
+        .iter()
+        .find(|variant| variant.name == name)
+        .map(|variant| variant.index)
+}
+
+fn variant_name(index: u8) -> Option<&'static str> {
+    let TypeDef::Variant(events) = InvariantEvent::type_info().type_def else {
+        return None;
+    };
+
+    events
+        .variants
+        .iter()
+        .find(|variant| variant.index == index)
+        .map(|variant| variant.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::{FeeTier, PoolKey};
+    use decimal::*;
+    use math::percentage::Percentage;
+    use sails_rs::ActorId;
+
+    #[test]
+    fn test_decode_event() {
+        let fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 1).unwrap();
+        let event = InvariantEvent::PoolDeprecatedEvent {
+            timestamp: 5,
+            pool_key: PoolKey::new(ActorId::from(1), ActorId::from(2), fee_tier).unwrap(),
+        };
+
+        let payload = encode_event(&event);
+        assert_eq!(decode_event(&payload), Ok(Some(event.clone())));
+
+        // other services are skipped
+        let payload = ("PositionNft", "TransferEvent", 0u64).encode();
+        assert_eq!(decode_event(&payload), Ok(None));
+
+        let payload = (SERVICE_ROUTE, "MissingEvent").encode();
+        assert_eq!(
+            decode_event(&payload),
+            Err(IndexerError::UnknownEvent("MissingEvent".into()))
+        );
+
+        let dump = write_scale_dump(&[encode_event(&event), encode_event(&event)]);
+        let payloads = read_scale_dump(&dump).unwrap();
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(decode_event(&payloads[1]), Ok(Some(event.clone())));
+
+        let line = format!(
+            "{{\"payload\":\"0x{}\"}}",
+            hex::encode(encode_event(&event))
+        );
+        let payloads = read_json_lines(&format!("{line}\n\n{line}\n")).unwrap();
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(decode_event(&payloads[0]), Ok(Some(event)));
+
+        assert_eq!(
+            read_json_lines("{\"block\":1}"),
+            Err(IndexerError::InvalidDumpEntry(0))
+        );
+    }
+}
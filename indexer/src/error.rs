@@ -0,0 +1,52 @@
+use contracts::InvariantError;
+use std::fmt;
+use traceable_result::TrackableError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexerError {
+    InvalidPayload,
+    UnknownEvent(String),
+    InvalidDumpEntry(usize),
+    PoolNotFound,
+    PositionNotFound,
+    TickNotFound,
+    // the replayed state doesn't produce the amounts reported by the event
+    ReplayMismatch(&'static str),
+    Invariant(InvariantError),
+    Math(String),
+}
+
+impl From<InvariantError> for IndexerError {
+    fn from(error: InvariantError) -> Self {
+        match error {
+            InvariantError::PoolNotFound => IndexerError::PoolNotFound,
+            InvariantError::PositionNotFound => IndexerError::PositionNotFound,
+            InvariantError::TickNotFound => IndexerError::TickNotFound,
+            error => IndexerError::Invariant(error),
+        }
+    }
+}
+
+impl From<TrackableError> for IndexerError {
+    fn from(error: TrackableError) -> Self {
+        IndexerError::Math(error.cause)
+    }
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexerError::InvalidPayload => write!(f, "payload is not an invariant event"),
+            IndexerError::UnknownEvent(name) => write!(f, "unknown event {name}"),
+            IndexerError::InvalidDumpEntry(index) => write!(f, "invalid dump entry {index}"),
+            IndexerError::PoolNotFound => write!(f, "pool not found"),
+            IndexerError::PositionNotFound => write!(f, "position not found"),
+            IndexerError::TickNotFound => write!(f, "tick not found"),
+            IndexerError::ReplayMismatch(what) => write!(f, "replayed {what} differs from event"),
+            IndexerError::Invariant(error) => write!(f, "{error:?}"),
+            IndexerError::Math(cause) => write!(f, "{cause}"),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
@@ -0,0 +1,11 @@
+pub mod dump;
+pub mod error;
+pub mod series;
+pub mod state;
+pub mod swap;
+
+pub use dump::*;
+pub use error::*;
+pub use series::*;
+pub use state::*;
+pub use swap::*;
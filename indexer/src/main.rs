@@ -0,0 +1,105 @@
+use anyhow::{anyhow, Context, Result};
+use decimal::*;
+use indexer::{read_json_lines, read_scale_dump, sqrt_price_to_price, Indexer, ReplayConfig};
+use math::percentage::Percentage;
+use sails_rs::ActorId;
+use std::{env, fs};
+
+const USAGE: &str =
+    "usage: indexer [--jsonl] [--interval <ms>] [--protocol-fee <value>] [--admin <hex>] <dump>";
+
+fn main() -> Result<()> {
+    let mut config = ReplayConfig::default();
+    let mut is_jsonl = false;
+    let mut path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or(anyhow!("{arg} requires a value\n{USAGE}"))
+        };
+
+        match arg.as_str() {
+            "--jsonl" => is_jsonl = true,
+            "--interval" => config.candle_interval = value()?.parse()?,
+            // the value is in the units of the percentage, 10^12 is 100%
+            "--protocol-fee" => config.protocol_fee = Percentage::new(value()?.parse()?),
+            "--admin" => config.admin = parse_actor_id(&value()?)?,
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return Err(anyhow!("unknown argument {arg}\n{USAGE}")),
+        }
+    }
+
+    if config.candle_interval == 0 {
+        return Err(anyhow!("candle interval can't be zero"));
+    }
+
+    let path = path.ok_or(anyhow!("dump wasn't given\n{USAGE}"))?;
+
+    let payloads = if is_jsonl {
+        read_json_lines(&fs::read_to_string(&path)?)?
+    } else {
+        read_scale_dump(&fs::read(&path)?)?
+    };
+
+    let mut indexer = Indexer::new(config);
+    for (index, payload) in payloads.iter().enumerate() {
+        indexer
+            .apply_payload(payload)
+            .with_context(|| format!("failed to apply dump entry {index}"))?;
+    }
+
+    println!("pool,token_x,token_y,fee,tick_spacing");
+    for (index, pool_key) in indexer.get_pool_keys().iter().enumerate() {
+        println!(
+            "{index},0x{},0x{},{},{}",
+            hex::encode(pool_key.token_x),
+            hex::encode(pool_key.token_y),
+            pool_key.fee_tier.fee.get(),
+            pool_key.fee_tier.tick_spacing
+        );
+    }
+
+    println!();
+    println!("pool,start,open,high,low,close,volume_x,volume_y,fee_x,fee_y");
+    for (index, pool_key) in indexer.get_pool_keys().iter().enumerate() {
+        for candle in indexer.get_series(*pool_key)?.candles.get_all() {
+            println!(
+                "{index},{},{},{},{},{},{},{},{},{}",
+                candle.start_timestamp,
+                sqrt_price_to_price(candle.open),
+                sqrt_price_to_price(candle.high),
+                sqrt_price_to_price(candle.low),
+                sqrt_price_to_price(candle.close),
+                candle.volume_x.get(),
+                candle.volume_y.get(),
+                candle.fee_x.get(),
+                candle.fee_y.get()
+            );
+        }
+    }
+
+    println!();
+    println!("pool,timestamp,amount_x,amount_y");
+    for (index, pool_key) in indexer.get_pool_keys().iter().enumerate() {
+        for point in indexer.get_series(*pool_key)?.tvl.iter() {
+            println!(
+                "{index},{},{},{}",
+                point.timestamp,
+                point.amount_x.get(),
+                point.amount_y.get()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_actor_id(value: &str) -> Result<ActorId> {
+    let bytes: [u8; 32] = hex::decode(value.trim_start_matches("0x"))?
+        .try_into()
+        .map_err(|_| anyhow!("address must be 32 bytes long"))?;
+
+    Ok(ActorId::from(bytes))
+}
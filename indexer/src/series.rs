@@ -0,0 +1,147 @@
+use decimal::*;
+use math::{sqrt_price::SqrtPrice, token_amount::TokenAmount};
+
+// Timestamps of the events are in milliseconds
+pub const DEFAULT_CANDLE_INTERVAL: u64 = 60 * 60 * 1000;
+
+// Prices are kept as sqrt prices of token x in token y, same as in the pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Candle {
+    pub start_timestamp: u64,
+    pub open: SqrtPrice,
+    pub high: SqrtPrice,
+    pub low: SqrtPrice,
+    pub close: SqrtPrice,
+    pub volume_x: TokenAmount,
+    pub volume_y: TokenAmount,
+    pub fee_x: TokenAmount,
+    pub fee_y: TokenAmount,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TvlPoint {
+    pub timestamp: u64,
+    pub amount_x: TokenAmount,
+    pub amount_y: TokenAmount,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Trade {
+    pub timestamp: u64,
+    pub start_sqrt_price: SqrtPrice,
+    pub target_sqrt_price: SqrtPrice,
+    pub amount_x: TokenAmount,
+    pub amount_y: TokenAmount,
+    pub fee_x: TokenAmount,
+    pub fee_y: TokenAmount,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candles {
+    interval: u64,
+    candles: Vec<Candle>,
+}
+
+impl Candles {
+    pub fn new(interval: u64) -> Self {
+        assert!(interval > 0, "candle interval can't be zero");
+
+        Self {
+            interval,
+            candles: vec![],
+        }
+    }
+
+    pub fn add(&mut self, trade: Trade) {
+        let start_timestamp = trade.timestamp - trade.timestamp % self.interval;
+        let (low, high) = if trade.start_sqrt_price < trade.target_sqrt_price {
+            (trade.start_sqrt_price, trade.target_sqrt_price)
+        } else {
+            (trade.target_sqrt_price, trade.start_sqrt_price)
+        };
+
+        match self.candles.last_mut() {
+            Some(candle) if candle.start_timestamp == start_timestamp => {
+                if high > candle.high {
+                    candle.high = high;
+                }
+                if low < candle.low {
+                    candle.low = low;
+                }
+                candle.close = trade.target_sqrt_price;
+                candle.volume_x += trade.amount_x;
+                candle.volume_y += trade.amount_y;
+                candle.fee_x += trade.fee_x;
+                candle.fee_y += trade.fee_y;
+            }
+            _ => self.candles.push(Candle {
+                start_timestamp,
+                open: trade.start_sqrt_price,
+                high,
+                low,
+                close: trade.target_sqrt_price,
+                volume_x: trade.amount_x,
+                volume_y: trade.amount_y,
+                fee_x: trade.fee_x,
+                fee_y: trade.fee_y,
+            }),
+        }
+    }
+
+    pub fn get_all(&self) -> &[Candle] {
+        &self.candles
+    }
+}
+
+// Converts the sqrt price to a float price, it's only meant for presenting the series
+pub fn sqrt_price_to_price(sqrt_price: SqrtPrice) -> f64 {
+    let sqrt_price = sqrt_price.get() as f64 / SqrtPrice::one().get() as f64;
+    sqrt_price * sqrt_price
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sails_rs::U256;
+
+    fn trade(timestamp: u64, start: u128, target: u128, amount_x: u64) -> Trade {
+        Trade {
+            timestamp,
+            start_sqrt_price: SqrtPrice::from_integer(start),
+            target_sqrt_price: SqrtPrice::from_integer(target),
+            amount_x: TokenAmount::new(U256::from(amount_x)),
+            amount_y: TokenAmount::new(U256::from(0)),
+            fee_x: TokenAmount::new(U256::from(1)),
+            fee_y: TokenAmount::new(U256::from(0)),
+        }
+    }
+
+    #[test]
+    fn test_candles() {
+        let mut candles = Candles::new(1000);
+
+        candles.add(trade(1200, 5, 4, 10));
+        candles.add(trade(1500, 4, 7, 20));
+        candles.add(trade(1999, 7, 6, 30));
+        candles.add(trade(3000, 6, 5, 40));
+
+        let all = candles.get_all();
+        assert_eq!(all.len(), 2);
+
+        assert_eq!(all[0].start_timestamp, 1000);
+        assert_eq!(all[0].open, SqrtPrice::from_integer(5));
+        assert_eq!(all[0].high, SqrtPrice::from_integer(7));
+        assert_eq!(all[0].low, SqrtPrice::from_integer(4));
+        assert_eq!(all[0].close, SqrtPrice::from_integer(6));
+        assert_eq!(all[0].volume_x, TokenAmount::new(U256::from(60)));
+        assert_eq!(all[0].fee_x, TokenAmount::new(U256::from(3)));
+
+        // empty intervals don't produce candles
+        assert_eq!(all[1].start_timestamp, 3000);
+        assert_eq!(all[1].open, SqrtPrice::from_integer(6));
+        assert_eq!(all[1].close, SqrtPrice::from_integer(5));
+        assert_eq!(all[1].volume_x, TokenAmount::new(U256::from(40)));
+
+        assert_eq!(sqrt_price_to_price(SqrtPrice::from_integer(3)), 9.0);
+    }
+}
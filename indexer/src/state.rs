@@ -0,0 +1,758 @@
+use crate::{
+    decode_event, Candles, IndexerError, SwapRecord, Trade, TvlPoint, DEFAULT_CANDLE_INTERVAL,
+};
+use contracts::{Pool, PoolId, PoolKey, Position, PositionId, Tick, Tickmap, Ticks};
+use decimal::*;
+use io::InvariantEvent;
+use math::{
+    clamm::calculate_amount_delta, liquidity::Liquidity, percentage::Percentage,
+    sqrt_price::SqrtPrice, token_amount::TokenAmount,
+};
+use sails_rs::{ActorId, U256};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+// Values that are set at the initialization of the contract, later changes of the protocol fee
+// are replayed from its events
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplayConfig {
+    pub admin: ActorId,
+    pub protocol_fee: Percentage,
+    pub candle_interval: u64,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            admin: ActorId::from(0),
+            protocol_fee: Percentage::default(),
+            candle_interval: DEFAULT_CANDLE_INTERVAL,
+        }
+    }
+}
+
+// Series outlive the pool, so the history is still available after it's removed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolSeries {
+    pub volume_x: TokenAmount,
+    pub volume_y: TokenAmount,
+    pub fee_x: TokenAmount,
+    pub fee_y: TokenAmount,
+    pub candles: Candles,
+    pub tvl: Vec<TvlPoint>,
+}
+
+impl PoolSeries {
+    fn new(candle_interval: u64) -> Self {
+        Self {
+            volume_x: TokenAmount::new(U256::from(0)),
+            volume_y: TokenAmount::new(U256::from(0)),
+            fee_x: TokenAmount::new(U256::from(0)),
+            fee_y: TokenAmount::new(U256::from(0)),
+            candles: Candles::new(candle_interval),
+            tvl: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Indexer {
+    pub(crate) config: ReplayConfig,
+    pub(crate) pools: HashMap<PoolKey, Pool>,
+    pub(crate) ticks: Ticks,
+    pub(crate) tickmap: Tickmap,
+    positions: HashMap<PositionId, (ActorId, Position)>,
+    // settled limit orders keep their liquidity but are no longer a part of the pool
+    settled_limit_orders: HashSet<PositionId>,
+    series: HashMap<PoolKey, PoolSeries>,
+    // pools in the order of creation, including the removed ones
+    pool_keys: Vec<PoolKey>,
+    // a pool keeps its id when it's created again after the removal
+    pool_ids: HashMap<PoolKey, PoolId>,
+    // cross tick event always directly precedes the swap event it belongs to
+    crossed_ticks: Option<(PoolKey, Vec<i32>)>,
+}
+
+impl Indexer {
+    pub fn new(config: ReplayConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    // Payloads of the other services are skipped
+    pub fn apply_payload(&mut self, payload: &[u8]) -> Result<(), IndexerError> {
+        match decode_event(payload)? {
+            Some(event) => self.apply(event),
+            None => Ok(()),
+        }
+    }
+
+    pub fn apply(&mut self, event: InvariantEvent) -> Result<(), IndexerError> {
+        if !matches!(event, InvariantEvent::SwapEvent { .. }) && self.crossed_ticks.is_some() {
+            return Err(IndexerError::ReplayMismatch("cross tick"));
+        }
+
+        match event {
+            InvariantEvent::PoolCreatedEvent {
+                timestamp,
+                pool_key,
+                init_sqrt_price,
+                init_tick,
+                ..
+            } => {
+                let pool = Pool::create(
+                    init_sqrt_price,
+                    init_tick,
+                    timestamp,
+                    pool_key.fee_tier.tick_spacing,
+                    self.config.admin,
+                )?;
+                self.pools.insert(pool_key, pool);
+
+                if let Entry::Vacant(entry) = self.series.entry(pool_key) {
+                    entry.insert(PoolSeries::new(self.config.candle_interval));
+                    self.pool_ids
+                        .insert(pool_key, self.pool_keys.len() as PoolId);
+                    self.pool_keys.push(pool_key);
+                }
+            }
+            InvariantEvent::PositionCreatedEvent {
+                timestamp,
+                address,
+                position_id,
+                pool_key,
+                liquidity_delta,
+                lower_tick,
+                upper_tick,
+                current_sqrt_price,
+            } => {
+                self.create_position(
+                    timestamp,
+                    address,
+                    position_id,
+                    pool_key,
+                    liquidity_delta,
+                    lower_tick,
+                    upper_tick,
+                )?;
+                self.check_sqrt_price(pool_key, current_sqrt_price)?;
+                self.record_tvl(timestamp, pool_key)?;
+            }
+            InvariantEvent::PositionLiquidityIncreasedEvent {
+                timestamp,
+                pool_key,
+                position_id,
+                liquidity_delta,
+                current_sqrt_price,
+                ..
+            } => {
+                self.modify_position(position_id, |position, pool, lower, upper| {
+                    let sqrt_price = pool.sqrt_price;
+                    position
+                        .increase_liquidity(
+                            pool,
+                            lower,
+                            upper,
+                            timestamp,
+                            liquidity_delta,
+                            sqrt_price,
+                            sqrt_price,
+                        )
+                        .map(|_| ())
+                })?;
+                self.check_sqrt_price(pool_key, current_sqrt_price)?;
+                self.record_tvl(timestamp, pool_key)?;
+            }
+            InvariantEvent::PositionLiquidityDecreasedEvent {
+                timestamp,
+                pool_key,
+                position_id,
+                liquidity_delta,
+                current_sqrt_price,
+                ..
+            } => {
+                self.modify_position(position_id, |position, pool, lower, upper| {
+                    position
+                        .decrease_liquidity(pool, lower, upper, timestamp, liquidity_delta)
+                        .map(|_| ())
+                })?;
+                self.check_sqrt_price(pool_key, current_sqrt_price)?;
+                self.record_tvl(timestamp, pool_key)?;
+            }
+            InvariantEvent::PositionRemovedEvent {
+                timestamp,
+                position_id,
+                pool_key,
+                liquidity,
+                sqrt_price,
+                ..
+            } => {
+                self.remove_position(timestamp, position_id, liquidity)?;
+                self.check_sqrt_price(pool_key, sqrt_price)?;
+                self.record_tvl(timestamp, pool_key)?;
+            }
+            InvariantEvent::PositionTransferredEvent {
+                position_id, to, ..
+            } => {
+                let (owner, _) = self
+                    .positions
+                    .get_mut(&position_id)
+                    .ok_or(IndexerError::PositionNotFound)?;
+                *owner = to;
+            }
+            InvariantEvent::FeeClaimedEvent {
+                timestamp,
+                position_id,
+                amount_x,
+                amount_y,
+                ..
+            } => {
+                let mut claimed = (TokenAmount::default(), TokenAmount::default());
+                if self.settled_limit_orders.contains(&position_id) {
+                    let (_, position) = self
+                        .positions
+                        .get_mut(&position_id)
+                        .ok_or(IndexerError::PositionNotFound)?;
+                    claimed = (position.tokens_owed_x, position.tokens_owed_y);
+                    position.tokens_owed_x = TokenAmount::new(U256::from(0));
+                    position.tokens_owed_y = TokenAmount::new(U256::from(0));
+                } else {
+                    self.modify_position(position_id, |position, pool, lower, upper| {
+                        claimed = position.claim_fee(pool, upper, lower, timestamp);
+                        Ok(())
+                    })?;
+                }
+
+                if claimed != (amount_x, amount_y) {
+                    return Err(IndexerError::ReplayMismatch("fee claim"));
+                }
+            }
+            InvariantEvent::CrossTickEvent {
+                pool_key, indexes, ..
+            } => {
+                self.crossed_ticks = Some((pool_key, indexes));
+            }
+            InvariantEvent::SwapEvent {
+                timestamp,
+                pool_key,
+                amount_in,
+                amount_out,
+                fee,
+                start_sqrt_price,
+                target_sqrt_price,
+                x_to_y,
+                ..
+            } => {
+                let crossed_ticks = match self.crossed_ticks.take() {
+                    Some((key, indexes)) if key == pool_key => indexes,
+                    Some(_) => return Err(IndexerError::ReplayMismatch("cross tick")),
+                    None => vec![],
+                };
+
+                let result = self.replay_swap(&SwapRecord {
+                    timestamp,
+                    pool_key,
+                    x_to_y,
+                    amount_in,
+                    amount_out,
+                    fee,
+                    start_sqrt_price,
+                    target_sqrt_price,
+                    crossed_ticks,
+                })?;
+
+                let pool_id = self.pool_id(pool_key)?;
+                for tick in result.ticks.iter() {
+                    self.ticks.update(pool_id, tick.index, *tick)?;
+                }
+                self.pools.insert(pool_key, result.pool);
+
+                let (amount_x, amount_y) = if x_to_y {
+                    (amount_in, amount_out)
+                } else {
+                    (amount_out, amount_in)
+                };
+                let zero = TokenAmount::new(U256::from(0));
+                let (fee_x, fee_y) = if x_to_y { (fee, zero) } else { (zero, fee) };
+
+                let series = self.series_mut(pool_key)?;
+                series.volume_x += amount_x;
+                series.volume_y += amount_y;
+                series.fee_x += fee_x;
+                series.fee_y += fee_y;
+                series.candles.add(Trade {
+                    timestamp,
+                    start_sqrt_price,
+                    target_sqrt_price,
+                    amount_x,
+                    amount_y,
+                    fee_x,
+                    fee_y,
+                });
+
+                self.record_tvl(timestamp, pool_key)?;
+            }
+            InvariantEvent::FlashLoanEvent {
+                pool_key,
+                fee_x,
+                fee_y,
+                ..
+            } => {
+                let protocol_fee = self.config.protocol_fee;
+                let pool = self.pool_mut(pool_key)?;
+                let protocol_fee = pool.effective_protocol_fee(protocol_fee);
+
+                pool.add_fee(fee_x, true, protocol_fee)?;
+                pool.add_fee(fee_y, false, protocol_fee)?;
+
+                let series = self.series_mut(pool_key)?;
+                series.fee_x += fee_x;
+                series.fee_y += fee_y;
+            }
+            InvariantEvent::ProtocolFeeWithdrawnEvent {
+                pool_key,
+                amount_x,
+                amount_y,
+                ..
+            } => {
+                let withdrawn = self.pool_mut(pool_key)?.withdraw_protocol_fee(pool_key);
+
+                if withdrawn != (amount_x, amount_y) {
+                    return Err(IndexerError::ReplayMismatch("protocol fee"));
+                }
+            }
+            InvariantEvent::FeeReceiverChangedEvent {
+                pool_key,
+                fee_receiver,
+                ..
+            } => {
+                self.pool_mut(pool_key)?.fee_receiver = fee_receiver;
+            }
+            InvariantEvent::PauseFlagsChangedEvent {
+                pool_key: Some(pool_key),
+                pause,
+                ..
+            } => {
+                self.pool_mut(pool_key)?.pause = pause;
+            }
+            InvariantEvent::ProtocolFeeChangedEvent { protocol_fee, .. } => {
+                self.config.protocol_fee = protocol_fee;
+            }
+            InvariantEvent::PoolProtocolFeeChangedEvent {
+                pool_key,
+                protocol_fee,
+                ..
+            } => {
+                self.pool_mut(pool_key)?.protocol_fee = protocol_fee;
+            }
+            InvariantEvent::PoolDynamicFeeChangedEvent {
+                pool_key,
+                dynamic_fee,
+                ..
+            } => {
+                self.pool_mut(pool_key)?.dynamic_fee = dynamic_fee;
+            }
+            InvariantEvent::PoolDeprecatedEvent { pool_key, .. } => {
+                self.pool_mut(pool_key)?.deprecated = true;
+            }
+            InvariantEvent::PoolRemovedEvent { pool_key, .. } => {
+                self.pools
+                    .remove(&pool_key)
+                    .ok_or(IndexerError::PoolNotFound)?;
+
+                let pool_id = self.pool_id(pool_key)?;
+                self.tickmap.bitmap.remove(&pool_id);
+                self.ticks.remove_pool(pool_id);
+            }
+            // balances, operators and the global configuration don't affect the state of the pools
+            InvariantEvent::LimitOrderSettledEvent {
+                timestamp,
+                pool_key,
+                position_id,
+                amount_x,
+                amount_y,
+            } => {
+                self.settle_limit_order(timestamp, position_id, amount_x, amount_y)?;
+                self.record_tvl(timestamp, pool_key)?;
+            }
+            InvariantEvent::PauseFlagsChangedEvent { pool_key: None, .. }
+            | InvariantEvent::FeeTierAddedEvent { .. }
+            | InvariantEvent::FeeTierRemovedEvent { .. }
+            | InvariantEvent::TokenDepositedEvent { .. }
+            | InvariantEvent::TokenWithdrawnEvent { .. }
+            | InvariantEvent::OperatorSetEvent { .. }
+            | InvariantEvent::PositionApprovedEvent { .. } => {}
+        }
+
+        Ok(())
+    }
+
+    pub fn get_pool(&self, pool_key: PoolKey) -> Result<&Pool, IndexerError> {
+        self.pools.get(&pool_key).ok_or(IndexerError::PoolNotFound)
+    }
+
+    pub fn get_pool_keys(&self) -> &[PoolKey] {
+        &self.pool_keys
+    }
+
+    pub fn get_tick(&self, pool_key: PoolKey, index: i32) -> Result<&Tick, IndexerError> {
+        Ok(self.ticks.get(self.pool_id(pool_key)?, index)?)
+    }
+
+    pub fn is_tick_initialized(&self, pool_key: PoolKey, index: i32) -> bool {
+        self.pool_id(pool_key).map_or(false, |pool_id| {
+            self.tickmap
+                .get(index, pool_key.fee_tier.tick_spacing, pool_id)
+        })
+    }
+
+    pub fn get_position(&self, position_id: PositionId) -> Result<&Position, IndexerError> {
+        self.positions
+            .get(&position_id)
+            .map(|(_, position)| position)
+            .ok_or(IndexerError::PositionNotFound)
+    }
+
+    pub fn get_position_owner(&self, position_id: PositionId) -> Result<ActorId, IndexerError> {
+        self.positions
+            .get(&position_id)
+            .map(|(owner, _)| *owner)
+            .ok_or(IndexerError::PositionNotFound)
+    }
+
+    pub fn get_series(&self, pool_key: PoolKey) -> Result<&PoolSeries, IndexerError> {
+        self.series.get(&pool_key).ok_or(IndexerError::PoolNotFound)
+    }
+
+    pub(crate) fn pool_id(&self, pool_key: PoolKey) -> Result<PoolId, IndexerError> {
+        self.pool_ids
+            .get(&pool_key)
+            .copied()
+            .ok_or(IndexerError::PoolNotFound)
+    }
+
+    fn pool_mut(&mut self, pool_key: PoolKey) -> Result<&mut Pool, IndexerError> {
+        self.pools
+            .get_mut(&pool_key)
+            .ok_or(IndexerError::PoolNotFound)
+    }
+
+    fn series_mut(&mut self, pool_key: PoolKey) -> Result<&mut PoolSeries, IndexerError> {
+        self.series
+            .get_mut(&pool_key)
+            .ok_or(IndexerError::PoolNotFound)
+    }
+
+    fn check_sqrt_price(
+        &self,
+        pool_key: PoolKey,
+        sqrt_price: SqrtPrice,
+    ) -> Result<(), IndexerError> {
+        if self.get_pool(pool_key)?.sqrt_price != sqrt_price {
+            return Err(IndexerError::ReplayMismatch("sqrt price"));
+        }
+
+        Ok(())
+    }
+
+    // Mirrors the creation of the ticks done by the contract, the block number of the position
+    // isn't a part of the event so it's left at zero
+    #[allow(clippy::too_many_arguments)]
+    fn create_position(
+        &mut self,
+        timestamp: u64,
+        owner: ActorId,
+        position_id: PositionId,
+        pool_key: PoolKey,
+        liquidity_delta: Liquidity,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> Result<(), IndexerError> {
+        let mut pool = self.get_pool(pool_key)?.clone();
+
+        let (mut lower_tick, should_add_lower) =
+            self.get_or_create_tick(timestamp, pool_key, lower_tick)?;
+        let (mut upper_tick, should_add_upper) =
+            self.get_or_create_tick(timestamp, pool_key, upper_tick)?;
+
+        let sqrt_price = pool.sqrt_price;
+        let (position, _, _) = Position::create(
+            &mut pool,
+            pool_key,
+            &mut lower_tick,
+            &mut upper_tick,
+            timestamp,
+            liquidity_delta,
+            sqrt_price,
+            sqrt_price,
+            0,
+            pool_key.fee_tier.tick_spacing,
+        )?;
+
+        self.pools.insert(pool_key, pool);
+        self.positions.insert(position_id, (owner, position));
+
+        let pool_id = self.pool_id(pool_key)?;
+
+        for (tick, should_add) in [
+            (lower_tick, should_add_lower),
+            (upper_tick, should_add_upper),
+        ] {
+            if should_add {
+                self.ticks.add(pool_id, tick.index, tick)?;
+                self.tickmap
+                    .flip(true, tick.index, pool_key.fee_tier.tick_spacing, pool_id);
+            } else {
+                self.ticks.update(pool_id, tick.index, tick)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_or_create_tick(
+        &self,
+        timestamp: u64,
+        pool_key: PoolKey,
+        index: i32,
+    ) -> Result<(Tick, bool), IndexerError> {
+        if let Ok(tick) = self.ticks.get(self.pool_id(pool_key)?, index) {
+            return Ok((*tick, false));
+        }
+
+        let mut pool = self.get_pool(pool_key)?.clone();
+        pool.update_seconds_per_liquidity_global(timestamp)?;
+
+        Ok((Tick::create(index, &pool, timestamp), true))
+    }
+
+    fn modify_position(
+        &mut self,
+        position_id: PositionId,
+        modify: impl FnOnce(
+            &mut Position,
+            &mut Pool,
+            &mut Tick,
+            &mut Tick,
+        ) -> Result<(), contracts::InvariantError>,
+    ) -> Result<(), IndexerError> {
+        let (_, mut position) = *self
+            .positions
+            .get(&position_id)
+            .ok_or(IndexerError::PositionNotFound)?;
+        let pool_key = position.pool_key;
+
+        let mut pool = self.get_pool(pool_key)?.clone();
+        let pool_id = self.pool_id(pool_key)?;
+        let mut lower_tick = *self.ticks.get(pool_id, position.lower_tick_index)?;
+        let mut upper_tick = *self.ticks.get(pool_id, position.upper_tick_index)?;
+
+        modify(&mut position, &mut pool, &mut lower_tick, &mut upper_tick)?;
+
+        self.pools.insert(pool_key, pool);
+        self.ticks.update(pool_id, lower_tick.index, lower_tick)?;
+        self.ticks.update(pool_id, upper_tick.index, upper_tick)?;
+        if let Some((_, stored)) = self.positions.get_mut(&position_id) {
+            *stored = position;
+        }
+
+        Ok(())
+    }
+
+    fn remove_position(
+        &mut self,
+        timestamp: u64,
+        position_id: PositionId,
+        liquidity: Liquidity,
+    ) -> Result<(), IndexerError> {
+        let (_, mut position) = self
+            .positions
+            .remove(&position_id)
+            .ok_or(IndexerError::PositionNotFound)?;
+
+        if position.liquidity != liquidity {
+            return Err(IndexerError::ReplayMismatch("removed liquidity"));
+        }
+
+        if !self.settled_limit_orders.remove(&position_id) {
+            self.withdraw_liquidity(timestamp, &mut position)?;
+        }
+
+        Ok(())
+    }
+
+    fn settle_limit_order(
+        &mut self,
+        timestamp: u64,
+        position_id: PositionId,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+    ) -> Result<(), IndexerError> {
+        let (_, mut position) = *self
+            .positions
+            .get(&position_id)
+            .ok_or(IndexerError::PositionNotFound)?;
+
+        if self.withdraw_liquidity(timestamp, &mut position)? != (amount_x, amount_y) {
+            return Err(IndexerError::ReplayMismatch("limit order"));
+        }
+
+        position.tokens_owed_x = amount_x;
+        position.tokens_owed_y = amount_y;
+        if let Some((_, stored)) = self.positions.get_mut(&position_id) {
+            *stored = position;
+        }
+        self.settled_limit_orders.insert(position_id);
+
+        Ok(())
+    }
+
+    // Takes the whole liquidity of the position out of the pool and its ticks, the ticks left
+    // without liquidity are deinitialized
+    fn withdraw_liquidity(
+        &mut self,
+        timestamp: u64,
+        position: &mut Position,
+    ) -> Result<(TokenAmount, TokenAmount), IndexerError> {
+        let pool_key = position.pool_key;
+
+        let mut pool = self.get_pool(pool_key)?.clone();
+        let pool_id = self.pool_id(pool_key)?;
+        let mut lower_tick = *self.ticks.get(pool_id, position.lower_tick_index)?;
+        let mut upper_tick = *self.ticks.get(pool_id, position.upper_tick_index)?;
+
+        let (amount_x, amount_y, remove_lower_tick, remove_upper_tick) = position.remove(
+            &mut pool,
+            timestamp,
+            &mut lower_tick,
+            &mut upper_tick,
+            pool_key.fee_tier.tick_spacing,
+        );
+
+        self.pools.insert(pool_key, pool);
+
+        for (tick, remove) in [
+            (lower_tick, remove_lower_tick),
+            (upper_tick, remove_upper_tick),
+        ] {
+            if remove {
+                self.tickmap
+                    .flip(false, tick.index, pool_key.fee_tier.tick_spacing, pool_id);
+                self.ticks.remove(pool_id, tick.index)?;
+            } else {
+                self.ticks.update(pool_id, tick.index, tick)?;
+            }
+        }
+
+        Ok((amount_x, amount_y))
+    }
+
+    // Value locked in the liquidity of the positions at the current price, unclaimed fees aren't
+    // included
+    fn record_tvl(&mut self, timestamp: u64, pool_key: PoolKey) -> Result<(), IndexerError> {
+        let pool = self.get_pool(pool_key)?;
+        let mut amount_x = TokenAmount::new(U256::from(0));
+        let mut amount_y = TokenAmount::new(U256::from(0));
+
+        for (position_id, (_, position)) in &self.positions {
+            if position.pool_key != pool_key
+                || position.liquidity.is_zero()
+                || self.settled_limit_orders.contains(position_id)
+            {
+                continue;
+            }
+
+            let (x, y, _) = calculate_amount_delta(
+                pool.current_tick_index,
+                pool.sqrt_price,
+                position.liquidity,
+                false,
+                position.upper_tick_index,
+                position.lower_tick_index,
+            )?;
+            amount_x += x;
+            amount_y += y;
+        }
+
+        let point = TvlPoint {
+            timestamp,
+            amount_x,
+            amount_y,
+        };
+
+        let tvl = &mut self.series_mut(pool_key)?.tvl;
+        match tvl.last_mut() {
+            Some(last) if last.timestamp == timestamp => *last = point,
+            _ => tvl.push(point),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contracts::FeeTier;
+
+    #[test]
+    fn test_replay_position() {
+        let token_x = ActorId::from(106);
+        let token_y = ActorId::from(107);
+        let user = ActorId::from(2);
+        let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+        let sqrt_price = SqrtPrice::from_integer(1);
+        let liquidity = Liquidity::from_integer(1000000);
+
+        let mut indexer = Indexer::new(ReplayConfig::default());
+        indexer
+            .apply(InvariantEvent::PoolCreatedEvent {
+                timestamp: 1000,
+                address: user,
+                pool_key,
+                init_sqrt_price: sqrt_price,
+                init_tick: 0,
+            })
+            .unwrap();
+        indexer
+            .apply(InvariantEvent::PositionCreatedEvent {
+                timestamp: 2000,
+                address: user,
+                position_id: 0,
+                pool_key,
+                liquidity_delta: liquidity,
+                lower_tick: -20,
+                upper_tick: 10,
+                current_sqrt_price: sqrt_price,
+            })
+            .unwrap();
+
+        assert_eq!(indexer.get_pool_keys(), &[pool_key]);
+        assert_eq!(indexer.get_pool(pool_key).unwrap().liquidity, liquidity);
+        assert_eq!(indexer.get_position_owner(0).unwrap(), user);
+        assert!(indexer.is_tick_initialized(pool_key, -20));
+        assert!(indexer.is_tick_initialized(pool_key, 10));
+        assert!(!indexer.is_tick_initialized(pool_key, 0));
+
+        let (amount_x, amount_y, _) =
+            calculate_amount_delta(0, sqrt_price, liquidity, false, 10, -20).unwrap();
+        let tvl = &indexer.get_series(pool_key).unwrap().tvl;
+        assert_eq!(tvl.len(), 1);
+        assert_eq!((tvl[0].amount_x, tvl[0].amount_y), (amount_x, amount_y));
+
+        // the price of the event has to match the replayed pool
+        let result = indexer.apply(InvariantEvent::PositionCreatedEvent {
+            timestamp: 3000,
+            address: user,
+            position_id: 1,
+            pool_key,
+            liquidity_delta: liquidity,
+            lower_tick: -20,
+            upper_tick: 10,
+            current_sqrt_price: SqrtPrice::from_integer(2),
+        });
+        assert_eq!(result, Err(IndexerError::ReplayMismatch("sqrt price")));
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{Indexer, IndexerError};
+use contracts::{simulate_swap, PoolKey};
+use decimal::*;
+use io::CalculateSwapResult;
+use math::{sqrt_price::SqrtPrice, token_amount::TokenAmount, MAX_SQRT_PRICE, MIN_SQRT_PRICE};
+
+// Everything the swap event and the preceding cross tick event tell about the swap
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapRecord {
+    pub timestamp: u64,
+    pub pool_key: PoolKey,
+    pub x_to_y: bool,
+    pub amount_in: TokenAmount,
+    pub amount_out: TokenAmount,
+    pub fee: TokenAmount,
+    pub start_sqrt_price: SqrtPrice,
+    pub target_sqrt_price: SqrtPrice,
+    pub crossed_ticks: Vec<i32>,
+}
+
+impl SwapRecord {
+    fn matches(&self, result: &CalculateSwapResult) -> bool {
+        result.amount_in == self.amount_in
+            && result.amount_out == self.amount_out
+            && result.fee == self.fee
+            && result.target_sqrt_price == self.target_sqrt_price
+            && result
+                .ticks
+                .iter()
+                .map(|tick| tick.index)
+                .eq(self.crossed_ticks.iter().copied())
+    }
+}
+
+impl Indexer {
+    // Events don't carry the requested amount nor the price limit, so the swap is replayed
+    // with the parameters that could have produced it until one gives the same result
+    pub(crate) fn replay_swap(
+        &self,
+        record: &SwapRecord,
+    ) -> Result<CalculateSwapResult, IndexerError> {
+        let pool = self
+            .pools
+            .get(&record.pool_key)
+            .ok_or(IndexerError::PoolNotFound)?;
+
+        if pool.sqrt_price != record.start_sqrt_price {
+            return Err(IndexerError::ReplayMismatch("swap start price"));
+        }
+
+        let extreme_limit = if record.x_to_y {
+            SqrtPrice::new(MIN_SQRT_PRICE)
+        } else {
+            SqrtPrice::new(MAX_SQRT_PRICE)
+        };
+
+        let candidates = [
+            (record.amount_in, true, extreme_limit),
+            (record.amount_out, false, extreme_limit),
+            (record.amount_in, true, record.target_sqrt_price),
+            (record.amount_out, false, record.target_sqrt_price),
+        ];
+
+        candidates
+            .into_iter()
+            .filter_map(|(amount, by_amount_in, sqrt_price_limit)| {
+                self.simulate_swap(record, amount, by_amount_in, sqrt_price_limit)
+                    .ok()
+            })
+            .find(|result| record.matches(result))
+            .ok_or(IndexerError::ReplayMismatch("swap"))
+    }
+
+    // Runs the same calculation as the contract on the stored state
+    fn simulate_swap(
+        &self,
+        record: &SwapRecord,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    ) -> Result<CalculateSwapResult, IndexerError> {
+        let pool = self
+            .pools
+            .get(&record.pool_key)
+            .cloned()
+            .ok_or(IndexerError::PoolNotFound)?;
+
+        let simulation = simulate_swap(
+            pool,
+            record.pool_key,
+            self.pool_id(record.pool_key)?,
+            &self.ticks,
+            &self.tickmap,
+            self.config.protocol_fee,
+            record.timestamp,
+            record.x_to_y,
+            amount,
+            by_amount_in,
+            sqrt_price_limit,
+        )?;
+
+        Ok(simulation.into())
+    }
+}
@@ -0,0 +1,360 @@
+use contracts::*;
+use decimal::*;
+use gtest::{Program, RunResult, System};
+use indexer::{read_scale_dump, write_scale_dump, Indexer, ReplayConfig};
+use io::InvariantConfig;
+use math::{
+    clamm::calculate_amount_delta, liquidity::Liquidity, percentage::Percentage,
+    sqrt_price::SqrtPrice, token_amount::TokenAmount, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
+use sails_rs::{prelude::*, ActorId};
+
+const EVENT_ADDRESS: u64 = 0;
+const ADMIN: u64 = 1;
+const REGULAR_USER_1: u64 = 2;
+const REGULAR_USER_2: u64 = 3;
+const PROGRAM_OWNER: u64 = 4;
+const INVARIANT_ID: u64 = 105;
+const TOKEN_X_ID: u64 = 106;
+const TOKEN_Y_ID: u64 = 107;
+
+fn send(
+    program: &Program,
+    user: u64,
+    service: &str,
+    action: &str,
+    payload: impl Encode,
+) -> RunResult {
+    let request = [service.encode(), action.encode(), payload.encode()].concat();
+    program.send_bytes(user, request)
+}
+
+fn query<T: Decode>(program: &Program, action: &str, payload: impl Encode) -> T {
+    let res = send(program, PROGRAM_OWNER, "Service", action, payload);
+    assert!(!res.main_failed());
+
+    let reply = res.log().last().expect("query has a reply").payload();
+    <(String, String, T)>::decode(&mut &reply[..])
+        .expect("failed to decode the reply")
+        .2
+}
+
+// Only the events are kept, the reply to the message isn't a part of the dump
+fn capture(payloads: &mut Vec<Vec<u8>>, res: RunResult) {
+    assert!(!res.main_failed());
+    payloads.extend(
+        res.log()
+            .iter()
+            .filter(|log| {
+                log.source() == INVARIANT_ID.into() && log.destination() == EVENT_ADDRESS.into()
+            })
+            .map(|log| log.payload().to_vec()),
+    );
+}
+
+fn init_programs(
+    sys: &System,
+    protocol_fee: Percentage,
+) -> (Program<'_>, Program<'_>, Program<'_>) {
+    let invariant = Program::from_binary_with_id(
+        sys,
+        INVARIANT_ID,
+        include_bytes!("../../target/wasm32-unknown-unknown/release/invariant_wasm.opt.wasm"),
+    );
+    let config = InvariantConfig {
+        admin: ADMIN.into(),
+        protocol_fee,
+        pause: PauseFlags::default(),
+    };
+    assert!(!invariant
+        .send_bytes(PROGRAM_OWNER, ["New".encode(), config.encode()].concat())
+        .main_failed());
+
+    let token_bytes =
+        include_bytes!("../../target/wasm32-unknown-unknown/release/extended_vft_wasm.opt.wasm");
+    let token_x = Program::from_binary_with_id(sys, TOKEN_X_ID, token_bytes);
+    let token_y = Program::from_binary_with_id(sys, TOKEN_Y_ID, token_bytes);
+    let init = ("TokenName".to_owned(), "TokenSymbol".to_owned(), 10_u8);
+    for token in [&token_x, &token_y] {
+        assert!(!token
+            .send_bytes(PROGRAM_OWNER, ["New".encode(), init.encode()].concat())
+            .main_failed());
+    }
+
+    (invariant, token_x, token_y)
+}
+
+#[test]
+fn test_replay_gtest_events() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+    let protocol_fee = Percentage::from_scale(1, 2);
+
+    let (invariant, token_x_program, token_y_program) = init_programs(&sys, protocol_fee);
+
+    let mut payloads = vec![];
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    capture(
+        &mut payloads,
+        send(&invariant, ADMIN, "Service", "AddFeeTier", fee_tier),
+    );
+    capture(
+        &mut payloads,
+        send(
+            &invariant,
+            REGULAR_USER_1,
+            "Service",
+            "CreatePool",
+            (token_x, token_y, fee_tier, SqrtPrice::from_integer(1), 0i32),
+        ),
+    );
+
+    for (user, amount) in [(REGULAR_USER_1, 10u128.pow(10)), (REGULAR_USER_2, 10000)] {
+        let amount = U256::from(amount);
+        for (token, token_program) in [(token_x, &token_x_program), (token_y, &token_y_program)] {
+            let account = ActorId::from(user);
+            let spender = ActorId::from(INVARIANT_ID);
+            assert!(!send(
+                token_program,
+                PROGRAM_OWNER,
+                "Vft",
+                "Mint",
+                (account, amount)
+            )
+            .main_failed());
+            assert!(!send(token_program, user, "Vft", "Approve", (spender, amount)).main_failed());
+            capture(
+                &mut payloads,
+                send(
+                    &invariant,
+                    user,
+                    "Service",
+                    "DepositSingleToken",
+                    (token, TokenAmount(amount)),
+                ),
+            );
+        }
+    }
+
+    let liquidity = Liquidity::from_integer(1000000);
+    let sqrt_price = SqrtPrice::from_integer(1);
+    for (lower_tick, upper_tick) in [(-20i32, 10i32), (-40, -10)] {
+        capture(
+            &mut payloads,
+            send(
+                &invariant,
+                REGULAR_USER_1,
+                "Service",
+                "CreatePosition",
+                (
+                    pool_key,
+                    lower_tick,
+                    upper_tick,
+                    liquidity,
+                    sqrt_price,
+                    sqrt_price,
+                    None::<u64>,
+                ),
+            ),
+        );
+    }
+
+    let min_sqrt_price = SqrtPrice::new(MIN_SQRT_PRICE);
+    let max_sqrt_price = SqrtPrice::new(MAX_SQRT_PRICE);
+    let swaps = [
+        (true, 1000, true, min_sqrt_price),
+        (false, 300, false, max_sqrt_price),
+        (true, 200, false, min_sqrt_price),
+        (false, 500, true, max_sqrt_price),
+    ];
+    for (index, (x_to_y, amount, by_amount_in, sqrt_price_limit)) in swaps.into_iter().enumerate() {
+        // swaps after the change are replayed with the new protocol fee
+        if index == 2 {
+            capture(
+                &mut payloads,
+                send(
+                    &invariant,
+                    ADMIN,
+                    "Service",
+                    "ChangeProtocolFee",
+                    Percentage::from_scale(2, 2),
+                ),
+            );
+        }
+
+        capture(
+            &mut payloads,
+            send(
+                &invariant,
+                REGULAR_USER_2,
+                "Service",
+                "Swap",
+                (
+                    pool_key,
+                    x_to_y,
+                    TokenAmount::new(U256::from(amount)),
+                    by_amount_in,
+                    sqrt_price_limit,
+                    None::<u64>,
+                    false,
+                ),
+            ),
+        );
+    }
+
+    let pool =
+        query::<Result<Pool, InvariantError>>(&invariant, "GetPool", (token_x, token_y, fee_tier))
+            .unwrap();
+    capture(
+        &mut payloads,
+        send(
+            &invariant,
+            REGULAR_USER_1,
+            "Service",
+            "IncreaseLiquidity",
+            (
+                0u64,
+                Liquidity::from_integer(500000),
+                pool.sqrt_price,
+                pool.sqrt_price,
+            ),
+        ),
+    );
+    capture(
+        &mut payloads,
+        send(
+            &invariant,
+            REGULAR_USER_1,
+            "Service",
+            "DecreaseLiquidity",
+            (1u64, Liquidity::from_integer(250000)),
+        ),
+    );
+    for position_id in [0u64, 1] {
+        capture(
+            &mut payloads,
+            send(
+                &invariant,
+                REGULAR_USER_1,
+                "Service",
+                "ClaimFee",
+                position_id,
+            ),
+        );
+    }
+    capture(
+        &mut payloads,
+        send(
+            &invariant,
+            REGULAR_USER_1,
+            "Service",
+            "TransferPosition",
+            (0u64, ActorId::from(REGULAR_USER_2)),
+        ),
+    );
+    capture(
+        &mut payloads,
+        send(
+            &invariant,
+            REGULAR_USER_1,
+            "Service",
+            "RemovePosition",
+            (1u64, None::<u64>),
+        ),
+    );
+    capture(
+        &mut payloads,
+        send(
+            &invariant,
+            ADMIN,
+            "Service",
+            "WithdrawProtocolFee",
+            pool_key,
+        ),
+    );
+
+    // the dump is replayed the same way the cli reads it
+    let payloads = read_scale_dump(&write_scale_dump(&payloads)).unwrap();
+
+    let mut indexer = Indexer::new(ReplayConfig {
+        admin: ADMIN.into(),
+        protocol_fee,
+        ..ReplayConfig::default()
+    });
+    for payload in payloads.iter() {
+        indexer.apply_payload(payload).unwrap();
+    }
+
+    // the pool is queried with the effective protocol fee in place of the override
+    let pool =
+        query::<Result<Pool, InvariantError>>(&invariant, "GetPool", (token_x, token_y, fee_tier))
+            .unwrap();
+    let replayed_pool = indexer.get_pool(pool_key).unwrap();
+    assert_eq!(
+        Pool {
+            protocol_fee: Some(replayed_pool.effective_protocol_fee(Percentage::from_scale(2, 2))),
+            ..replayed_pool.clone()
+        },
+        pool
+    );
+    assert_eq!(indexer.get_pool_keys(), &[pool_key]);
+
+    for index in [-40, -20, -10, 10] {
+        assert_eq!(
+            indexer.get_tick(pool_key, index).ok(),
+            query::<Result<Tick, InvariantError>>(&invariant, "GetTick", (pool_key, index))
+                .ok()
+                .as_ref()
+        );
+        assert_eq!(
+            indexer.is_tick_initialized(pool_key, index),
+            query::<bool>(&invariant, "IsTickInitialized", (pool_key, index))
+        );
+    }
+
+    // block number isn't a part of the events
+    let position =
+        query::<Result<Position, InvariantError>>(&invariant, "GetPosition", 0u64).unwrap();
+    let replayed_position = Position {
+        last_block_number: position.last_block_number,
+        ..*indexer.get_position(0).unwrap()
+    };
+    assert_eq!(replayed_position, position);
+    assert_eq!(
+        indexer.get_position_owner(0).unwrap(),
+        ActorId::from(REGULAR_USER_2)
+    );
+    assert!(indexer.get_position(1).is_err());
+
+    let series = indexer.get_series(pool_key).unwrap();
+    let candles = series.candles.get_all();
+    assert!(!candles.is_empty());
+    assert_eq!(candles.first().unwrap().open, SqrtPrice::from_integer(1));
+    assert_eq!(candles.last().unwrap().close, pool.sqrt_price);
+    assert_eq!(
+        candles
+            .iter()
+            .fold(TokenAmount::new(U256::from(0)), |volume, candle| volume
+                + candle.volume_x),
+        series.volume_x
+    );
+    assert!(!series.fee_x.is_zero());
+    assert!(!series.fee_y.is_zero());
+
+    let (amount_x, amount_y, _) = calculate_amount_delta(
+        pool.current_tick_index,
+        pool.sqrt_price,
+        position.liquidity,
+        false,
+        position.upper_tick_index,
+        position.lower_tick_index,
+    )
+    .unwrap();
+    let tvl = series.tvl.last().unwrap();
+    assert_eq!((tvl.amount_x, tvl.amount_y), (amount_x, amount_y));
+}
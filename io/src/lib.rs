@@ -11,12 +11,14 @@ use sails_rs::prelude::*;
 pub struct InvariantConfig {
     pub admin: ActorId,
     pub protocol_fee: Percentage,
+    pub pause: PauseFlags,
 }
 impl Default for InvariantConfig {
     fn default() -> Self {
         Self {
             admin: ActorId::from(0),
             protocol_fee: Percentage::default(),
+            pause: PauseFlags::default(),
         }
     }
 }
@@ -26,6 +28,7 @@ pub enum InvariantEvent {
     PositionCreatedEvent {
         timestamp: u64,
         address: ActorId,
+        position_id: PositionId,
         pool_key: PoolKey,
         liquidity_delta: Liquidity,
         lower_tick: i32,
@@ -35,12 +38,33 @@ pub enum InvariantEvent {
     PositionRemovedEvent {
         timestamp: u64,
         address: ActorId,
+        position_id: PositionId,
         pool_key: PoolKey,
         liquidity: Liquidity,
         lower_tick_index: i32,
         upper_tick_index: i32,
         sqrt_price: SqrtPrice,
     },
+    PositionLiquidityIncreasedEvent {
+        timestamp: u64,
+        address: ActorId,
+        pool_key: PoolKey,
+        position_id: PositionId,
+        liquidity_delta: Liquidity,
+        lower_tick: i32,
+        upper_tick: i32,
+        current_sqrt_price: SqrtPrice,
+    },
+    PositionLiquidityDecreasedEvent {
+        timestamp: u64,
+        address: ActorId,
+        pool_key: PoolKey,
+        position_id: PositionId,
+        liquidity_delta: Liquidity,
+        lower_tick: i32,
+        upper_tick: i32,
+        current_sqrt_price: SqrtPrice,
+    },
     CrossTickEvent {
         timestamp: u64,
         address: ActorId,
@@ -58,6 +82,172 @@ pub enum InvariantEvent {
         target_sqrt_price: SqrtPrice,
         x_to_y: bool,
     },
+    FlashLoanEvent {
+        timestamp: u64,
+        address: ActorId,
+        pool_key: PoolKey,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+        fee_x: TokenAmount,
+        fee_y: TokenAmount,
+    },
+    PauseFlagsChangedEvent {
+        timestamp: u64,
+        pool_key: Option<PoolKey>,
+        pause: PauseFlags,
+    },
+    PoolDeprecatedEvent {
+        timestamp: u64,
+        pool_key: PoolKey,
+    },
+    PoolRemovedEvent {
+        timestamp: u64,
+        pool_key: PoolKey,
+    },
+    PoolCreatedEvent {
+        timestamp: u64,
+        address: ActorId,
+        pool_key: PoolKey,
+        init_sqrt_price: SqrtPrice,
+        init_tick: i32,
+    },
+    FeeTierAddedEvent {
+        timestamp: u64,
+        fee_tier: FeeTier,
+    },
+    FeeTierRemovedEvent {
+        timestamp: u64,
+        fee_tier: FeeTier,
+    },
+    FeeReceiverChangedEvent {
+        timestamp: u64,
+        pool_key: PoolKey,
+        fee_receiver: ActorId,
+    },
+    FeeClaimedEvent {
+        timestamp: u64,
+        address: ActorId,
+        position_id: PositionId,
+        pool_key: PoolKey,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+    },
+    ProtocolFeeWithdrawnEvent {
+        timestamp: u64,
+        address: ActorId,
+        pool_key: PoolKey,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+    },
+    PositionTransferredEvent {
+        timestamp: u64,
+        position_id: PositionId,
+        from: ActorId,
+        to: ActorId,
+    },
+    OperatorSetEvent {
+        timestamp: u64,
+        owner: ActorId,
+        operator: ActorId,
+        approved: bool,
+    },
+    TokenDepositedEvent {
+        timestamp: u64,
+        address: ActorId,
+        token: ActorId,
+        amount: TokenAmount,
+    },
+    TokenWithdrawnEvent {
+        timestamp: u64,
+        address: ActorId,
+        token: ActorId,
+        amount: TokenAmount,
+    },
+    ProtocolFeeChangedEvent {
+        timestamp: u64,
+        protocol_fee: Percentage,
+    },
+    PoolProtocolFeeChangedEvent {
+        timestamp: u64,
+        pool_key: PoolKey,
+        protocol_fee: Option<Percentage>,
+    },
+    PoolDynamicFeeChangedEvent {
+        timestamp: u64,
+        pool_key: PoolKey,
+        dynamic_fee: Option<DynamicFee>,
+    },
+    LimitOrderSettledEvent {
+        timestamp: u64,
+        pool_key: PoolKey,
+        position_id: PositionId,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+    },
+    PositionApprovedEvent {
+        timestamp: u64,
+        position_id: PositionId,
+        owner: ActorId,
+        spender: ActorId,
+    },
+}
+
+#[derive(Clone, Decode, Encode, Debug, PartialEq, Eq, TypeInfo)]
+pub enum IncentivesEvent {
+    CampaignCreatedEvent {
+        timestamp: u64,
+        campaign_id: u64,
+        creator: ActorId,
+        pool_key: PoolKey,
+        reward_token: ActorId,
+        total_reward: TokenAmount,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    },
+    CampaignEndedEvent {
+        timestamp: u64,
+        campaign_id: u64,
+        refund: TokenAmount,
+    },
+    PositionStakedEvent {
+        timestamp: u64,
+        address: ActorId,
+        position_id: PositionId,
+        campaign_id: u64,
+        liquidity: Liquidity,
+    },
+    PositionUnstakedEvent {
+        timestamp: u64,
+        address: ActorId,
+        position_id: PositionId,
+        campaign_id: u64,
+    },
+    RewardClaimedEvent {
+        timestamp: u64,
+        address: ActorId,
+        campaign_id: u64,
+        reward_token: ActorId,
+        amount: TokenAmount,
+    },
+}
+
+#[derive(Clone, Decode, Encode, Debug, PartialEq, Eq, TypeInfo)]
+pub enum PositionNftEvent {
+    TransferEvent {
+        from: ActorId,
+        to: ActorId,
+        token_id: PositionId,
+    },
+    ApprovalEvent {
+        owner: ActorId,
+        approved: ActorId,
+        token_id: PositionId,
+    },
+    ApprovalForAllEvent {
+        owner: ActorId,
+        operator: ActorId,
+        approved: bool,
+    },
 }
 
 #[derive(Decode, Default, Encode, Clone, Debug, PartialEq, Eq, TypeInfo)]
@@ -71,6 +261,20 @@ pub struct CalculateSwapResult {
     pub ticks: Vec<Tick>,
 }
 
+impl From<SwapSimulation> for CalculateSwapResult {
+    fn from(simulation: SwapSimulation) -> Self {
+        Self {
+            amount_in: simulation.amount_in,
+            amount_out: simulation.amount_out,
+            start_sqrt_price: simulation.start_sqrt_price,
+            target_sqrt_price: simulation.target_sqrt_price,
+            fee: simulation.fee,
+            pool: simulation.pool,
+            ticks: simulation.ticks,
+        }
+    }
+}
+
 #[derive(Decode, Default, Encode, Clone, Debug, PartialEq, Eq, TypeInfo)]
 pub struct SwapHop {
     pub pool_key: PoolKey,
@@ -84,3 +288,63 @@ pub struct QuoteResult {
     pub target_sqrt_price: SqrtPrice,
     pub ticks: Vec<Tick>,
 }
+
+#[derive(Decode, Default, Encode, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub struct PositionMetadata {
+    pub token_id: PositionId,
+    pub owner: ActorId,
+    pub pool_key: PoolKey,
+    pub lower_tick_index: i32,
+    pub upper_tick_index: i32,
+    pub liquidity: Liquidity,
+    pub uncollected_fee_x: TokenAmount,
+    pub uncollected_fee_y: TokenAmount,
+    pub current_sqrt_price: SqrtPrice,
+}
+
+#[derive(Decode, Encode, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub enum InvariantOp {
+    CreatePosition {
+        pool_key: PoolKey,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+    },
+    RemovePosition {
+        position_id: PositionId,
+    },
+    ClaimFee {
+        position_id: PositionId,
+    },
+    Swap {
+        pool_key: PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    },
+    TransferPosition {
+        position_id: PositionId,
+        receiver: ActorId,
+    },
+}
+
+#[derive(Decode, Encode, Clone, Debug, PartialEq, Eq, TypeInfo)]
+#[allow(clippy::large_enum_variant)]
+pub enum InvariantOpResult {
+    CreatePosition(Position),
+    RemovePosition(TokenAmount, TokenAmount),
+    ClaimFee(TokenAmount, TokenAmount),
+    Swap(CalculateSwapResult),
+    TransferPosition,
+}
+
+#[derive(Decode, Encode, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub struct StateExport {
+    pub chunk: StateChunk,
+    pub checksum: StateChecksum,
+    // None once the last chunk of the state was exported
+    pub next_cursor: Option<StateCursor>,
+}
@@ -9,6 +9,15 @@ export {
   PositionRemovedEvent,
   PositionCreatedEvent,
   SwapEvent,
+  PoolCreatedEvent,
+  FeeTierAddedEvent,
+  FeeTierRemovedEvent,
+  FeeReceiverChangedEvent,
+  FeeClaimedEvent,
+  ProtocolFeeWithdrawnEvent,
+  PositionTransferredEvent,
+  TokenDepositedEvent,
+  TokenWithdrawnEvent,
   CalculateSwapResult,
   FeeTier,
   Percentage,
@@ -38,7 +47,16 @@ export enum InvariantEvent {
   CrossTickEvent = 'CrossTickEvent',
   SwapEvent = 'SwapEvent',
   PositionCreatedEvent = 'PositionCreatedEvent',
-  PositionRemovedEvent = 'PositionRemovedEvent'
+  PositionRemovedEvent = 'PositionRemovedEvent',
+  PoolCreatedEvent = 'PoolCreatedEvent',
+  FeeTierAddedEvent = 'FeeTierAddedEvent',
+  FeeTierRemovedEvent = 'FeeTierRemovedEvent',
+  FeeReceiverChangedEvent = 'FeeReceiverChangedEvent',
+  FeeClaimedEvent = 'FeeClaimedEvent',
+  ProtocolFeeWithdrawnEvent = 'ProtocolFeeWithdrawnEvent',
+  PositionTransferredEvent = 'PositionTransferredEvent',
+  TokenDepositedEvent = 'TokenDepositedEvent',
+  TokenWithdrawnEvent = 'TokenWithdrawnEvent'
 }
 
 export interface LiquidityBreakpoint {
@@ -51,6 +51,15 @@ import {
   SwapEvent,
   PositionCreatedEvent,
   PositionRemovedEvent,
+  PoolCreatedEvent,
+  FeeTierAddedEvent,
+  FeeTierRemovedEvent,
+  FeeReceiverChangedEvent,
+  FeeClaimedEvent,
+  ProtocolFeeWithdrawnEvent,
+  PositionTransferredEvent,
+  TokenDepositedEvent,
+  TokenWithdrawnEvent,
   CalculateSwapResult,
   FeeTier,
   Percentage,
@@ -239,6 +248,54 @@ export const convertCrossTickEvent = (crossTickEvent: any): CrossTickEvent => {
   return crossTickEvent as CrossTickEvent
 }
 
+export const convertPoolCreatedEvent = (poolEvent: any): PoolCreatedEvent => {
+  poolEvent = convertFieldsToBigInt(poolEvent, ['address', 'poolKey'])
+  poolEvent.poolKey = convertPoolKey(poolEvent.poolKey)
+  return poolEvent as PoolCreatedEvent
+}
+
+export const convertFeeTierAddedEvent = (feeTierEvent: any): FeeTierAddedEvent => {
+  feeTierEvent = convertFieldsToBigInt(feeTierEvent, ['feeTier'])
+  feeTierEvent.feeTier = convertFeeTier(feeTierEvent.feeTier)
+  return feeTierEvent as FeeTierAddedEvent
+}
+
+export const convertFeeTierRemovedEvent = (feeTierEvent: any): FeeTierRemovedEvent => {
+  feeTierEvent = convertFieldsToBigInt(feeTierEvent, ['feeTier'])
+  feeTierEvent.feeTier = convertFeeTier(feeTierEvent.feeTier)
+  return feeTierEvent as FeeTierRemovedEvent
+}
+
+export const convertFeeReceiverChangedEvent = (feeReceiverEvent: any): FeeReceiverChangedEvent => {
+  feeReceiverEvent = convertFieldsToBigInt(feeReceiverEvent, ['poolKey', 'feeReceiver'])
+  feeReceiverEvent.poolKey = convertPoolKey(feeReceiverEvent.poolKey)
+  return feeReceiverEvent as FeeReceiverChangedEvent
+}
+
+export const convertFeeClaimedEvent = (feeEvent: any): FeeClaimedEvent => {
+  feeEvent = convertFieldsToBigInt(feeEvent, ['address', 'poolKey'])
+  feeEvent.poolKey = convertPoolKey(feeEvent.poolKey)
+  return feeEvent as FeeClaimedEvent
+}
+
+export const convertProtocolFeeWithdrawnEvent = (feeEvent: any): ProtocolFeeWithdrawnEvent => {
+  feeEvent = convertFieldsToBigInt(feeEvent, ['address', 'poolKey'])
+  feeEvent.poolKey = convertPoolKey(feeEvent.poolKey)
+  return feeEvent as ProtocolFeeWithdrawnEvent
+}
+
+export const convertPositionTransferredEvent = (positionEvent: any): PositionTransferredEvent => {
+  return convertFieldsToBigInt(positionEvent, ['from', 'to']) as PositionTransferredEvent
+}
+
+export const convertTokenDepositedEvent = (tokenEvent: any): TokenDepositedEvent => {
+  return convertFieldsToBigInt(tokenEvent, ['address', 'token']) as TokenDepositedEvent
+}
+
+export const convertTokenWithdrawnEvent = (tokenEvent: any): TokenWithdrawnEvent => {
+  return convertFieldsToBigInt(tokenEvent, ['address', 'token']) as TokenWithdrawnEvent
+}
+
 export const convertCalculateSwapResult = (calculateSwapResult: any): CalculateSwapResult => {
   calculateSwapResult = convertFieldsToBigInt(calculateSwapResult, ['pool', 'ticks'])
   calculateSwapResult.pool = convertPool(calculateSwapResult.pool)
@@ -367,11 +424,56 @@ export type PositionCreatedEventCallback = {
   callback: (event: PositionCreatedEvent) => void | Promise<void>
 }
 
+export type PoolCreatedEventCallback = {
+  ident: InvariantEvent.PoolCreatedEvent
+  callback: (event: PoolCreatedEvent) => void | Promise<void>
+}
+export type FeeTierAddedEventCallback = {
+  ident: InvariantEvent.FeeTierAddedEvent
+  callback: (event: FeeTierAddedEvent) => void | Promise<void>
+}
+export type FeeTierRemovedEventCallback = {
+  ident: InvariantEvent.FeeTierRemovedEvent
+  callback: (event: FeeTierRemovedEvent) => void | Promise<void>
+}
+export type FeeReceiverChangedEventCallback = {
+  ident: InvariantEvent.FeeReceiverChangedEvent
+  callback: (event: FeeReceiverChangedEvent) => void | Promise<void>
+}
+export type FeeClaimedEventCallback = {
+  ident: InvariantEvent.FeeClaimedEvent
+  callback: (event: FeeClaimedEvent) => void | Promise<void>
+}
+export type ProtocolFeeWithdrawnEventCallback = {
+  ident: InvariantEvent.ProtocolFeeWithdrawnEvent
+  callback: (event: ProtocolFeeWithdrawnEvent) => void | Promise<void>
+}
+export type PositionTransferredEventCallback = {
+  ident: InvariantEvent.PositionTransferredEvent
+  callback: (event: PositionTransferredEvent) => void | Promise<void>
+}
+export type TokenDepositedEventCallback = {
+  ident: InvariantEvent.TokenDepositedEvent
+  callback: (event: TokenDepositedEvent) => void | Promise<void>
+}
+export type TokenWithdrawnEventCallback = {
+  ident: InvariantEvent.TokenWithdrawnEvent
+  callback: (event: TokenWithdrawnEvent) => void | Promise<void>
+}
 export type InvariantEventCallback =
   | SwapEventCallback
   | CrossTickEventCallback
   | PositionRemovedEventCallback
   | PositionCreatedEventCallback
+  | PoolCreatedEventCallback
+  | FeeTierAddedEventCallback
+  | FeeTierRemovedEventCallback
+  | FeeReceiverChangedEventCallback
+  | FeeClaimedEventCallback
+  | ProtocolFeeWithdrawnEventCallback
+  | PositionTransferredEventCallback
+  | TokenDepositedEventCallback
+  | TokenWithdrawnEventCallback
 export const decodeEvent = (registry: TypeRegistry, payload: HexString, prefix: string): any => {
   let type: string
   let convertFunction
@@ -398,6 +500,51 @@ export const decodeEvent = (registry: TypeRegistry, payload: HexString, prefix:
         '(String, String, {"timestamp":"u64","address":"[u8;32]","poolKey":"PoolKey","amountIn":"TokenAmount","amountOut":"TokenAmount","fee":"TokenAmount","startSqrtPrice":"SqrtPrice","targetSqrtPrice":"SqrtPrice","xToY":"bool"})'
       convertFunction = convertSwapEvent
       break
+    case InvariantEvent.PoolCreatedEvent:
+      type =
+        '(String, String, {"timestamp":"u64","address":"[u8;32]","poolKey":"PoolKey","initSqrtPrice":"SqrtPrice","initTick":"i32"})'
+      convertFunction = convertPoolCreatedEvent
+      break
+    case InvariantEvent.FeeTierAddedEvent:
+      type =
+        '(String, String, {"timestamp":"u64","feeTier":"FeeTier"})'
+      convertFunction = convertFeeTierAddedEvent
+      break
+    case InvariantEvent.FeeTierRemovedEvent:
+      type =
+        '(String, String, {"timestamp":"u64","feeTier":"FeeTier"})'
+      convertFunction = convertFeeTierRemovedEvent
+      break
+    case InvariantEvent.FeeReceiverChangedEvent:
+      type =
+        '(String, String, {"timestamp":"u64","poolKey":"PoolKey","feeReceiver":"[u8;32]"})'
+      convertFunction = convertFeeReceiverChangedEvent
+      break
+    case InvariantEvent.FeeClaimedEvent:
+      type =
+        '(String, String, {"timestamp":"u64","address":"[u8;32]","positionId":"u64","poolKey":"PoolKey","amountX":"TokenAmount","amountY":"TokenAmount"})'
+      convertFunction = convertFeeClaimedEvent
+      break
+    case InvariantEvent.ProtocolFeeWithdrawnEvent:
+      type =
+        '(String, String, {"timestamp":"u64","address":"[u8;32]","poolKey":"PoolKey","amountX":"TokenAmount","amountY":"TokenAmount"})'
+      convertFunction = convertProtocolFeeWithdrawnEvent
+      break
+    case InvariantEvent.PositionTransferredEvent:
+      type =
+        '(String, String, {"timestamp":"u64","positionId":"u64","from":"[u8;32]","to":"[u8;32]"})'
+      convertFunction = convertPositionTransferredEvent
+      break
+    case InvariantEvent.TokenDepositedEvent:
+      type =
+        '(String, String, {"timestamp":"u64","address":"[u8;32]","token":"[u8;32]","amount":"TokenAmount"})'
+      convertFunction = convertTokenDepositedEvent
+      break
+    case InvariantEvent.TokenWithdrawnEvent:
+      type =
+        '(String, String, {"timestamp":"u64","address":"[u8;32]","token":"[u8;32]","amount":"TokenAmount"})'
+      convertFunction = convertTokenWithdrawnEvent
+      break
   }
   const event = (registry.createType(type, payload) as any)[2].toJSON() as any
 
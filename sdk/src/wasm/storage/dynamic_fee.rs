@@ -0,0 +1,41 @@
+use crate::types::percentage::Percentage;
+use decimal::*;
+use serde::{Deserialize, Serialize};
+use tsify::Tsify;
+use wasm_bindgen::prelude::*;
+
+#[derive(Default, PartialEq, Debug, Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicFee {
+    #[tsify(type = "bigint")]
+    pub min_fee: Percentage,
+    #[tsify(type = "bigint")]
+    pub max_fee: Percentage,
+    #[tsify(type = "bigint")]
+    pub fee_per_tick: Percentage,
+    pub tick_movements: Vec<u32>,
+}
+
+impl DynamicFee {
+    // must match the fee charged by the contract for the same recorded movements
+    pub fn fee(&self, base_fee: Percentage) -> Percentage {
+        let movement = self
+            .tick_movements
+            .iter()
+            .fold(0u128, |sum, movement| sum + *movement as u128);
+        let fee = Percentage::new(
+            base_fee
+                .get()
+                .saturating_add(self.fee_per_tick.get().saturating_mul(movement)),
+        );
+
+        if fee < self.min_fee {
+            self.min_fee
+        } else if fee > self.max_fee {
+            self.max_fee
+        } else {
+            fee
+        }
+    }
+}
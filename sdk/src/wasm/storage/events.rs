@@ -3,7 +3,7 @@ use crate::alloc::vec::Vec;
 use crate::types::liquidity::Liquidity;
 use crate::types::sqrt_price::SqrtPrice;
 use crate::types::token_amount::TokenAmount;
-use crate::PoolKey;
+use crate::{FeeTier, PoolKey};
 
 use serde::{Deserialize, Serialize};
 use tsify::Tsify;
@@ -68,3 +68,104 @@ pub struct SwapEvent {
     target_sqrt_price: SqrtPrice,
     x_to_y: bool,
 }
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct PoolCreatedEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    address: String,
+    pool_key: PoolKey,
+    init_sqrt_price: SqrtPrice,
+    #[tsify(type = "bigint")]
+    init_tick: i32,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FeeTierAddedEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    fee_tier: FeeTier,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FeeTierRemovedEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    fee_tier: FeeTier,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FeeReceiverChangedEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    pool_key: PoolKey,
+    fee_receiver: String,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct FeeClaimedEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    address: String,
+    #[tsify(type = "bigint")]
+    position_id: u64,
+    pool_key: PoolKey,
+    amount_x: TokenAmount,
+    amount_y: TokenAmount,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct ProtocolFeeWithdrawnEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    address: String,
+    pool_key: PoolKey,
+    amount_x: TokenAmount,
+    amount_y: TokenAmount,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct PositionTransferredEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    #[tsify(type = "bigint")]
+    position_id: u64,
+    from: String,
+    to: String,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TokenDepositedEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    address: String,
+    token: String,
+    amount: TokenAmount,
+}
+
+#[derive(Default, Debug, PartialEq, Serialize, Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct TokenWithdrawnEvent {
+    #[tsify(type = "bigint")]
+    timestamp: u64,
+    address: String,
+    token: String,
+    amount: TokenAmount,
+}
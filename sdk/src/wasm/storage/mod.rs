@@ -1,3 +1,4 @@
+pub mod dynamic_fee;
 pub mod errors;
 pub mod events;
 pub mod fee_tier;
@@ -8,6 +9,7 @@ pub mod position;
 pub mod tick;
 pub mod swap_simulation;
 
+pub use dynamic_fee::*;
 pub use errors::*;
 pub use events::*;
 pub use fee_tier::*;
@@ -27,6 +27,9 @@ pub struct Pool {
     pub last_timestamp: u64,
     #[tsify(type = "string")]
     pub fee_receiver: String,
+    #[serde(default)]
+    #[tsify(optional)]
+    pub dynamic_fee: Option<DynamicFee>,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -48,6 +48,13 @@ pub fn simulate_invariant_swap(
         get_max_tick(fee_tier.tick_spacing as u16)?
     };
 
+    let fee_tier = FeeTier {
+        fee: match &pool.dynamic_fee {
+            Some(dynamic_fee) => dynamic_fee.fee(fee_tier.fee),
+            None => fee_tier.fee,
+        },
+        ..fee_tier
+    };
     let start_sqrt_price = pool.sqrt_price;
 
     let mut swap_step_number = 0;
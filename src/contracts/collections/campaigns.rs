@@ -0,0 +1,161 @@
+use crate::{Campaign, InvariantError, PoolKey};
+use sails_rs::{collections::HashMap, prelude::*};
+
+#[derive(Debug, Default)]
+pub struct Campaigns {
+    campaigns: HashMap<u64, Campaign>,
+    next_campaign_id: u64,
+    // number of campaigns created in each pool
+    pool_campaigns: HashMap<PoolKey, u32>,
+}
+
+impl Campaigns {
+    pub fn add(&mut self, campaign: &Campaign) -> u64 {
+        let campaign_id = self.next_campaign_id;
+
+        self.campaigns.insert(campaign_id, *campaign);
+        *self.pool_campaigns.entry(campaign.pool_key).or_default() += 1;
+        self.next_campaign_id += 1;
+
+        campaign_id
+    }
+
+    pub fn update(&mut self, campaign_id: u64, campaign: &Campaign) -> Result<(), InvariantError> {
+        self.get(campaign_id)?;
+
+        self.campaigns.insert(campaign_id, *campaign);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, campaign_id: u64) -> Result<Campaign, InvariantError> {
+        let campaign = self
+            .campaigns
+            .remove(&campaign_id)
+            .ok_or(InvariantError::CampaignNotFound)?;
+
+        let count = self.pool_campaigns.get_mut(&campaign.pool_key).unwrap();
+        *count -= 1;
+
+        if *count == 0 {
+            self.pool_campaigns.remove(&campaign.pool_key);
+        }
+
+        Ok(campaign)
+    }
+
+    pub fn get(&self, campaign_id: u64) -> Result<Campaign, InvariantError> {
+        self.campaigns
+            .get(&campaign_id)
+            .copied()
+            .ok_or(InvariantError::CampaignNotFound)
+    }
+
+    // Keeps the id of a campaign moved from another program
+    pub fn insert(&mut self, campaign_id: u64, campaign: &Campaign) -> Result<(), InvariantError> {
+        if self.campaigns.contains_key(&campaign_id) {
+            return Err(InvariantError::CampaignAlreadyExist);
+        }
+
+        self.campaigns.insert(campaign_id, *campaign);
+        *self.pool_campaigns.entry(campaign.pool_key).or_default() += 1;
+        self.next_campaign_id = self.next_campaign_id.max(campaign_id + 1);
+
+        Ok(())
+    }
+
+    pub fn next_campaign_id(&self) -> u64 {
+        self.next_campaign_id
+    }
+
+    pub fn set_next_campaign_id(&mut self, next_campaign_id: u64) {
+        self.next_campaign_id = self.next_campaign_id.max(next_campaign_id);
+    }
+
+    // The order is only stable while the campaigns aren't modified
+    pub fn iter(&self) -> impl Iterator<Item = (u64, Campaign)> + '_ {
+        self.campaigns
+            .iter()
+            .map(|(campaign_id, campaign)| (*campaign_id, *campaign))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.campaigns.is_empty()
+    }
+
+    pub fn get_pool_length(&self, pool_key: &PoolKey) -> u32 {
+        self.pool_campaigns
+            .get(pool_key)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use decimal::*;
+    use math::token_amount::TokenAmount;
+
+    #[test]
+    fn test_add() {
+        let campaigns = &mut Campaigns::default();
+        let campaign = Campaign::default();
+        let new_campaign = Campaign {
+            total_reward: TokenAmount::from_integer(1),
+            ..Campaign::default()
+        };
+
+        assert_eq!(campaigns.add(&campaign), 0);
+        assert_eq!(campaigns.add(&new_campaign), 1);
+        assert_eq!(campaigns.get(0), Ok(campaign));
+        assert_eq!(campaigns.get(1), Ok(new_campaign));
+        assert_eq!(campaigns.get(2), Err(InvariantError::CampaignNotFound));
+    }
+
+    #[test]
+    fn test_update() {
+        let campaigns = &mut Campaigns::default();
+        let campaign = Campaign::default();
+        let new_campaign = Campaign {
+            number_of_stakes: 1,
+            ..Campaign::default()
+        };
+
+        let campaign_id = campaigns.add(&campaign);
+        campaigns.update(campaign_id, &new_campaign).unwrap();
+        assert_eq!(campaigns.get(campaign_id), Ok(new_campaign));
+
+        let result = campaigns.update(campaign_id + 1, &new_campaign);
+        assert_eq!(result, Err(InvariantError::CampaignNotFound));
+    }
+
+    #[test]
+    fn test_remove() {
+        let campaigns = &mut Campaigns::default();
+        let campaign = Campaign::default();
+
+        let campaign_id = campaigns.add(&campaign);
+        assert_eq!(campaigns.remove(campaign_id), Ok(campaign));
+        assert_eq!(
+            campaigns.get(campaign_id),
+            Err(InvariantError::CampaignNotFound)
+        );
+
+        // ids are never reused
+        assert_eq!(campaigns.add(&campaign), campaign_id + 1);
+    }
+
+    #[test]
+    fn test_get_pool_length() {
+        let campaigns = &mut Campaigns::default();
+        let campaign = Campaign::default();
+
+        let campaign_id = campaigns.add(&campaign);
+        campaigns.insert(campaign_id + 1, &campaign).unwrap();
+        assert_eq!(campaigns.get_pool_length(&campaign.pool_key), 2);
+
+        campaigns.remove(campaign_id).unwrap();
+        campaigns.remove(campaign_id + 1).unwrap();
+        assert_eq!(campaigns.get_pool_length(&campaign.pool_key), 0);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{InvariantError, LimitOrder, PoolKey, PositionId};
+use sails_rs::{collections::HashMap, prelude::*};
+
+#[derive(Debug, Default)]
+pub struct LimitOrders {
+    limit_orders: HashMap<PositionId, LimitOrder>,
+    // unsettled orders by the tick which settles them
+    pending: HashMap<(PoolKey, i32), Vec<PositionId>>,
+}
+
+impl<'a> LimitOrders {
+    pub fn add(
+        &mut self,
+        position_id: PositionId,
+        limit_order: &LimitOrder,
+    ) -> Result<(), InvariantError> {
+        if self.contains(position_id) {
+            return Err(InvariantError::InvalidLimitOrder);
+        }
+
+        self.limit_orders.insert(position_id, *limit_order);
+
+        // settled orders only come from another program
+        if !limit_order.settled {
+            self.pending
+                .entry((limit_order.pool_key, limit_order.settlement_tick_index))
+                .or_default()
+                .push(position_id);
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, position_id: PositionId) -> Result<LimitOrder, InvariantError> {
+        let limit_order = self
+            .limit_orders
+            .remove(&position_id)
+            .ok_or(InvariantError::PositionNotFound)?;
+
+        if !limit_order.settled {
+            self.remove_pending(&limit_order, position_id);
+        }
+
+        Ok(limit_order)
+    }
+
+    pub fn settle(&mut self, position_id: PositionId) -> Result<(), InvariantError> {
+        let limit_order = self
+            .limit_orders
+            .get_mut(&position_id)
+            .ok_or(InvariantError::PositionNotFound)?;
+
+        if limit_order.settled {
+            return Err(InvariantError::LimitOrderSettled);
+        }
+
+        limit_order.settled = true;
+        let limit_order = *limit_order;
+        self.remove_pending(&limit_order, position_id);
+
+        Ok(())
+    }
+
+    pub fn get(&'a self, position_id: PositionId) -> Result<&'a LimitOrder, InvariantError> {
+        self.limit_orders
+            .get(&position_id)
+            .ok_or(InvariantError::PositionNotFound)
+    }
+
+    pub fn contains(&self, position_id: PositionId) -> bool {
+        self.limit_orders.contains_key(&position_id)
+    }
+
+    // The order is only stable while the orders aren't modified
+    pub fn iter(&'a self) -> impl Iterator<Item = (PositionId, LimitOrder)> + 'a {
+        self.limit_orders
+            .iter()
+            .map(|(position_id, limit_order)| (*position_id, *limit_order))
+    }
+
+    pub fn is_settled(&self, position_id: PositionId) -> bool {
+        self.limit_orders
+            .get(&position_id)
+            .is_some_and(|limit_order| limit_order.settled)
+    }
+
+    // Orders which are settled by a swap in the given direction crossing the tick
+    pub fn get_settled_by(
+        &self,
+        pool_key: PoolKey,
+        tick_index: i32,
+        x_to_y: bool,
+    ) -> Vec<PositionId> {
+        self.pending
+            .get(&(pool_key, tick_index))
+            .map(|ids| {
+                ids.iter()
+                    .copied()
+                    .filter(|id| self.limit_orders[id].is_settled_by(tick_index, x_to_y))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn remove_pending(&mut self, limit_order: &LimitOrder, position_id: PositionId) {
+        let key = (limit_order.pool_key, limit_order.settlement_tick_index);
+        let ids = self.pending.get_mut(&key).unwrap();
+        let index = ids.iter().position(|id| *id == position_id).unwrap();
+        ids.swap_remove(index);
+
+        if ids.is_empty() {
+            self.pending.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        let limit_orders = &mut LimitOrders::default();
+        let pool_key = PoolKey::default();
+        let limit_order = LimitOrder {
+            pool_key,
+            x_to_y: true,
+            settlement_tick_index: 10,
+            settled: false,
+        };
+
+        limit_orders.add(0, &limit_order).unwrap();
+        assert_eq!(limit_orders.get(0), Ok(&limit_order));
+        assert_eq!(limit_orders.get_settled_by(pool_key, 10, false), vec![0]);
+        assert_eq!(
+            limit_orders.get_settled_by(pool_key, 10, true),
+            Vec::<PositionId>::new()
+        );
+        assert_eq!(
+            limit_orders.get_settled_by(pool_key, 0, false),
+            Vec::<PositionId>::new()
+        );
+
+        let result = limit_orders.add(0, &limit_order);
+        assert_eq!(result, Err(InvariantError::InvalidLimitOrder));
+    }
+
+    #[test]
+    fn test_settle() {
+        let limit_orders = &mut LimitOrders::default();
+        let pool_key = PoolKey::default();
+        let limit_order = LimitOrder {
+            pool_key,
+            x_to_y: false,
+            settlement_tick_index: -10,
+            settled: false,
+        };
+
+        limit_orders.add(0, &limit_order).unwrap();
+        limit_orders.add(1, &limit_order).unwrap();
+
+        limit_orders.settle(0).unwrap();
+        assert!(limit_orders.is_settled(0));
+        assert!(!limit_orders.is_settled(1));
+        assert_eq!(limit_orders.get_settled_by(pool_key, -10, true), vec![1]);
+
+        let result = limit_orders.settle(0);
+        assert_eq!(result, Err(InvariantError::LimitOrderSettled));
+    }
+
+    #[test]
+    fn test_remove() {
+        let limit_orders = &mut LimitOrders::default();
+        let pool_key = PoolKey::default();
+        let limit_order = LimitOrder {
+            pool_key,
+            x_to_y: true,
+            settlement_tick_index: 10,
+            settled: false,
+        };
+
+        limit_orders.add(0, &limit_order).unwrap();
+        limit_orders.add(1, &limit_order).unwrap();
+        limit_orders.settle(1).unwrap();
+
+        assert_eq!(limit_orders.remove(0), Ok(limit_order));
+        assert_eq!(
+            limit_orders.get_settled_by(pool_key, 10, false),
+            Vec::<PositionId>::new()
+        );
+        assert!(limit_orders.remove(1).unwrap().settled);
+        assert!(!limit_orders.contains(1));
+
+        let result = limit_orders.remove(1);
+        assert_eq!(result, Err(InvariantError::PositionNotFound));
+    }
+}
@@ -1,13 +1,23 @@
+pub mod campaigns;
 pub mod fee_tiers;
+pub mod limit_orders;
+pub mod oracles;
 pub mod pool_keys;
 pub mod pools;
 pub mod positions;
+pub mod roles;
+pub mod stakes;
 pub mod tickmap;
 pub mod ticks;
 
+pub use campaigns::*;
 pub use fee_tiers::*;
+pub use limit_orders::*;
+pub use oracles::*;
 pub use pool_keys::*;
 pub use pools::*;
 pub use positions::*;
+pub use roles::*;
+pub use stakes::*;
 pub use tickmap::*;
 pub use ticks::*;
@@ -0,0 +1,121 @@
+use crate::{InvariantError, Oracle, PoolKey};
+use sails_rs::{collections::HashMap, prelude::*};
+
+#[derive(Debug, Default)]
+pub struct Oracles {
+    oracles: HashMap<PoolKey, Oracle>,
+}
+
+impl<'a> Oracles {
+    pub fn add(&mut self, pool_key: &PoolKey, oracle: Oracle) -> Result<(), InvariantError> {
+        self.oracles
+            .get(pool_key)
+            .map_or(Ok(()), |_| Err(InvariantError::PoolAlreadyExist))?;
+
+        self.oracles.insert(*pool_key, oracle);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, pool_key: &PoolKey) -> Result<(), InvariantError> {
+        self.get(pool_key)?;
+
+        self.oracles.remove(pool_key);
+        Ok(())
+    }
+
+    pub fn get(&'a self, pool_key: &PoolKey) -> Result<&'a Oracle, InvariantError> {
+        self.oracles
+            .get(pool_key)
+            .ok_or(InvariantError::PoolNotFound)
+    }
+
+    // The order is only stable while no pool is added or removed
+    pub fn iter(&'a self) -> impl Iterator<Item = (PoolKey, &'a Oracle)> + 'a {
+        self.oracles
+            .iter()
+            .map(|(pool_key, oracle)| (*pool_key, oracle))
+    }
+
+    // observations are updated in place to avoid copying the whole buffer on every swap
+    pub fn get_mut(&'a mut self, pool_key: &PoolKey) -> Result<&'a mut Oracle, InvariantError> {
+        self.oracles
+            .get_mut(pool_key)
+            .ok_or(InvariantError::PoolNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FeeTier;
+    use decimal::*;
+    use math::{liquidity::Liquidity, percentage::Percentage};
+
+    #[test]
+    fn test_add() {
+        let oracles = &mut Oracles::default();
+        let token_x = ActorId::from([0x01; 32]);
+        let token_y = ActorId::from([0x02; 32]);
+        let fee_tier = FeeTier {
+            fee: Percentage::new(0),
+            tick_spacing: 1,
+        };
+        let new_fee_tier = FeeTier {
+            fee: Percentage::new(0),
+            tick_spacing: 2,
+        };
+        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+        let new_pool_key = PoolKey::new(token_x, token_y, new_fee_tier).unwrap();
+        let oracle = Oracle::new(0);
+
+        oracles.add(&pool_key, oracle.clone()).unwrap();
+        assert_eq!(oracles.get(&pool_key), Ok(&oracle));
+        assert_eq!(
+            oracles.get(&new_pool_key),
+            Err(InvariantError::PoolNotFound)
+        );
+
+        let result = oracles.add(&pool_key, oracle);
+        assert_eq!(result, Err(InvariantError::PoolAlreadyExist));
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let oracles = &mut Oracles::default();
+        let token_x = ActorId::from([0x01; 32]);
+        let token_y = ActorId::from([0x02; 32]);
+        let fee_tier = FeeTier {
+            fee: Percentage::new(0),
+            tick_spacing: 1,
+        };
+        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+        oracles.add(&pool_key, Oracle::new(0)).unwrap();
+        oracles
+            .get_mut(&pool_key)
+            .unwrap()
+            .write(10, 1, Liquidity::from_integer(1));
+
+        assert_eq!(oracles.get(&pool_key).unwrap().last().tick_cumulative, 10);
+    }
+
+    #[test]
+    fn test_remove() {
+        let oracles = &mut Oracles::default();
+        let token_x = ActorId::from([0x01; 32]);
+        let token_y = ActorId::from([0x02; 32]);
+        let fee_tier = FeeTier {
+            fee: Percentage::new(0),
+            tick_spacing: 1,
+        };
+        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+        oracles.add(&pool_key, Oracle::new(0)).unwrap();
+
+        oracles.remove(&pool_key).unwrap();
+        assert_eq!(oracles.get(&pool_key), Err(InvariantError::PoolNotFound));
+
+        let result = oracles.remove(&pool_key);
+        assert_eq!(result, Err(InvariantError::PoolNotFound));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{InvariantError, PoolKey};
+use crate::{InvariantError, PoolId, PoolKey};
 use sails_rs::{collections::HashMap, prelude::*};
 
 #[derive(Debug, Default)]
@@ -6,6 +6,8 @@ pub struct PoolKeys {
     pool_keys: HashMap<PoolKey, u16>,
     pool_keys_by_index: HashMap<u16, PoolKey>,
     pool_keys_length: u16,
+    pool_ids: HashMap<PoolKey, PoolId>,
+    next_pool_id: PoolId,
 }
 
 impl PoolKeys {
@@ -13,6 +15,11 @@ impl PoolKeys {
         self.pool_keys.get(pool_key).copied()
     }
 
+    // Unlike the index, the id of a pool doesn't change when other pools are removed
+    pub fn get_id(&self, pool_key: &PoolKey) -> Option<PoolId> {
+        self.pool_ids.get(pool_key).copied()
+    }
+
     pub fn add(&mut self, pool_key: &PoolKey) -> Result<(), InvariantError> {
         if self.contains(pool_key) {
             return Err(InvariantError::PoolKeyAlreadyExist);
@@ -23,16 +30,27 @@ impl PoolKeys {
             .insert(self.pool_keys_length, *pool_key);
         self.pool_keys_length += 1;
 
+        self.pool_ids.insert(*pool_key, self.next_pool_id);
+        self.next_pool_id += 1;
+
         Ok(())
     }
 
-    #[allow(dead_code)]
+    // The last key takes the index of the removed one so that indexes stay contiguous
     pub fn remove(&mut self, pool_key: &PoolKey) -> Result<(), InvariantError> {
         match self.get_index(pool_key) {
             Some(index) => {
-                self.pool_keys_by_index.remove(&index);
+                let last_index = self.pool_keys_length - 1;
+                let last_pool_key = self.pool_keys_by_index.remove(&last_index).unwrap();
+
+                if index != last_index {
+                    self.pool_keys_by_index.insert(index, last_pool_key);
+                    self.pool_keys.insert(last_pool_key, index);
+                }
+
                 self.pool_keys_length -= 1;
                 self.pool_keys.remove(pool_key);
+                self.pool_ids.remove(pool_key);
                 Ok(())
             }
             None => Err(InvariantError::PoolKeyNotFound),
@@ -103,6 +121,58 @@ mod tests {
         assert_eq!(result, Err(InvariantError::PoolKeyNotFound));
     }
 
+    #[test]
+    fn test_remove_keeps_indexes() {
+        let pool_keys = &mut PoolKeys::default();
+        let fee_tier = FeeTier {
+            fee: Percentage::new(0),
+            tick_spacing: 1,
+        };
+        let keys: Vec<PoolKey> = (1..=3)
+            .map(|token| {
+                PoolKey::new(ActorId::from([0; 32]), ActorId::from([token; 32]), fee_tier).unwrap()
+            })
+            .collect();
+
+        for pool_key in &keys {
+            pool_keys.add(pool_key).unwrap();
+        }
+
+        pool_keys.remove(&keys[0]).unwrap();
+        assert_eq!(pool_keys.count(), 2);
+        assert_eq!(pool_keys.get_all(3, 0), vec![keys[2], keys[1]]);
+        assert_eq!(pool_keys.get_index(&keys[2]), Some(0));
+
+        pool_keys.remove(&keys[1]).unwrap();
+        assert_eq!(pool_keys.get_all(3, 0), vec![keys[2]]);
+    }
+
+    #[test]
+    fn test_ids_are_not_reused() {
+        let pool_keys = &mut PoolKeys::default();
+        let fee_tier = FeeTier {
+            fee: Percentage::new(0),
+            tick_spacing: 1,
+        };
+        let keys: Vec<PoolKey> = (1..=2)
+            .map(|token| {
+                PoolKey::new(ActorId::from([0; 32]), ActorId::from([token; 32]), fee_tier).unwrap()
+            })
+            .collect();
+
+        pool_keys.add(&keys[0]).unwrap();
+        pool_keys.add(&keys[1]).unwrap();
+        assert_eq!(pool_keys.get_id(&keys[0]), Some(0));
+        assert_eq!(pool_keys.get_id(&keys[1]), Some(1));
+
+        pool_keys.remove(&keys[0]).unwrap();
+        assert_eq!(pool_keys.get_id(&keys[0]), None);
+        assert_eq!(pool_keys.get_id(&keys[1]), Some(1));
+
+        pool_keys.add(&keys[0]).unwrap();
+        assert_eq!(pool_keys.get_id(&keys[0]), Some(2));
+    }
+
     #[test]
     fn test_get_all() {
         let pool_keys = &mut PoolKeys::default();
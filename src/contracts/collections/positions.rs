@@ -1,113 +1,315 @@
-use crate::{InvariantError, Position};
+use crate::{InvariantError, PoolKey, Position, PositionId};
 use sails_rs::{collections::HashMap, prelude::*};
 
 #[derive(Debug, Default)]
 pub struct Positions {
-    positions_length: HashMap<ActorId, u32>,
-    positions: HashMap<(ActorId, u32), Position>,
+    positions: HashMap<PositionId, Position>,
+    owners: HashMap<PositionId, ActorId>,
+    // owner index, its order is what index based lookups refer to
+    owner_positions: HashMap<ActorId, Vec<PositionId>>,
+    next_position_id: PositionId,
+    approvals: HashMap<PositionId, ActorId>,
+    operators: HashMap<ActorId, Vec<ActorId>>,
+    // number of positions opened in each pool
+    pool_positions: HashMap<PoolKey, u32>,
 }
 
 impl<'a> Positions {
-    pub fn add(&mut self, account_id: &ActorId, position: &Position) {
-        let positions_length = self.get_length(account_id);
+    pub fn add(&mut self, account_id: &ActorId, position: &Position) -> PositionId {
+        let position_id = self.next_position_id;
 
-        self.positions
-            .insert((account_id.clone(), positions_length), *position);
+        self.positions.insert(position_id, *position);
+        self.owners.insert(position_id, *account_id);
+        self.owner_positions
+            .entry(*account_id)
+            .or_default()
+            .push(position_id);
+        *self.pool_positions.entry(position.pool_key).or_default() += 1;
+
+        self.next_position_id += 1;
 
-        self.positions_length
-            .insert(account_id.clone(), positions_length + 1);
+        position_id
     }
 
-    pub fn update(
+    // Keeps the id of a position moved from another program, positions of an owner have to be
+    // inserted in the order of the owner index
+    pub fn insert(
         &mut self,
         account_id: &ActorId,
-        index: u32,
+        position_id: PositionId,
         position: &Position,
     ) -> Result<(), InvariantError> {
-        let positions_length = self.get_length(account_id);
-
-        if index >= positions_length {
-            return Err(InvariantError::PositionNotFound);
+        if self.positions.contains_key(&position_id) {
+            return Err(InvariantError::PositionAlreadyExist);
         }
 
-        self.positions
-            .insert((account_id.clone(), index), *position);
+        self.positions.insert(position_id, *position);
+        self.owners.insert(position_id, *account_id);
+        self.owner_positions
+            .entry(*account_id)
+            .or_default()
+            .push(position_id);
+        *self.pool_positions.entry(position.pool_key).or_default() += 1;
+
+        self.next_position_id = self.next_position_id.max(position_id + 1);
 
         Ok(())
     }
 
-    pub fn remove(&mut self, account_id: &ActorId, index: u32) -> Result<Position, InvariantError> {
-        let positions_length = self.get_length(account_id);
-        let position = self.get(account_id, index)?.clone();
+    pub fn update(
+        &mut self,
+        position_id: PositionId,
+        position: &Position,
+    ) -> Result<(), InvariantError> {
+        let current_position = self
+            .positions
+            .get_mut(&position_id)
+            .ok_or(InvariantError::PositionNotFound)?;
 
-        if index < positions_length - 1 {
-            let last_position = self
-                .positions
-                .remove(&(account_id.clone(), positions_length - 1))
-                .unwrap();
+        *current_position = *position;
 
-            self.positions
-                .insert((account_id.clone(), index), last_position);
-        } else {
-            self.positions.remove(&(account_id.clone(), index));
-        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, position_id: PositionId) -> Result<Position, InvariantError> {
+        let position = self
+            .positions
+            .remove(&position_id)
+            .ok_or(InvariantError::PositionNotFound)?;
+        let account_id = self.owners.remove(&position_id).unwrap();
 
-        self.positions_length
-            .insert(account_id.clone(), positions_length - 1);
+        self.remove_from_owner(&account_id, position_id);
+        self.approvals.remove(&position_id);
+        self.remove_from_pool(&position.pool_key);
 
         Ok(position)
     }
 
     pub fn transfer(
         &mut self,
-        account_id: &ActorId,
-        index: u32,
+        position_id: PositionId,
         receiver_account_id: &ActorId,
     ) -> Result<(), InvariantError> {
-        let position = self.remove(account_id, index)?;
-        self.add(receiver_account_id, &position);
+        let account_id = self.get_owner(position_id)?;
+
+        self.remove_from_owner(&account_id, position_id);
+        self.owner_positions
+            .entry(*receiver_account_id)
+            .or_default()
+            .push(position_id);
+        self.owners.insert(position_id, *receiver_account_id);
+        self.approvals.remove(&position_id);
 
         Ok(())
     }
 
-    pub fn get(&'a self, account_id: &ActorId, index: u32) -> Result<&'a Position, InvariantError> {
-        let position = self
-            .positions
-            .get(&(account_id.clone(), index))
-            .ok_or(InvariantError::PositionNotFound)?;
+    // Approving the zero address clears the approval
+    pub fn approve(
+        &mut self,
+        position_id: PositionId,
+        spender: &ActorId,
+    ) -> Result<(), InvariantError> {
+        self.get(position_id)?;
 
-        Ok(position)
+        if spender.is_zero() {
+            self.approvals.remove(&position_id);
+        } else {
+            self.approvals.insert(position_id, *spender);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_approved(&self, position_id: PositionId) -> Option<ActorId> {
+        self.approvals.get(&position_id).copied()
+    }
+
+    pub fn set_operator(&mut self, account_id: &ActorId, operator: &ActorId, approved: bool) {
+        let operators = self.operators.entry(*account_id).or_default();
+        let index = operators.iter().position(|id| id == operator);
+
+        match (index, approved) {
+            (None, true) => operators.push(*operator),
+            (Some(index), false) => {
+                operators.swap_remove(index);
+            }
+            _ => {}
+        }
+
+        if operators.is_empty() {
+            self.operators.remove(account_id);
+        }
+    }
+
+    pub fn is_operator(&self, account_id: &ActorId, operator: &ActorId) -> bool {
+        self.operators
+            .get(account_id)
+            .is_some_and(|operators| operators.contains(operator))
+    }
+
+    pub fn get(&'a self, position_id: PositionId) -> Result<&'a Position, InvariantError> {
+        self.positions
+            .get(&position_id)
+            .ok_or(InvariantError::PositionNotFound)
+    }
+
+    pub fn get_owner(&self, position_id: PositionId) -> Result<ActorId, InvariantError> {
+        self.owners
+            .get(&position_id)
+            .copied()
+            .ok_or(InvariantError::PositionNotFound)
+    }
+
+    // Positions of other accounts are reported as missing
+    pub fn get_owned(
+        &'a self,
+        account_id: &ActorId,
+        position_id: PositionId,
+    ) -> Result<&'a Position, InvariantError> {
+        if self.get_owner(position_id)? != *account_id {
+            return Err(InvariantError::PositionNotFound);
+        }
+
+        self.get(position_id)
+    }
+
+    // Returns the owner of the position if the account is the owner, is approved for the position
+    // or is an operator of the owner
+    pub fn get_authorized(
+        &'a self,
+        account_id: &ActorId,
+        position_id: PositionId,
+    ) -> Result<(ActorId, &'a Position), InvariantError> {
+        let owner_id = self.get_owner(position_id)?;
+
+        if owner_id != *account_id
+            && self.get_approved(position_id) != Some(*account_id)
+            && !self.is_operator(&owner_id, account_id)
+        {
+            return Err(InvariantError::PositionNotFound);
+        }
+
+        Ok((owner_id, self.get(position_id)?))
+    }
+
+    pub fn get_id(&self, account_id: &ActorId, index: u32) -> Result<PositionId, InvariantError> {
+        self.get_ids(account_id)
+            .get(index as usize)
+            .copied()
+            .ok_or(InvariantError::PositionNotFound)
+    }
+
+    pub fn get_ids(&self, account_id: &ActorId) -> &[PositionId] {
+        self.owner_positions
+            .get(account_id)
+            .map(|ids| ids.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn get_by_index(
+        &'a self,
+        account_id: &ActorId,
+        index: u32,
+    ) -> Result<&'a Position, InvariantError> {
+        self.get(self.get_id(account_id, index)?)
     }
 
     pub fn get_all(&self, account_id: &ActorId) -> Vec<Position> {
-        (0..self.get_length(account_id))
-            .map(|index| *self.positions.get(&(account_id.clone(), index)).unwrap())
+        self.get_ids(account_id)
+            .iter()
+            .map(|position_id| self.positions[position_id])
+            .collect()
+    }
+
+    pub fn get_slice(
+        &self,
+        account_id: &ActorId,
+        offset: u32,
+        size: u32,
+    ) -> Vec<(PositionId, Position)> {
+        self.get_ids(account_id)
+            .iter()
+            .skip(offset as usize)
+            .take(size as usize)
+            .map(|position_id| (*position_id, self.positions[position_id]))
             .collect()
     }
 
-    pub fn get_slice(&self, account_id: &ActorId, offset: u32, size: u32) -> Vec<Position> {
-        let length = self.get_length(account_id);
-        let offset_with_size = offset.checked_add(size).unwrap();
+    pub fn get_length(&self, account_id: &ActorId) -> u32 {
+        self.get_ids(account_id).len() as u32
+    }
 
-        let max = if offset_with_size > length {
-            length
-        } else {
-            offset_with_size
-        };
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    pub fn get_pool_length(&self, pool_key: &PoolKey) -> u32 {
+        self.pool_positions
+            .get(pool_key)
+            .copied()
+            .unwrap_or_default()
+    }
 
-        (offset..max)
-            .map(|index| *self.positions.get(&(*account_id, index)).unwrap())
+    // Positions of all owners with their ids, each owner's positions are in the order of the
+    // owner index. Order of the owners is only stable while the positions aren't modified
+    pub fn get_all_slice(&self, offset: u32, size: u32) -> Vec<(ActorId, PositionId, Position)> {
+        self.owner_positions
+            .iter()
+            .flat_map(|(account_id, ids)| ids.iter().map(move |id| (*account_id, *id)))
+            .skip(offset as usize)
+            .take(size as usize)
+            .map(|(account_id, id)| (account_id, id, self.positions[&id]))
             .collect()
     }
 
-    pub fn get_length(&self, account_id: &ActorId) -> u32 {
-        self.positions_length.get(account_id).copied().unwrap_or(0)
+    pub fn next_position_id(&self) -> PositionId {
+        self.next_position_id
+    }
+
+    pub fn set_next_position_id(&mut self, next_position_id: PositionId) {
+        self.next_position_id = self.next_position_id.max(next_position_id);
+    }
+
+    // The order of approvals and operators is only stable while they aren't modified
+    pub fn approvals(&'a self) -> impl Iterator<Item = (PositionId, ActorId)> + 'a {
+        self.approvals
+            .iter()
+            .map(|(position_id, spender)| (*position_id, *spender))
+    }
+
+    pub fn operators(&'a self) -> impl Iterator<Item = (ActorId, ActorId)> + 'a {
+        self.operators.iter().flat_map(|(account_id, operators)| {
+            operators
+                .iter()
+                .map(move |operator| (*account_id, *operator))
+        })
+    }
+
+    // The last position takes the freed index to keep the owner index compact
+    fn remove_from_owner(&mut self, account_id: &ActorId, position_id: PositionId) {
+        let ids = self.owner_positions.get_mut(account_id).unwrap();
+        let index = ids.iter().position(|id| *id == position_id).unwrap();
+        ids.swap_remove(index);
+
+        if ids.is_empty() {
+            self.owner_positions.remove(account_id);
+        }
+    }
+
+    fn remove_from_pool(&mut self, pool_key: &PoolKey) {
+        let count = self.pool_positions.get_mut(pool_key).unwrap();
+        *count -= 1;
+
+        if *count == 0 {
+            self.pool_positions.remove(pool_key);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::FeeTier;
 
     #[test]
     fn test_add() {
@@ -120,14 +322,13 @@ mod tests {
             ..Position::default()
         };
 
-        positions.add(&account_id, &position);
-        positions.add(&account_id, &new_position);
-        assert_eq!(positions.get(&account_id, 0), Ok(&position));
-        assert_eq!(positions.get(&account_id, 1), Ok(&new_position));
-        assert_eq!(
-            positions.get(&account_id, 2),
-            Err(InvariantError::PositionNotFound)
-        );
+        assert_eq!(positions.add(&account_id, &position), 0);
+        assert_eq!(positions.add(&account_id, &new_position), 1);
+        assert_eq!(positions.get(0), Ok(&position));
+        assert_eq!(positions.get(1), Ok(&new_position));
+        assert_eq!(positions.get(2), Err(InvariantError::PositionNotFound));
+        assert_eq!(positions.get_by_index(&account_id, 1), Ok(&new_position));
+        assert_eq!(positions.get_owner(1), Ok(account_id));
         assert_eq!(positions.get_length(&account_id), 2);
     }
 
@@ -142,13 +343,13 @@ mod tests {
             ..Position::default()
         };
 
-        positions.add(&account_id, &position);
+        let position_id = positions.add(&account_id, &position);
 
-        positions.update(&account_id, 0, &new_position).unwrap();
-        assert_eq!(positions.get(&account_id, 0), Ok(&new_position));
+        positions.update(position_id, &new_position).unwrap();
+        assert_eq!(positions.get(position_id), Ok(&new_position));
         assert_eq!(positions.get_length(&account_id), 1);
 
-        let result = positions.update(&account_id, 1, &new_position);
+        let result = positions.update(position_id + 1, &new_position);
         assert_eq!(result, Err(InvariantError::PositionNotFound));
     }
 
@@ -163,24 +364,59 @@ mod tests {
             ..Position::default()
         };
 
-        positions.add(&account_id, &position);
-        positions.add(&account_id, &new_position);
+        let position_id = positions.add(&account_id, &position);
+        let new_position_id = positions.add(&account_id, &new_position);
 
-        let result = positions.remove(&account_id, 0);
+        let result = positions.remove(position_id);
         assert_eq!(result, Ok(position));
-        assert_eq!(positions.get(&account_id, 0), Ok(&new_position));
+        assert_eq!(positions.get(new_position_id), Ok(&new_position));
+        assert_eq!(positions.get_by_index(&account_id, 0), Ok(&new_position));
         assert_eq!(positions.get_length(&account_id), 1);
 
-        let result = positions.remove(&account_id, 0);
+        let result = positions.remove(new_position_id);
         assert_eq!(result, Ok(new_position));
         assert_eq!(
-            positions.get(&account_id, 0),
+            positions.get_by_index(&account_id, 0),
             Err(InvariantError::PositionNotFound)
         );
         assert_eq!(positions.get_length(&account_id), 0);
 
-        let result = positions.remove(&account_id, 0);
+        let result = positions.remove(new_position_id);
         assert_eq!(result, Err(InvariantError::PositionNotFound));
+
+        // ids are never reused
+        assert_eq!(positions.add(&account_id, &position), 2);
+    }
+
+    #[test]
+    fn test_get_pool_length() {
+        let positions = &mut Positions::default();
+        let account_id = ActorId::from([0x01; 32]);
+        let position = Position::default();
+        let other_pool_key = PoolKey::new(
+            ActorId::from([0x01; 32]),
+            ActorId::from([0x02; 32]),
+            FeeTier::default(),
+        )
+        .unwrap();
+        let other_position = Position {
+            pool_key: other_pool_key,
+            ..Position::default()
+        };
+
+        let position_id = positions.add(&account_id, &position);
+        positions.add(&account_id, &position);
+        positions.add(&account_id, &other_position);
+        assert_eq!(positions.get_pool_length(&position.pool_key), 2);
+        assert_eq!(positions.get_pool_length(&other_pool_key), 1);
+
+        positions.remove(position_id).unwrap();
+        assert_eq!(positions.get_pool_length(&position.pool_key), 1);
+
+        positions
+            .insert(&account_id, position_id, &other_position)
+            .unwrap();
+        assert_eq!(positions.get_pool_length(&other_pool_key), 2);
     }
 
     #[test]
@@ -190,23 +426,102 @@ mod tests {
         let receiver_account_id = ActorId::from([0x02; 32]);
         let position = Position::default();
 
-        positions.add(&account_id, &position);
+        let position_id = positions.add(&account_id, &position);
 
         positions
-            .transfer(&account_id, 0, &receiver_account_id)
+            .transfer(position_id, &receiver_account_id)
             .unwrap();
         assert_eq!(
-            positions.get(&account_id, 0),
+            positions.get_owned(&account_id, position_id),
             Err(InvariantError::PositionNotFound)
         );
         assert_eq!(positions.get_length(&account_id), 0);
-        assert_eq!(positions.get(&receiver_account_id, 0), Ok(&position));
+        assert_eq!(
+            positions.get_owned(&receiver_account_id, position_id),
+            Ok(&position)
+        );
+        assert_eq!(positions.get_id(&receiver_account_id, 0), Ok(position_id));
         assert_eq!(positions.get_length(&receiver_account_id), 1);
 
-        let result = positions.transfer(&account_id, 0, &receiver_account_id);
+        let result = positions.transfer(position_id + 1, &receiver_account_id);
         assert_eq!(result, Err(InvariantError::PositionNotFound));
     }
 
+    #[test]
+    fn test_approve() {
+        let positions = &mut Positions::default();
+        let account_id = ActorId::from([0x01; 32]);
+        let spender_id = ActorId::from([0x02; 32]);
+        let receiver_account_id = ActorId::from([0x03; 32]);
+        let position = Position::default();
+
+        let position_id = positions.add(&account_id, &position);
+
+        assert_eq!(
+            positions.get_authorized(&spender_id, position_id),
+            Err(InvariantError::PositionNotFound)
+        );
+
+        positions.approve(position_id, &spender_id).unwrap();
+        assert_eq!(positions.get_approved(position_id), Some(spender_id));
+        assert_eq!(
+            positions.get_authorized(&spender_id, position_id),
+            Ok((account_id, &position))
+        );
+        assert_eq!(
+            positions.get_owned(&spender_id, position_id),
+            Err(InvariantError::PositionNotFound)
+        );
+
+        positions.approve(position_id, &ActorId::zero()).unwrap();
+        assert_eq!(positions.get_approved(position_id), None);
+
+        // approvals do not survive a transfer
+        positions.approve(position_id, &spender_id).unwrap();
+        positions
+            .transfer(position_id, &receiver_account_id)
+            .unwrap();
+        assert_eq!(positions.get_approved(position_id), None);
+        assert_eq!(
+            positions.get_authorized(&spender_id, position_id),
+            Err(InvariantError::PositionNotFound)
+        );
+
+        let result = positions.approve(position_id + 1, &spender_id);
+        assert_eq!(result, Err(InvariantError::PositionNotFound));
+    }
+
+    #[test]
+    fn test_set_operator() {
+        let positions = &mut Positions::default();
+        let account_id = ActorId::from([0x01; 32]);
+        let operator_id = ActorId::from([0x02; 32]);
+        let position = Position::default();
+
+        let position_id = positions.add(&account_id, &position);
+        let new_position_id = positions.add(&account_id, &position);
+
+        positions.set_operator(&account_id, &operator_id, true);
+        positions.set_operator(&account_id, &operator_id, true);
+        assert!(positions.is_operator(&account_id, &operator_id));
+        assert!(!positions.is_operator(&operator_id, &account_id));
+        assert_eq!(
+            positions.get_authorized(&operator_id, position_id),
+            Ok((account_id, &position))
+        );
+        assert_eq!(
+            positions.get_authorized(&operator_id, new_position_id),
+            Ok((account_id, &position))
+        );
+
+        positions.set_operator(&account_id, &operator_id, false);
+        assert!(!positions.is_operator(&account_id, &operator_id));
+        assert_eq!(
+            positions.get_authorized(&operator_id, position_id),
+            Err(InvariantError::PositionNotFound)
+        );
+    }
+
     #[test]
     fn test_get_all() {
         let positions = &mut Positions::default();
@@ -230,6 +545,10 @@ mod tests {
         assert_eq!(result, vec![position, new_position]);
         assert_eq!(result.len(), 2);
         assert_eq!(positions.get_length(&account_id), 2);
+        assert_eq!(
+            positions.get_slice(&account_id, 1, 5),
+            vec![(1, new_position)]
+        );
     }
 
     #[test]
@@ -0,0 +1,100 @@
+use crate::{InvariantError, Role};
+use sails_rs::{collections::HashMap, prelude::*};
+
+#[derive(Debug, Default)]
+pub struct Roles {
+    roles: HashMap<ActorId, Vec<Role>>,
+}
+
+impl Roles {
+    pub fn grant(&mut self, account_id: &ActorId, role: Role) -> Result<(), InvariantError> {
+        let roles = self.roles.entry(*account_id).or_default();
+
+        if roles.contains(&role) {
+            return Err(InvariantError::RoleAlreadyGranted);
+        }
+
+        roles.push(role);
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, account_id: &ActorId, role: Role) -> Result<(), InvariantError> {
+        let roles = self
+            .roles
+            .get_mut(account_id)
+            .ok_or(InvariantError::RoleNotGranted)?;
+
+        let index = roles
+            .iter()
+            .position(|granted_role| *granted_role == role)
+            .ok_or(InvariantError::RoleNotGranted)?;
+
+        roles.remove(index);
+
+        if roles.is_empty() {
+            self.roles.remove(account_id);
+        }
+
+        Ok(())
+    }
+
+    pub fn has(&self, account_id: &ActorId, role: Role) -> bool {
+        self.roles
+            .get(account_id)
+            .map(|roles| roles.contains(&role))
+            .unwrap_or(false)
+    }
+
+    pub fn get_all(&self, account_id: &ActorId) -> Vec<Role> {
+        self.roles.get(account_id).cloned().unwrap_or_default()
+    }
+
+    // Roles of all accounts, the order is only stable while no role is granted or revoked
+    pub fn iter(&self) -> impl Iterator<Item = (ActorId, Role)> + '_ {
+        self.roles
+            .iter()
+            .flat_map(|(account_id, roles)| roles.iter().map(move |role| (*account_id, *role)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grant_and_revoke() {
+        let roles = &mut Roles::default();
+        let account_id = ActorId::from(1);
+
+        roles.grant(&account_id, Role::Pauser).unwrap();
+        roles.grant(&account_id, Role::FeeTierManager).unwrap();
+        assert!(roles.has(&account_id, Role::Pauser));
+        assert!(!roles.has(&account_id, Role::ProtocolFeeManager));
+        assert_eq!(
+            roles.grant(&account_id, Role::Pauser),
+            Err(InvariantError::RoleAlreadyGranted)
+        );
+        assert_eq!(
+            roles.get_all(&account_id),
+            vec![Role::Pauser, Role::FeeTierManager]
+        );
+
+        roles.revoke(&account_id, Role::Pauser).unwrap();
+        assert!(!roles.has(&account_id, Role::Pauser));
+        assert_eq!(
+            roles.revoke(&account_id, Role::Pauser),
+            Err(InvariantError::RoleNotGranted)
+        );
+
+        roles.revoke(&account_id, Role::FeeTierManager).unwrap();
+        assert_eq!(roles.get_all(&account_id), vec![]);
+        assert_eq!(
+            roles.revoke(&ActorId::from(2), Role::Pauser),
+            Err(InvariantError::RoleNotGranted)
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{InvariantError, PositionId, Stake};
+use sails_rs::{collections::HashMap, prelude::*};
+
+#[derive(Debug, Default)]
+pub struct Stakes {
+    stakes: HashMap<PositionId, Stake>,
+}
+
+impl<'a> Stakes {
+    pub fn add(&mut self, position_id: PositionId, stake: &Stake) -> Result<(), InvariantError> {
+        if self.contains(position_id) {
+            return Err(InvariantError::PositionStaked);
+        }
+
+        self.stakes.insert(position_id, *stake);
+        Ok(())
+    }
+
+    pub fn update(&mut self, position_id: PositionId, stake: &Stake) -> Result<(), InvariantError> {
+        self.get(position_id)?;
+
+        self.stakes.insert(position_id, *stake);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, position_id: PositionId) -> Result<Stake, InvariantError> {
+        self.stakes
+            .remove(&position_id)
+            .ok_or(InvariantError::PositionNotStaked)
+    }
+
+    pub fn get(&'a self, position_id: PositionId) -> Result<&'a Stake, InvariantError> {
+        self.stakes
+            .get(&position_id)
+            .ok_or(InvariantError::PositionNotStaked)
+    }
+
+    pub fn contains(&self, position_id: PositionId) -> bool {
+        self.stakes.contains_key(&position_id)
+    }
+
+    // The order is only stable while the stakes aren't modified
+    pub fn iter(&'a self) -> impl Iterator<Item = (PositionId, Stake)> + 'a {
+        self.stakes
+            .iter()
+            .map(|(position_id, stake)| (*position_id, *stake))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add() {
+        let stakes = &mut Stakes::default();
+        let stake = Stake::default();
+
+        stakes.add(0, &stake).unwrap();
+        assert_eq!(stakes.get(0), Ok(&stake));
+        assert_eq!(stakes.get(1), Err(InvariantError::PositionNotStaked));
+
+        let result = stakes.add(0, &stake);
+        assert_eq!(result, Err(InvariantError::PositionStaked));
+    }
+
+    #[test]
+    fn test_update() {
+        let stakes = &mut Stakes::default();
+        let stake = Stake::default();
+        let new_stake = Stake {
+            campaign_id: 2,
+            ..Stake::default()
+        };
+
+        let result = stakes.update(0, &new_stake);
+        assert_eq!(result, Err(InvariantError::PositionNotStaked));
+
+        stakes.add(0, &stake).unwrap();
+        stakes.update(0, &new_stake).unwrap();
+        assert_eq!(stakes.get(0), Ok(&new_stake));
+    }
+
+    #[test]
+    fn test_remove() {
+        let stakes = &mut Stakes::default();
+        let stake = Stake::default();
+
+        stakes.add(0, &stake).unwrap();
+        assert_eq!(stakes.remove(0), Ok(stake));
+        assert!(!stakes.contains(0));
+
+        let result = stakes.remove(0);
+        assert_eq!(result, Err(InvariantError::PositionNotStaked));
+    }
+}
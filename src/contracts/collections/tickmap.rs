@@ -1,4 +1,7 @@
-use crate::{InvariantError, PoolKey};
+extern crate alloc;
+
+use crate::{InvariantError, PoolId};
+use alloc::collections::BTreeMap;
 use math::{
     types::sqrt_price::{calculate_sqrt_price, get_max_tick, SqrtPrice},
     MAX_TICK,
@@ -8,9 +11,10 @@ use sails_rs::collections::HashMap;
 pub const TICK_SEARCH_RANGE: i32 = 256;
 pub const CHUNK_SIZE: i32 = 64;
 
+// Only chunks with an initialized tick are stored, so searches skip the empty ones
 #[derive(Debug, Default)]
 pub struct Tickmap {
-    pub bitmap: HashMap<(u16, PoolKey), u64>,
+    pub bitmap: HashMap<PoolId, BTreeMap<u16, u64>>,
 }
 
 pub fn get_max_chunk(tick_spacing: u16) -> u16 {
@@ -79,7 +83,7 @@ pub fn get_search_limit(tick: i32, tick_spacing: u16, up: bool) -> i32 {
 }
 
 impl Tickmap {
-    pub fn next_initialized(&self, tick: i32, tick_spacing: u16, pool_key: PoolKey) -> Option<i32> {
+    pub fn next_initialized(&self, tick: i32, tick_spacing: u16, pool_id: PoolId) -> Option<i32> {
         let limit = get_search_limit(tick, tick_spacing, true);
 
         if tick + tick_spacing as i32 > MAX_TICK {
@@ -87,89 +91,84 @@ impl Tickmap {
         }
 
         // add 1 to not check current tick
-        let (mut chunk, mut bit) =
-            tick_to_position(tick.checked_add(tick_spacing as i32)?, tick_spacing);
+        let (chunk, bit) = tick_to_position(tick.checked_add(tick_spacing as i32)?, tick_spacing);
         let (limiting_chunk, limiting_bit) = tick_to_position(limit, tick_spacing);
 
-        while chunk < limiting_chunk || (chunk == limiting_chunk && bit <= limiting_bit) {
-            let mut shifted = self.bitmap.get(&(chunk, pool_key)).copied().unwrap_or(0) >> bit;
+        if chunk > limiting_chunk {
+            return None;
+        }
 
-            if shifted != 0 {
-                while shifted.checked_rem(2)? == 0 {
-                    shifted >>= 1;
-                    bit = bit.checked_add(1)?;
-                }
+        let chunks = self.bitmap.get(&pool_id)?;
 
-                return if chunk < limiting_chunk || (chunk == limiting_chunk && bit <= limiting_bit)
-                {
-                    // no possibility of overflow
-                    let index: i32 = (chunk as i32 * CHUNK_SIZE) + bit as i32;
-
-                    Some(
-                        index
-                            .checked_sub(MAX_TICK / tick_spacing as i32)?
-                            .checked_mul(tick_spacing.into())?,
-                    )
-                } else {
-                    None
-                };
+        // empty chunks aren't stored, so only the ones with initialized ticks are visited
+        for (&chunk_index, &value) in chunks.range(chunk..=limiting_chunk) {
+            let first_bit = if chunk_index == chunk { bit } else { 0 };
+            let shifted = value >> first_bit;
+
+            if shifted == 0 {
+                continue;
             }
 
-            // go to the text chunk
-            if let Some(value) = chunk.checked_add(1) {
-                chunk = value;
+            let initialized_bit = first_bit + shifted.trailing_zeros() as u8;
+
+            return if chunk_index < limiting_chunk || initialized_bit <= limiting_bit {
+                // no possibility of overflow
+                let index: i32 = (chunk_index as i32 * CHUNK_SIZE) + initialized_bit as i32;
+
+                Some(
+                    index
+                        .checked_sub(MAX_TICK / tick_spacing as i32)?
+                        .checked_mul(tick_spacing.into())?,
+                )
             } else {
-                return None;
-            }
-            bit = 0;
+                None
+            };
         }
 
         None
     }
 
     // tick_spacing - spacing already scaled by tick_spacing
-    pub fn prev_initialized(&self, tick: i32, tick_spacing: u16, pool_key: PoolKey) -> Option<i32> {
+    pub fn prev_initialized(&self, tick: i32, tick_spacing: u16, pool_id: PoolId) -> Option<i32> {
         // don't subtract 1 to check the current tick
         let limit = get_search_limit(tick, tick_spacing, false); // limit scaled by tick_spacing
-        let (mut chunk, mut bit) = tick_to_position(tick, tick_spacing);
+        let (chunk, bit) = tick_to_position(tick, tick_spacing);
         let (limiting_chunk, limiting_bit) = tick_to_position(limit, tick_spacing);
 
-        while chunk > limiting_chunk || (chunk == limiting_chunk && bit >= limiting_bit) {
+        if chunk < limiting_chunk {
+            return None;
+        }
+
+        let chunks = self.bitmap.get(&pool_id)?;
+
+        for (&chunk_index, &value) in chunks.range(limiting_chunk..=chunk).rev() {
+            let last_bit = if chunk_index == chunk {
+                bit
+            } else {
+                CHUNK_SIZE as u8 - 1
+            };
             // always safe due to limitated domain of bit variable
-            let mut mask = 1u128 << bit; // left = MSB direction (increase value)
-            let value = self.bitmap.get(&(chunk, pool_key)).copied().unwrap_or(0) as u128;
-
-            // enter if some of previous bits are initialized in current chunk
-            if value.checked_rem(mask.checked_shl(1)?)? > 0 {
-                // skip uninitalized ticks
-                while value & mask == 0 {
-                    mask >>= 1;
-                    bit = bit.checked_sub(1)?;
-                }
+            let masked = value as u128 % (1u128 << (last_bit + 1));
 
-                // return first initalized tick if limiit is not exceeded, otherswise return None
-                return if chunk > limiting_chunk || (chunk == limiting_chunk && bit >= limiting_bit)
-                {
-                    // no possibility to overflow
-                    let index: i32 = (chunk as i32 * CHUNK_SIZE) + bit as i32;
-
-                    Some(
-                        index
-                            .checked_sub(MAX_TICK / tick_spacing as i32)?
-                            .checked_mul(tick_spacing.into())?,
-                    )
-                } else {
-                    None
-                };
+            if masked == 0 {
+                continue;
             }
 
-            // go to the next chunk
-            if let Some(value) = chunk.checked_sub(1) {
-                chunk = value;
+            let initialized_bit = (u128::BITS - 1 - masked.leading_zeros()) as u8;
+
+            // return first initalized tick if limiit is not exceeded, otherswise return None
+            return if chunk_index > limiting_chunk || initialized_bit >= limiting_bit {
+                // no possibility to overflow
+                let index: i32 = (chunk_index as i32 * CHUNK_SIZE) + initialized_bit as i32;
+
+                Some(
+                    index
+                        .checked_sub(MAX_TICK / tick_spacing as i32)?
+                        .checked_mul(tick_spacing.into())?,
+                )
             } else {
-                return None;
-            }
-            bit = CHUNK_SIZE as u8 - 1;
+                None
+            };
         }
 
         None
@@ -183,12 +182,12 @@ impl Tickmap {
         x_to_y: bool,
         current_tick: i32,
         tick_spacing: u16,
-        pool_key: PoolKey,
+        pool_id: PoolId,
     ) -> Result<(SqrtPrice, Option<(i32, bool)>), InvariantError> {
         let closes_tick_index = if x_to_y {
-            self.prev_initialized(current_tick, tick_spacing, pool_key)
+            self.prev_initialized(current_tick, tick_spacing, pool_id)
         } else {
-            self.next_initialized(current_tick, tick_spacing, pool_key)
+            self.next_initialized(current_tick, tick_spacing, pool_id)
         };
 
         match closes_tick_index {
@@ -222,15 +221,22 @@ impl Tickmap {
         }
     }
 
-    pub fn get(&self, tick: i32, tick_spacing: u16, pool_key: PoolKey) -> bool {
+    pub fn get(&self, tick: i32, tick_spacing: u16, pool_id: PoolId) -> bool {
         let (chunk, bit) = tick_to_position(tick, tick_spacing);
-        let returned_chunk = self.bitmap.get(&(chunk, pool_key)).copied().unwrap_or(0);
+        let returned_chunk = self
+            .bitmap
+            .get(&pool_id)
+            .and_then(|chunks| chunks.get(&chunk))
+            .copied()
+            .unwrap_or(0);
         get_bit_at_position(returned_chunk, bit) == 1
     }
 
-    pub fn flip(&mut self, value: bool, tick: i32, tick_spacing: u16, pool_key: PoolKey) {
+    // Chunks without initialized ticks are removed
+    pub fn flip(&mut self, value: bool, tick: i32, tick_spacing: u16, pool_id: PoolId) {
         let (chunk, bit) = tick_to_position(tick, tick_spacing);
-        let returned_chunk = self.bitmap.get(&(chunk, pool_key)).copied().unwrap_or(0);
+        let chunks = self.bitmap.entry(pool_id).or_default();
+        let returned_chunk = chunks.get(&chunk).copied().unwrap_or(0);
 
         assert_eq!(
             get_bit_at_position(returned_chunk, bit) == 0,
@@ -238,8 +244,17 @@ impl Tickmap {
             "tick initialize tick again"
         );
 
+        match flip_bit_at_position(returned_chunk, bit) {
+            0 => chunks.remove(&chunk),
+            flipped_chunk => chunks.insert(chunk, flipped_chunk),
+        };
+    }
+
+    pub fn get_chunks(&self, pool_id: PoolId) -> impl Iterator<Item = (u16, u64)> + '_ {
         self.bitmap
-            .insert((chunk, pool_key), flip_bit_at_position(returned_chunk, bit));
+            .get(&pool_id)
+            .into_iter()
+            .flat_map(|chunks| chunks.iter().map(|(chunk, value)| (*chunk, *value)))
     }
 }
 
@@ -247,27 +262,20 @@ impl Tickmap {
 mod tests {
 
     use super::*;
-    use crate::FeeTier;
     use decimal::*;
-    use math::{percentage::Percentage, sqrt_price::get_min_tick};
-    use sails_rs::ActorId;
+    use math::sqrt_price::get_min_tick;
+    use sails_rs::prelude::*;
 
     #[test]
     fn test_get_closer_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
-        tickmap.flip(true, 0, 1, pool_key);
+        tickmap.flip(true, 0, 1, pool_id);
         // tick limit closer
         {
             let (result, from_tick) = tickmap
-                .get_closer_limit(SqrtPrice::from_integer(5), true, 100, 1, pool_key)
+                .get_closer_limit(SqrtPrice::from_integer(5), true, 100, 1, pool_id)
                 .unwrap();
             let expected = SqrtPrice::from_integer(5);
             assert_eq!(result, expected);
@@ -276,7 +284,7 @@ mod tests {
         // trade limit closer
         {
             let (result, from_tick) = tickmap
-                .get_closer_limit(SqrtPrice::from_scale(1, 1), true, 100, 1, pool_key)
+                .get_closer_limit(SqrtPrice::from_scale(1, 1), true, 100, 1, pool_id)
                 .unwrap();
             let expected = SqrtPrice::from_integer(1);
             assert_eq!(result, expected);
@@ -285,7 +293,7 @@ mod tests {
         // other direction
         {
             let (result, from_tick) = tickmap
-                .get_closer_limit(SqrtPrice::from_integer(2), false, -5, 1, pool_key)
+                .get_closer_limit(SqrtPrice::from_integer(2), false, -5, 1, pool_id)
                 .unwrap();
             let expected = SqrtPrice::from_integer(1);
             assert_eq!(result, expected);
@@ -294,7 +302,7 @@ mod tests {
         // other direction
         {
             let (result, from_tick) = tickmap
-                .get_closer_limit(SqrtPrice::from_scale(1, 1), false, -100, 10, pool_key)
+                .get_closer_limit(SqrtPrice::from_scale(1, 1), false, -100, 10, pool_id)
                 .unwrap();
             let expected = SqrtPrice::from_scale(1, 1);
             assert_eq!(result, expected);
@@ -304,353 +312,273 @@ mod tests {
 
     #[test]
     fn test_flip() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
         //zero
         {
             let index = 0;
 
-            assert!(!tickmap.get(index, 1, pool_key));
-            tickmap.flip(true, index, 1, pool_key);
-            assert!(tickmap.get(index, 1, pool_key));
-            tickmap.flip(false, index, 1, pool_key);
-            assert!(!tickmap.get(index, 1, pool_key));
+            assert!(!tickmap.get(index, 1, pool_id));
+            tickmap.flip(true, index, 1, pool_id);
+            assert!(tickmap.get(index, 1, pool_id));
+            tickmap.flip(false, index, 1, pool_id);
+            assert!(!tickmap.get(index, 1, pool_id));
         }
         // small
         {
             let index = 7;
 
-            assert!(!tickmap.get(index, 1, pool_key));
-            tickmap.flip(true, index, 1, pool_key);
-            assert!(tickmap.get(index, 1, pool_key));
-            tickmap.flip(false, index, 1, pool_key);
-            assert!(!tickmap.get(index, 1, pool_key));
+            assert!(!tickmap.get(index, 1, pool_id));
+            tickmap.flip(true, index, 1, pool_id);
+            assert!(tickmap.get(index, 1, pool_id));
+            tickmap.flip(false, index, 1, pool_id);
+            assert!(!tickmap.get(index, 1, pool_id));
         }
         // big
         {
             let index = MAX_TICK - 1;
 
-            assert!(!tickmap.get(index, 1, pool_key));
-            tickmap.flip(true, index, 1, pool_key);
-            assert!(tickmap.get(index, 1, pool_key));
-            tickmap.flip(false, index, 1, pool_key);
-            assert!(!tickmap.get(index, 1, pool_key));
+            assert!(!tickmap.get(index, 1, pool_id));
+            tickmap.flip(true, index, 1, pool_id);
+            assert!(tickmap.get(index, 1, pool_id));
+            tickmap.flip(false, index, 1, pool_id);
+            assert!(!tickmap.get(index, 1, pool_id));
         }
         // negative
         {
             let index = MAX_TICK - 40;
 
-            assert!(!tickmap.get(index, 1, pool_key));
-            tickmap.flip(true, index, 1, pool_key);
-            assert!(tickmap.get(index, 1, pool_key));
-            tickmap.flip(false, index, 1, pool_key);
-            assert!(!tickmap.get(index, 1, pool_key));
+            assert!(!tickmap.get(index, 1, pool_id));
+            tickmap.flip(true, index, 1, pool_id);
+            assert!(tickmap.get(index, 1, pool_id));
+            tickmap.flip(false, index, 1, pool_id);
+            assert!(!tickmap.get(index, 1, pool_id));
         }
         // tick spacing
         {
             let index = 20000;
             let tick_spacing = 1000;
 
-            assert!(!tickmap.get(index, tick_spacing, pool_key));
-            tickmap.flip(true, index, tick_spacing, pool_key);
-            assert!(tickmap.get(index, tick_spacing, pool_key));
-            tickmap.flip(false, index, tick_spacing, pool_key);
-            assert!(!tickmap.get(index, tick_spacing, pool_key));
+            assert!(!tickmap.get(index, tick_spacing, pool_id));
+            tickmap.flip(true, index, tick_spacing, pool_id);
+            assert!(tickmap.get(index, tick_spacing, pool_id));
+            tickmap.flip(false, index, tick_spacing, pool_id);
+            assert!(!tickmap.get(index, tick_spacing, pool_id));
         }
     }
 
+    #[test]
+    fn test_get_chunks() {
+        let pool_id: PoolId = 0;
+        let tickmap = &mut Tickmap::default();
+
+        tickmap.flip(true, 100, 1, pool_id);
+        tickmap.flip(true, -100, 1, pool_id);
+        tickmap.flip(true, -99, 1, pool_id);
+        tickmap.flip(true, 0, 1, 1);
+
+        let chunks: Vec<(u16, u64)> = tickmap.get_chunks(pool_id).collect();
+        let (lower_chunk, lower_bit) = tick_to_position(-100, 1);
+        let (upper_chunk, upper_bit) = tick_to_position(100, 1);
+        assert_eq!(
+            chunks,
+            vec![
+                (lower_chunk, 0b11 << lower_bit),
+                (upper_chunk, 1 << upper_bit)
+            ]
+        );
+
+        // empty chunks aren't kept
+        tickmap.flip(false, 100, 1, pool_id);
+        assert_eq!(tickmap.get_chunks(pool_id).count(), 1);
+        assert_eq!(tickmap.get_chunks(1).count(), 1);
+        assert_eq!(tickmap.get_chunks(2).count(), 0);
+    }
+
     #[test]
     fn test_next_initialized_simple() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
-        tickmap.flip(true, 5, 1, pool_key);
-        assert_eq!(tickmap.next_initialized(0, 1, pool_key), Some(5));
+        tickmap.flip(true, 5, 1, pool_id);
+        assert_eq!(tickmap.next_initialized(0, 1, pool_id), Some(5));
     }
 
     #[test]
     fn test_next_initialized_multiple() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
-        tickmap.flip(true, 50, 10, pool_key);
-        tickmap.flip(true, 100, 10, pool_key);
-        assert_eq!(tickmap.next_initialized(0, 10, pool_key), Some(50));
-        assert_eq!(tickmap.next_initialized(50, 10, pool_key), Some(100));
+        tickmap.flip(true, 50, 10, pool_id);
+        tickmap.flip(true, 100, 10, pool_id);
+        assert_eq!(tickmap.next_initialized(0, 10, pool_id), Some(50));
+        assert_eq!(tickmap.next_initialized(50, 10, pool_id), Some(100));
     }
 
     #[test]
     fn test_next_initialized_current_is_last() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
-        tickmap.flip(true, 0, 10, pool_key);
-        assert_eq!(tickmap.next_initialized(0, 10, pool_key), None);
+        tickmap.flip(true, 0, 10, pool_id);
+        assert_eq!(tickmap.next_initialized(0, 10, pool_id), None);
     }
 
     #[test]
     fn test_next_initialized_just_below_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, 0, 1, pool_key);
+        tickmap.flip(true, 0, 1, pool_id);
         assert_eq!(
-            tickmap.next_initialized(-TICK_SEARCH_RANGE, 1, pool_key),
+            tickmap.next_initialized(-TICK_SEARCH_RANGE, 1, pool_id),
             Some(0)
         );
     }
 
     #[test]
     fn test_next_initialized_at_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, 0, 1, pool_key);
+        tickmap.flip(true, 0, 1, pool_id);
         assert_eq!(
-            tickmap.next_initialized(-TICK_SEARCH_RANGE - 1, 1, pool_key),
+            tickmap.next_initialized(-TICK_SEARCH_RANGE - 1, 1, pool_id),
             None
         );
     }
 
     #[test]
     fn test_next_initialized_further_than_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, MAX_TICK - 10, 1, pool_key);
-        assert_eq!(tickmap.next_initialized(-MAX_TICK + 1, 1, pool_key), None);
+        tickmap.flip(true, MAX_TICK - 10, 1, pool_id);
+        assert_eq!(tickmap.next_initialized(-MAX_TICK + 1, 1, pool_id), None);
     }
 
     #[test]
     fn test_next_initialized_hitting_the_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        assert_eq!(tickmap.next_initialized(MAX_TICK - 22, 4, pool_key), None);
+        assert_eq!(tickmap.next_initialized(MAX_TICK - 22, 4, pool_id), None);
     }
 
     #[test]
     fn test_next_initialized_already_at_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        assert_eq!(tickmap.next_initialized(MAX_TICK - 2, 4, pool_key), None);
+        assert_eq!(tickmap.next_initialized(MAX_TICK - 2, 4, pool_id), None);
     }
 
     #[test]
     fn test_next_initialized_at_pos_63() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, MAX_TICK - 63, 1, pool_key);
+        tickmap.flip(true, MAX_TICK - 63, 1, pool_id);
         assert_eq!(
-            tickmap.next_initialized(MAX_TICK - 128, 1, pool_key),
+            tickmap.next_initialized(MAX_TICK - 128, 1, pool_id),
             Some(MAX_TICK - 63)
         );
     }
 
     #[test]
     fn test_prev_initialized_simple() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, -5, 1, pool_key);
-        assert_eq!(tickmap.prev_initialized(0, 1, pool_key), Some(-5));
+        tickmap.flip(true, -5, 1, pool_id);
+        assert_eq!(tickmap.prev_initialized(0, 1, pool_id), Some(-5));
     }
 
     #[test]
     fn test_prev_initialized_multiple() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, -50, 10, pool_key);
-        tickmap.flip(true, -100, 10, pool_key);
-        assert_eq!(tickmap.prev_initialized(0, 10, pool_key), Some(-50));
-        assert_eq!(tickmap.prev_initialized(-50, 10, pool_key), Some(-50));
+        tickmap.flip(true, -50, 10, pool_id);
+        tickmap.flip(true, -100, 10, pool_id);
+        assert_eq!(tickmap.prev_initialized(0, 10, pool_id), Some(-50));
+        assert_eq!(tickmap.prev_initialized(-50, 10, pool_id), Some(-50));
     }
 
     #[test]
     fn test_prev_initialized_current_is_last() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, 0, 10, pool_key);
-        assert_eq!(tickmap.prev_initialized(0, 10, pool_key), Some(0));
+        tickmap.flip(true, 0, 10, pool_id);
+        assert_eq!(tickmap.prev_initialized(0, 10, pool_id), Some(0));
     }
 
     #[test]
     fn test_prev_initialized_next_is_last() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, 10, 10, pool_key);
-        assert_eq!(tickmap.prev_initialized(0, 10, pool_key), None);
+        tickmap.flip(true, 10, 10, pool_id);
+        assert_eq!(tickmap.prev_initialized(0, 10, pool_id), None);
     }
 
     #[test]
     fn test_prev_initialized_just_below_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, 0, 1, pool_key);
+        tickmap.flip(true, 0, 1, pool_id);
         assert_eq!(
-            tickmap.prev_initialized(TICK_SEARCH_RANGE, 1, pool_key),
+            tickmap.prev_initialized(TICK_SEARCH_RANGE, 1, pool_id),
             Some(0)
         );
     }
 
     #[test]
     fn test_prev_initialized_at_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, 0, 1, pool_key);
+        tickmap.flip(true, 0, 1, pool_id);
         assert_eq!(
-            tickmap.prev_initialized(TICK_SEARCH_RANGE + 1, 1, pool_key),
+            tickmap.prev_initialized(TICK_SEARCH_RANGE + 1, 1, pool_id),
             None
         );
     }
 
     #[test]
     fn test_prev_initialized_farther_than_limit() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, -MAX_TICK + 1, 1, pool_key);
-        assert_eq!(tickmap.prev_initialized(MAX_TICK - 1, 1, pool_key), None);
+        tickmap.flip(true, -MAX_TICK + 1, 1, pool_id);
+        assert_eq!(tickmap.prev_initialized(MAX_TICK - 1, 1, pool_id), None);
     }
 
     #[test]
     fn test_prev_initialized_at_pos_63() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         let tickmap = &mut Tickmap::default();
 
-        tickmap.flip(true, -MAX_TICK + 63, 1, pool_key);
+        tickmap.flip(true, -MAX_TICK + 63, 1, pool_id);
         assert_eq!(
-            tickmap.prev_initialized(-MAX_TICK + 128, 1, pool_key),
+            tickmap.prev_initialized(-MAX_TICK + 128, 1, pool_id),
             Some(-MAX_TICK + 63)
         );
     }
@@ -701,13 +629,7 @@ mod tests {
 
     #[test]
     fn test_next_and_prev_initialized() {
-        let token_0: ActorId = ActorId::from([0x01; 32]);
-        let token_1: ActorId = ActorId::from([0x02; 32]);
-        let fee_tier: FeeTier = FeeTier {
-            fee: Percentage::new(1),
-            tick_spacing: 1,
-        };
-        let pool_key: PoolKey = PoolKey::new(token_0, token_1, fee_tier).unwrap();
+        let pool_id: PoolId = 0;
 
         // initalized edges
         {
@@ -717,22 +639,22 @@ mod tests {
                 let max_index = get_max_tick(spacing as u16);
                 let min_index = get_min_tick(spacing as u16);
 
-                tickmap.flip(true, max_index, spacing as u16, pool_key);
-                tickmap.flip(true, min_index, spacing as u16, pool_key);
+                tickmap.flip(true, max_index, spacing as u16, pool_id);
+                tickmap.flip(true, min_index, spacing as u16, pool_id);
 
                 let tick_edge_diff = TICK_SEARCH_RANGE / spacing * spacing;
 
                 let prev =
-                    tickmap.prev_initialized(min_index + tick_edge_diff, spacing as u16, pool_key);
+                    tickmap.prev_initialized(min_index + tick_edge_diff, spacing as u16, pool_id);
                 let next =
-                    tickmap.next_initialized(max_index - tick_edge_diff, spacing as u16, pool_key);
+                    tickmap.next_initialized(max_index - tick_edge_diff, spacing as u16, pool_id);
 
                 assert_eq!((prev.is_some(), next.is_some()), (true, true));
 
                 // cleanup
                 {
-                    tickmap.flip(false, max_index, spacing as u16, pool_key);
-                    tickmap.flip(false, min_index, spacing as u16, pool_key);
+                    tickmap.flip(false, max_index, spacing as u16, pool_id);
+                    tickmap.flip(false, min_index, spacing as u16, pool_id);
                 }
             }
         }
@@ -744,9 +666,9 @@ mod tests {
             let tick_edge_diff = TICK_SEARCH_RANGE / spacing * spacing;
 
             let prev =
-                tickmap.prev_initialized(min_index + tick_edge_diff, spacing as u16, pool_key);
+                tickmap.prev_initialized(min_index + tick_edge_diff, spacing as u16, pool_id);
             let next =
-                tickmap.next_initialized(max_index - tick_edge_diff, spacing as u16, pool_key);
+                tickmap.next_initialized(max_index - tick_edge_diff, spacing as u16, pool_id);
 
             assert_eq!((prev.is_some(), next.is_some()), (false, false));
         }
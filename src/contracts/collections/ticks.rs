@@ -1,120 +1,143 @@
-use crate::{InvariantError, PoolKey, Tick};
+extern crate alloc;
+
+use crate::{InvariantError, PoolId, Tick};
+use alloc::collections::BTreeMap;
 use sails_rs::{collections::HashMap, prelude::*};
 
+// Every pool has its own map of ticks ordered by the index
 #[derive(Debug, Default)]
 pub struct Ticks {
-    ticks: HashMap<(PoolKey, i32), Tick>,
+    ticks: HashMap<PoolId, BTreeMap<i32, Tick>>,
 }
 
 impl<'a> Ticks {
-    pub fn add(&mut self, pool_key: PoolKey, index: i32, tick: Tick) -> Result<(), InvariantError> {
-        self.ticks
-            .get(&(pool_key, index))
-            .map_or(Ok(()), |_| Err(InvariantError::TickAlreadyExist))?;
+    pub fn add(&mut self, pool_id: PoolId, index: i32, tick: Tick) -> Result<(), InvariantError> {
+        let ticks = self.ticks.entry(pool_id).or_default();
+
+        if ticks.contains_key(&index) {
+            return Err(InvariantError::TickAlreadyExist);
+        }
 
-        self.ticks.insert((pool_key, index), tick);
+        ticks.insert(index, tick);
         Ok(())
     }
 
     pub fn update(
         &mut self,
-        pool_key: PoolKey,
+        pool_id: PoolId,
         index: i32,
         tick: Tick,
     ) -> Result<(), InvariantError> {
-        self.get(pool_key, index)?;
+        let current_tick = self
+            .ticks
+            .get_mut(&pool_id)
+            .and_then(|ticks| ticks.get_mut(&index))
+            .ok_or(InvariantError::TickNotFound)?;
 
-        self.ticks.insert((pool_key, index), tick);
+        *current_tick = tick;
         Ok(())
     }
 
-    pub fn remove(&mut self, pool_key: PoolKey, index: i32) -> Result<(), InvariantError> {
-        self.get(pool_key, index)?;
+    pub fn remove(&mut self, pool_id: PoolId, index: i32) -> Result<(), InvariantError> {
+        self.ticks
+            .get_mut(&pool_id)
+            .and_then(|ticks| ticks.remove(&index))
+            .ok_or(InvariantError::TickNotFound)?;
 
-        self.ticks.remove(&(pool_key, index));
         Ok(())
     }
 
-    pub fn get(&'a self, pool_key: PoolKey, index: i32) -> Result<&'a Tick, InvariantError> {
+    pub fn get(&'a self, pool_id: PoolId, index: i32) -> Result<&'a Tick, InvariantError> {
         let tick = self
             .ticks
-            .get(&(pool_key, index))
+            .get(&pool_id)
+            .and_then(|ticks| ticks.get(&index))
             .ok_or(InvariantError::TickNotFound)?;
 
         Ok(tick)
     }
+
+    pub fn get_all(&'a self, pool_id: PoolId) -> impl Iterator<Item = &'a Tick> {
+        self.ticks
+            .get(&pool_id)
+            .into_iter()
+            .flat_map(|ticks| ticks.values())
+    }
+
+    pub fn remove_pool(&mut self, pool_id: PoolId) {
+        self.ticks.remove(&pool_id);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::FeeTier;
-    use decimal::*;
-    use math::percentage::Percentage;
 
     #[test]
     fn test_add() {
         let ticks = &mut Ticks::default();
-        let token_x = ActorId::from([0x01; 32]);
-        let token_y = ActorId::from([0x02; 32]);
-        let fee_tier = FeeTier {
-            fee: Percentage::new(0),
-            tick_spacing: 1,
-        };
-        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+        let pool_id = 0;
         let tick = Tick::default();
 
-        ticks.add(pool_key, 0, tick).unwrap();
-        assert_eq!(ticks.get(pool_key, 0), Ok(&tick));
-        assert_eq!(ticks.get(pool_key, 1), Err(InvariantError::TickNotFound));
+        ticks.add(pool_id, 0, tick).unwrap();
+        assert_eq!(ticks.get(pool_id, 0), Ok(&tick));
+        assert_eq!(ticks.get(pool_id, 1), Err(InvariantError::TickNotFound));
 
-        let result = ticks.add(pool_key, 0, tick);
+        let result = ticks.add(pool_id, 0, tick);
         assert_eq!(result, Err(InvariantError::TickAlreadyExist));
     }
 
     #[test]
     fn test_update() {
         let ticks = &mut Ticks::default();
-        let token_x = ActorId::from([0x01; 32]);
-        let token_y = ActorId::from([0x02; 32]);
-        let fee_tier = FeeTier {
-            fee: Percentage::new(0),
-            tick_spacing: 1,
-        };
-        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+        let pool_id = 0;
         let tick = Tick::default();
         let new_tick = Tick {
             seconds_outside: 1,
             ..Tick::default()
         };
 
-        ticks.add(pool_key, 0, tick).unwrap();
+        ticks.add(pool_id, 0, tick).unwrap();
 
-        ticks.update(pool_key, 0, new_tick).unwrap();
-        assert_eq!(ticks.get(pool_key, 0), Ok(&new_tick));
+        ticks.update(pool_id, 0, new_tick).unwrap();
+        assert_eq!(ticks.get(pool_id, 0), Ok(&new_tick));
 
-        let result = ticks.update(pool_key, 1, new_tick);
+        let result = ticks.update(pool_id, 1, new_tick);
         assert_eq!(result, Err(InvariantError::TickNotFound));
     }
 
     #[test]
     fn test_remove() {
         let ticks = &mut Ticks::default();
-        let token_x = ActorId::from([0x01; 32]);
-        let token_y = ActorId::from([0x02; 32]);
-        let fee_tier = FeeTier {
-            fee: Percentage::new(0),
-            tick_spacing: 1,
-        };
-        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+        let pool_id = 0;
         let tick = Tick::default();
 
-        ticks.add(pool_key, 0, tick).unwrap();
+        ticks.add(pool_id, 0, tick).unwrap();
 
-        ticks.remove(pool_key, 0).unwrap();
-        assert_eq!(ticks.get(pool_key, 0), Err(InvariantError::TickNotFound));
+        ticks.remove(pool_id, 0).unwrap();
+        assert_eq!(ticks.get(pool_id, 0), Err(InvariantError::TickNotFound));
 
-        let result = ticks.remove(pool_key, 0);
+        let result = ticks.remove(pool_id, 0);
         assert_eq!(result, Err(InvariantError::TickNotFound));
     }
+
+    #[test]
+    fn test_get_all() {
+        let ticks = &mut Ticks::default();
+
+        for (pool_id, index) in [(0, 10), (0, -10), (1, 0)] {
+            let tick = Tick {
+                index,
+                ..Tick::default()
+            };
+            ticks.add(pool_id, index, tick).unwrap();
+        }
+
+        let indexes: Vec<i32> = ticks.get_all(0).map(|tick| tick.index).collect();
+        assert_eq!(indexes, vec![-10, 10]);
+
+        ticks.remove_pool(0);
+        assert_eq!(ticks.get_all(0).count(), 0);
+        assert_eq!(ticks.get_all(1).count(), 1);
+    }
 }
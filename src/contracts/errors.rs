@@ -38,6 +38,42 @@ pub enum InvariantError {
     ReplyHandlingFailed,
     InvalidVaraDepositAttempt,
     InvalidVaraWithdrawAttempt,
+    ObservationTooOld,
+    InvalidOracleCardinality,
+    InsufficientOracleFee,
+    CampaignNotFound,
+    InvalidCampaignTime,
+    CampaignNotActive,
+    CampaignStillActive,
+    InvalidCampaignPool,
+    PositionStaked,
+    PositionNotStaked,
+    InsufficientLiquidity,
+    InvalidLimitOrder,
+    LimitOrderSettled,
+    FlashLoanActive,
+    FlashLoanNotFound,
+    FlashLoanNotRepaid,
+    AmountOverMaximumAmountIn,
+    InvalidSplitRoute,
+    DeadlineExceeded,
+    ActionPaused,
+    NotPendingAdmin,
+    RoleAlreadyGranted,
+    RoleNotGranted,
+    PoolDeprecated,
+    PoolNotDeprecated,
+    PoolNotEmpty,
+    PositionAlreadyExist,
+    StateImportInProgress,
+    StateImportNotStarted,
+    StateNotEmpty,
+    StateChecksumMismatch,
+    NotFlashLoanReceiver,
+    InsufficientPoolReserves,
+    StateExportInProgress,
+    StateExportNotStarted,
+    CampaignAlreadyExist,
 }
 
 impl Into<String> for InvariantError {
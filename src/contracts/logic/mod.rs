@@ -1,3 +1,5 @@
 pub mod liquidity_result;
+pub mod swap;
 
 pub use liquidity_result::*;
+pub use swap::*;
@@ -0,0 +1,178 @@
+extern crate alloc;
+
+use crate::{FeeTier, InvariantError, Pool, PoolId, PoolKey, Tick, Tickmap, Ticks, UpdatePoolTick};
+use decimal::*;
+use math::{
+    clamm::compute_swap_step,
+    percentage::Percentage,
+    sqrt_price::{get_max_tick, get_min_tick, SqrtPrice},
+    token_amount::TokenAmount,
+    MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+use traceable_result::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapSimulation {
+    pub amount_in: TokenAmount,
+    pub amount_out: TokenAmount,
+    pub start_sqrt_price: SqrtPrice,
+    pub target_sqrt_price: SqrtPrice,
+    pub fee: TokenAmount,
+    pub pool: Pool,
+    pub ticks: Vec<Tick>,
+}
+
+// Runs the swap on a copy of the pool without touching the ticks, shared by the contract and
+// the off-chain replay so that both calculate it the same way
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_swap(
+    mut pool: Pool,
+    pool_key: PoolKey,
+    pool_id: PoolId,
+    ticks: &Ticks,
+    tickmap: &Tickmap,
+    default_protocol_fee: Percentage,
+    current_timestamp: u64,
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: SqrtPrice,
+) -> Result<SwapSimulation, InvariantError> {
+    if amount.is_zero() {
+        return Err(InvariantError::AmountIsZero);
+    }
+
+    let mut crossed_ticks: Vec<Tick> = vec![];
+
+    let protocol_fee = pool.effective_protocol_fee(default_protocol_fee);
+    let fee_tier = FeeTier {
+        fee: pool.swap_fee(pool_key.fee_tier),
+        ..pool_key.fee_tier
+    };
+
+    if x_to_y {
+        if pool.sqrt_price <= sqrt_price_limit || sqrt_price_limit > SqrtPrice::new(MAX_SQRT_PRICE)
+        {
+            return Err(InvariantError::WrongLimit);
+        }
+    } else if pool.sqrt_price >= sqrt_price_limit
+        || sqrt_price_limit < SqrtPrice::new(MIN_SQRT_PRICE)
+    {
+        return Err(InvariantError::WrongLimit);
+    }
+
+    let tick_limit = if x_to_y {
+        get_min_tick(pool_key.fee_tier.tick_spacing)
+    } else {
+        get_max_tick(pool_key.fee_tier.tick_spacing)
+    };
+
+    let mut remaining_amount = amount;
+
+    let mut total_amount_in = TokenAmount::new(U256::from(0));
+    let mut total_amount_out = TokenAmount::new(U256::from(0));
+
+    let event_start_sqrt_price = pool.sqrt_price;
+    let start_tick_index = pool.current_tick_index;
+    let mut event_fee_amount = TokenAmount::new(U256::from(0));
+
+    while !remaining_amount.is_zero() {
+        let (swap_limit, limiting_tick) = tickmap.get_closer_limit(
+            sqrt_price_limit,
+            x_to_y,
+            pool.current_tick_index,
+            pool_key.fee_tier.tick_spacing,
+            pool_id,
+        )?;
+        let result = unwrap!(compute_swap_step(
+            pool.sqrt_price,
+            swap_limit,
+            pool.liquidity,
+            remaining_amount,
+            by_amount_in,
+            fee_tier.fee,
+        ));
+
+        // make remaining amount smaller
+        if by_amount_in {
+            remaining_amount -= result.amount_in + result.fee_amount;
+        } else {
+            remaining_amount -= result.amount_out;
+        }
+
+        unwrap!(pool.add_fee(result.fee_amount, x_to_y, protocol_fee));
+        event_fee_amount += result.fee_amount;
+
+        pool.sqrt_price = result.next_sqrt_price;
+
+        total_amount_in += result.amount_in + result.fee_amount;
+        total_amount_out += result.amount_out;
+
+        // Fail if price would go over swap limit
+        if pool.sqrt_price == sqrt_price_limit && !remaining_amount.is_zero() {
+            return Err(InvariantError::PriceLimitReached);
+        }
+
+        let mut tick_update = {
+            if let Some((tick_index, is_initialized)) = limiting_tick {
+                if is_initialized {
+                    let tick = ticks.get(pool_id, tick_index).cloned()?;
+                    UpdatePoolTick::TickInitialized(tick)
+                } else {
+                    UpdatePoolTick::TickUninitialized(tick_index)
+                }
+            } else {
+                UpdatePoolTick::NoTick
+            }
+        };
+
+        let (amount_to_add, amount_after_tick_update, has_crossed) = pool.update_tick(
+            result,
+            swap_limit,
+            &mut tick_update,
+            remaining_amount,
+            by_amount_in,
+            x_to_y,
+            current_timestamp,
+            protocol_fee,
+            fee_tier,
+        );
+
+        remaining_amount = amount_after_tick_update;
+        total_amount_in += amount_to_add;
+
+        if let UpdatePoolTick::TickInitialized(tick) = tick_update {
+            if has_crossed {
+                crossed_ticks.push(tick)
+            }
+        }
+
+        let reached_tick_limit = match x_to_y {
+            true => pool.current_tick_index <= tick_limit,
+            false => pool.current_tick_index >= tick_limit,
+        };
+
+        if reached_tick_limit {
+            return Err(InvariantError::TickLimitReached);
+        }
+    }
+
+    if total_amount_out.get() == U256::from(0) {
+        return Err(InvariantError::NoGainSwap);
+    }
+
+    if let Some(dynamic_fee) = pool.dynamic_fee.as_mut() {
+        dynamic_fee.record(pool.current_tick_index.abs_diff(start_tick_index));
+    }
+
+    Ok(SwapSimulation {
+        amount_in: total_amount_in,
+        amount_out: total_amount_out,
+        start_sqrt_price: event_start_sqrt_price,
+        target_sqrt_price: pool.sqrt_price,
+        fee: event_fee_amount,
+        pool,
+        ticks: crossed_ticks,
+    })
+}
@@ -0,0 +1,226 @@
+extern crate alloc;
+
+use crate::{InvariantError, PoolKey};
+use decimal::*;
+use math::types::{
+    liquidity::Liquidity, seconds_per_liquidity::SecondsPerLiquidity, token_amount::TokenAmount,
+};
+use sails_rs::prelude::*;
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Decode, Encode, TypeInfo)]
+pub struct Campaign {
+    pub creator: ActorId,
+    pub pool_key: PoolKey,
+    pub reward_token: ActorId,
+    pub total_reward: TokenAmount,
+    pub total_reward_unclaimed: TokenAmount,
+    // liquidity weighted in-range time that has already been paid out
+    pub total_seconds_claimed: U256,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub number_of_stakes: u32,
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Decode, Encode, TypeInfo)]
+pub struct Stake {
+    pub campaign_id: u64,
+    pub liquidity: Liquidity,
+    pub seconds_per_liquidity_inside_initial: SecondsPerLiquidity,
+}
+
+impl Campaign {
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        creator: ActorId,
+        pool_key: PoolKey,
+        reward_token: ActorId,
+        total_reward: TokenAmount,
+        start_timestamp: u64,
+        end_timestamp: u64,
+        current_timestamp: u64,
+    ) -> Result<Self, InvariantError> {
+        if total_reward.is_zero() {
+            return Err(InvariantError::AmountIsZero);
+        }
+
+        if start_timestamp < current_timestamp || end_timestamp <= start_timestamp {
+            return Err(InvariantError::InvalidCampaignTime);
+        }
+
+        Ok(Self {
+            creator,
+            pool_key,
+            reward_token,
+            total_reward,
+            total_reward_unclaimed: total_reward,
+            total_seconds_claimed: U256::from(0),
+            start_timestamp,
+            end_timestamp,
+            number_of_stakes: 0,
+        })
+    }
+
+    pub fn is_active(&self, current_timestamp: u64) -> bool {
+        self.start_timestamp <= current_timestamp && current_timestamp < self.end_timestamp
+    }
+
+    pub fn calculate_reward(
+        &self,
+        stake: &Stake,
+        seconds_per_liquidity_inside: SecondsPerLiquidity,
+        current_timestamp: u64,
+    ) -> (TokenAmount, U256) {
+        let seconds_inside = U512::from(
+            seconds_per_liquidity_inside
+                .unchecked_sub(stake.seconds_per_liquidity_inside_initial)
+                .get(),
+        ) * stake.liquidity.cast::<U512>();
+
+        // rewards of the time after the end are spread over everyone who is still staked
+        let total_seconds =
+            U512::from(current_timestamp.max(self.end_timestamp) - self.start_timestamp)
+                * U512::from(SecondsPerLiquidity::one().get())
+                * Liquidity::one().cast::<U512>();
+        let total_seconds_unclaimed =
+            total_seconds.saturating_sub(U512::uint_cast(self.total_seconds_claimed));
+
+        if total_seconds_unclaimed.is_zero() {
+            return (TokenAmount::new(U256::from(0)), U256::from(0));
+        }
+
+        let seconds_inside = seconds_inside.min(total_seconds_unclaimed);
+        let reward =
+            self.total_reward_unclaimed.cast::<U512>() * seconds_inside / total_seconds_unclaimed;
+
+        (
+            TokenAmount::new(U256::uint_cast(reward)),
+            U256::uint_cast(seconds_inside),
+        )
+    }
+
+    // Pays out the share accrued by the stake and moves its checkpoint to the current state
+    pub fn settle(
+        &mut self,
+        stake: &mut Stake,
+        seconds_per_liquidity_inside: SecondsPerLiquidity,
+        current_timestamp: u64,
+    ) -> TokenAmount {
+        let (reward, seconds_inside) =
+            self.calculate_reward(stake, seconds_per_liquidity_inside, current_timestamp);
+
+        self.total_reward_unclaimed -= reward;
+        self.total_seconds_claimed += seconds_inside;
+        stake.seconds_per_liquidity_inside_initial = seconds_per_liquidity_inside;
+
+        reward
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create() {
+        let pool_key = PoolKey::default();
+        let reward = TokenAmount::from_integer(1000);
+
+        let campaign = Campaign::create(
+            ActorId::from(1),
+            pool_key,
+            ActorId::from(2),
+            reward,
+            100,
+            200,
+            50,
+        )
+        .unwrap();
+        assert_eq!(campaign.total_reward_unclaimed, reward);
+        assert!(!campaign.is_active(99));
+        assert!(campaign.is_active(100));
+        assert!(!campaign.is_active(200));
+
+        let result = Campaign::create(
+            ActorId::from(1),
+            pool_key,
+            ActorId::from(2),
+            TokenAmount::new(U256::from(0)),
+            100,
+            200,
+            50,
+        );
+        assert_eq!(result, Err(InvariantError::AmountIsZero));
+
+        let result = Campaign::create(
+            ActorId::from(1),
+            pool_key,
+            ActorId::from(2),
+            reward,
+            100,
+            200,
+            150,
+        );
+        assert_eq!(result, Err(InvariantError::InvalidCampaignTime));
+
+        let result = Campaign::create(
+            ActorId::from(1),
+            pool_key,
+            ActorId::from(2),
+            reward,
+            200,
+            200,
+            50,
+        );
+        assert_eq!(result, Err(InvariantError::InvalidCampaignTime));
+    }
+
+    #[test]
+    fn test_settle() {
+        let liquidity = Liquidity::from_integer(10);
+        let mut campaign = Campaign::create(
+            ActorId::from(1),
+            PoolKey::default(),
+            ActorId::from(2),
+            TokenAmount::from_integer(1000),
+            0,
+            100,
+            0,
+        )
+        .unwrap();
+
+        // two stakes with the whole pool liquidity split equally
+        let mut first_stake = Stake {
+            campaign_id: 0,
+            liquidity,
+            seconds_per_liquidity_inside_initial: SecondsPerLiquidity::new(0),
+        };
+        let mut second_stake = first_stake;
+        let pool_liquidity = Liquidity::from_integer(20);
+
+        let seconds_per_liquidity_inside =
+            SecondsPerLiquidity::calculate_seconds_per_liquidity_global(pool_liquidity, 50, 0)
+                .unwrap();
+        let reward = campaign.settle(&mut first_stake, seconds_per_liquidity_inside, 50);
+        assert_eq!(reward, TokenAmount::from_integer(250));
+        assert_eq!(
+            first_stake.seconds_per_liquidity_inside_initial,
+            seconds_per_liquidity_inside
+        );
+
+        let seconds_per_liquidity_inside =
+            SecondsPerLiquidity::calculate_seconds_per_liquidity_global(pool_liquidity, 100, 0)
+                .unwrap();
+        let first_reward = campaign.settle(&mut first_stake, seconds_per_liquidity_inside, 100);
+        let second_reward = campaign.settle(&mut second_stake, seconds_per_liquidity_inside, 100);
+        assert_eq!(first_reward, TokenAmount::from_integer(250));
+        assert_eq!(second_reward, TokenAmount::from_integer(500));
+        assert_eq!(
+            campaign.total_reward_unclaimed,
+            TokenAmount::new(U256::from(0))
+        );
+
+        // nothing left after everything was paid out
+        let reward = campaign.settle(&mut first_stake, seconds_per_liquidity_inside, 100);
+        assert_eq!(reward, TokenAmount::new(U256::from(0)));
+    }
+}
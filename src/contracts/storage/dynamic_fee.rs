@@ -0,0 +1,121 @@
+extern crate alloc;
+
+use crate::InvariantError;
+use alloc::vec::Vec;
+use decimal::*;
+use math::types::percentage::Percentage;
+use sails_rs::prelude::*;
+
+pub const DYNAMIC_FEE_WINDOW: usize = 8;
+
+// Fee is fixed for the whole swap from the movements recorded before it,
+// so quotes and off-chain simulations reproduce it exactly
+#[derive(PartialEq, Eq, Debug, Default, Clone, Decode, Encode, TypeInfo)]
+pub struct DynamicFee {
+    pub min_fee: Percentage,
+    pub max_fee: Percentage,
+    pub fee_per_tick: Percentage,
+    pub tick_movements: Vec<u32>,
+}
+
+impl DynamicFee {
+    pub fn new(
+        min_fee: Percentage,
+        max_fee: Percentage,
+        fee_per_tick: Percentage,
+    ) -> Result<Self, InvariantError> {
+        if min_fee > max_fee || max_fee >= Percentage::from_integer(1) {
+            return Err(InvariantError::InvalidFee);
+        }
+
+        Ok(Self {
+            min_fee,
+            max_fee,
+            fee_per_tick,
+            tick_movements: Vec::new(),
+        })
+    }
+
+    pub fn fee(&self, base_fee: Percentage) -> Percentage {
+        let movement = self
+            .tick_movements
+            .iter()
+            .fold(0u128, |sum, movement| sum + *movement as u128);
+        let fee = Percentage::new(
+            base_fee
+                .get()
+                .saturating_add(self.fee_per_tick.get().saturating_mul(movement)),
+        );
+
+        if fee < self.min_fee {
+            self.min_fee
+        } else if fee > self.max_fee {
+            self.max_fee
+        } else {
+            fee
+        }
+    }
+
+    // Swaps that don't move the tick aren't recorded, so dust swaps can't flush the window
+    pub fn record(&mut self, tick_movement: u32) {
+        if tick_movement == 0 {
+            return;
+        }
+
+        self.tick_movements.push(tick_movement);
+
+        if self.tick_movements.len() > DYNAMIC_FEE_WINDOW {
+            self.tick_movements.remove(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_fee() {
+        let base_fee = Percentage::from_scale(3, 3);
+        let mut dynamic_fee = DynamicFee::new(
+            Percentage::from_scale(1, 3),
+            Percentage::from_scale(1, 2),
+            Percentage::from_scale(1, 5),
+        )
+        .unwrap();
+
+        // no movement recorded yet
+        assert_eq!(dynamic_fee.fee(base_fee), base_fee);
+        assert_eq!(
+            dynamic_fee.fee(Percentage::new(0)),
+            Percentage::from_scale(1, 3)
+        );
+
+        dynamic_fee.record(100);
+        assert_eq!(dynamic_fee.fee(base_fee), Percentage::from_scale(4, 3));
+
+        dynamic_fee.record(10000);
+        assert_eq!(dynamic_fee.fee(base_fee), Percentage::from_scale(1, 2));
+
+        for _ in 0..DYNAMIC_FEE_WINDOW {
+            dynamic_fee.record(0);
+        }
+        assert_eq!(dynamic_fee.tick_movements, vec![100, 10000]);
+        assert_eq!(dynamic_fee.fee(base_fee), Percentage::from_scale(1, 2));
+
+        for _ in 0..DYNAMIC_FEE_WINDOW {
+            dynamic_fee.record(1);
+        }
+        assert_eq!(dynamic_fee.tick_movements.len(), DYNAMIC_FEE_WINDOW);
+        assert_eq!(dynamic_fee.fee(base_fee), Percentage::from_scale(308, 5));
+
+        assert_eq!(
+            DynamicFee::new(
+                Percentage::from_scale(1, 2),
+                Percentage::from_scale(1, 3),
+                Percentage::new(0)
+            ),
+            Err(InvariantError::InvalidFee)
+        );
+    }
+}
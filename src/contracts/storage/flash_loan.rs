@@ -0,0 +1,99 @@
+use crate::{InvariantError, PoolKey};
+use decimal::*;
+use math::token_amount::TokenAmount;
+use sails_rs::prelude::*;
+
+// Tokens lent from a pool that have to be returned together with the fee
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Decode, Encode, TypeInfo)]
+pub struct FlashLoan {
+    pub pool_key: PoolKey,
+    pub amount_x: TokenAmount,
+    pub amount_y: TokenAmount,
+    pub fee_x: TokenAmount,
+    pub fee_y: TokenAmount,
+}
+
+impl FlashLoan {
+    pub fn create(
+        pool_key: PoolKey,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+    ) -> Result<Self, InvariantError> {
+        if amount_x.is_zero() && amount_y.is_zero() {
+            return Err(InvariantError::AmountIsZero);
+        }
+
+        // the fee of the pool is charged on the whole borrowed amount
+        Ok(Self {
+            pool_key,
+            amount_x,
+            amount_y,
+            fee_x: amount_x.big_mul_up(pool_key.fee_tier.fee),
+            fee_y: amount_y.big_mul_up(pool_key.fee_tier.fee),
+        })
+    }
+
+    pub fn repayment(&self) -> Result<(TokenAmount, TokenAmount), InvariantError> {
+        let repayment_x = self
+            .amount_x
+            .checked_add(self.fee_x)
+            .map_err(|_| InvariantError::FailedToChangeTokenBalance)?;
+        let repayment_y = self
+            .amount_y
+            .checked_add(self.fee_y)
+            .map_err(|_| InvariantError::FailedToChangeTokenBalance)?;
+
+        Ok((repayment_x, repayment_y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FeeTier;
+    use math::percentage::Percentage;
+
+    #[test]
+    fn test_create() {
+        let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+        let pool_key = PoolKey::new(
+            ActorId::from([0x01; 32]),
+            ActorId::from([0x02; 32]),
+            fee_tier,
+        )
+        .unwrap();
+
+        let flash_loan = FlashLoan::create(
+            pool_key,
+            TokenAmount::from_integer(1000),
+            TokenAmount::new(U256::from(0)),
+        )
+        .unwrap();
+        assert_eq!(flash_loan.fee_x, TokenAmount::from_integer(6));
+        assert_eq!(flash_loan.fee_y, TokenAmount::new(U256::from(0)));
+        assert_eq!(
+            flash_loan.repayment(),
+            Ok((
+                TokenAmount::from_integer(1006),
+                TokenAmount::new(U256::from(0))
+            ))
+        );
+
+        // fee is rounded up
+        let flash_loan = FlashLoan::create(
+            pool_key,
+            TokenAmount::from_integer(1),
+            TokenAmount::from_integer(1),
+        )
+        .unwrap();
+        assert_eq!(flash_loan.fee_x, TokenAmount::from_integer(1));
+        assert_eq!(flash_loan.fee_y, TokenAmount::from_integer(1));
+
+        let result = FlashLoan::create(
+            pool_key,
+            TokenAmount::new(U256::from(0)),
+            TokenAmount::new(U256::from(0)),
+        );
+        assert_eq!(result, Err(InvariantError::AmountIsZero));
+    }
+}
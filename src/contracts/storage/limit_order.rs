@@ -0,0 +1,90 @@
+use crate::{InvariantError, Pool, PoolKey};
+use sails_rs::prelude::*;
+
+// A position one tick spacing wide that is fully converted once the price crosses it
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Decode, Encode, TypeInfo)]
+pub struct LimitOrder {
+    pub pool_key: PoolKey,
+    // token x is sold for token y if true
+    pub x_to_y: bool,
+    // tick whose cross converts the order, it is the far end of the range
+    pub settlement_tick_index: i32,
+    pub settled: bool,
+}
+
+impl LimitOrder {
+    pub fn create(
+        pool: &Pool,
+        pool_key: PoolKey,
+        lower_tick: i32,
+        upper_tick: i32,
+    ) -> Result<Self, InvariantError> {
+        if upper_tick - lower_tick != pool_key.fee_tier.tick_spacing as i32 {
+            return Err(InvariantError::InvalidLimitOrder);
+        }
+
+        // the range has to hold only the token that is sold
+        let (x_to_y, settlement_tick_index) = if pool.current_tick_index < lower_tick {
+            (true, upper_tick)
+        } else if pool.current_tick_index >= upper_tick {
+            (false, lower_tick)
+        } else {
+            return Err(InvariantError::InvalidLimitOrder);
+        };
+
+        Ok(Self {
+            pool_key,
+            x_to_y,
+            settlement_tick_index,
+            settled: false,
+        })
+    }
+
+    // Only swaps moving the price away from the sold token settle the order
+    pub fn is_settled_by(&self, tick_index: i32, x_to_y: bool) -> bool {
+        !self.settled && self.settlement_tick_index == tick_index && self.x_to_y != x_to_y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FeeTier;
+    use decimal::*;
+    use math::percentage::Percentage;
+
+    #[test]
+    fn test_create() {
+        let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+        let pool_key = PoolKey::new(
+            ActorId::from([0x01; 32]),
+            ActorId::from([0x02; 32]),
+            fee_tier,
+        )
+        .unwrap();
+        let pool = Pool {
+            current_tick_index: 5,
+            ..Pool::default()
+        };
+
+        let order = LimitOrder::create(&pool, pool_key, 10, 20).unwrap();
+        assert!(order.x_to_y);
+        assert_eq!(order.settlement_tick_index, 20);
+        assert!(order.is_settled_by(20, false));
+        assert!(!order.is_settled_by(20, true));
+        assert!(!order.is_settled_by(10, false));
+
+        let order = LimitOrder::create(&pool, pool_key, -10, 0).unwrap();
+        assert!(!order.x_to_y);
+        assert_eq!(order.settlement_tick_index, -10);
+        assert!(order.is_settled_by(-10, true));
+
+        // wider than a single tick spacing
+        let result = LimitOrder::create(&pool, pool_key, 10, 30);
+        assert_eq!(result, Err(InvariantError::InvalidLimitOrder));
+
+        // current tick inside of the range
+        let result = LimitOrder::create(&pool, pool_key, 0, 10);
+        assert_eq!(result, Err(InvariantError::InvalidLimitOrder));
+    }
+}
@@ -1,14 +1,30 @@
 pub mod awaiting_transfers;
+pub mod campaign;
+pub mod dynamic_fee;
 pub mod fee_tier;
+pub mod flash_loan;
+pub mod limit_order;
+pub mod oracle;
+pub mod pause_flags;
 pub mod pool;
 pub mod pool_key;
 pub mod position;
+pub mod role;
+pub mod state_chunk;
 pub mod tick;
 pub mod utils;
 
 pub use awaiting_transfers::*;
+pub use campaign::*;
+pub use dynamic_fee::*;
 pub use fee_tier::*;
+pub use flash_loan::*;
+pub use limit_order::*;
+pub use oracle::*;
+pub use pause_flags::*;
 pub use pool::*;
 pub use pool_key::*;
 pub use position::*;
+pub use role::*;
+pub use state_chunk::*;
 pub use tick::*;
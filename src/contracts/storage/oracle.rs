@@ -0,0 +1,336 @@
+extern crate alloc;
+
+use crate::InvariantError;
+use decimal::*;
+use math::types::{liquidity::Liquidity, seconds_per_liquidity::SecondsPerLiquidity};
+use sails_rs::prelude::*;
+
+pub const MAX_ORACLE_CARDINALITY: u16 = 1000;
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Decode, Encode, TypeInfo)]
+pub struct Observation {
+    pub timestamp: u64,
+    pub tick_cumulative: i64,
+    pub seconds_per_liquidity_cumulative: SecondsPerLiquidity,
+    pub initialized: bool,
+}
+
+impl Observation {
+    pub fn transform(&self, timestamp: u64, tick: i32, liquidity: Liquidity) -> Self {
+        let delta = timestamp - self.timestamp;
+
+        // seconds per liquidity doesn't grow while the pool is empty
+        let seconds_per_liquidity_delta =
+            SecondsPerLiquidity::calculate_seconds_per_liquidity_global(
+                liquidity,
+                timestamp,
+                self.timestamp,
+            )
+            .unwrap_or(SecondsPerLiquidity::new(0));
+
+        Self {
+            timestamp,
+            tick_cumulative: self
+                .tick_cumulative
+                .wrapping_add((tick as i64).wrapping_mul(delta as i64)),
+            seconds_per_liquidity_cumulative: self
+                .seconds_per_liquidity_cumulative
+                .unchecked_add(seconds_per_liquidity_delta),
+            initialized: true,
+        }
+    }
+
+    // Linear interpolation between two observations surrounding the target timestamp
+    fn interpolate(&self, after: &Observation, target: u64) -> Self {
+        if target == self.timestamp {
+            return *self;
+        }
+        if target == after.timestamp {
+            return *after;
+        }
+
+        let observation_delta = (after.timestamp - self.timestamp) as i128;
+        let target_delta = (target - self.timestamp) as i128;
+
+        let tick_cumulative = self.tick_cumulative as i128
+            + (after.tick_cumulative as i128 - self.tick_cumulative as i128) * target_delta
+                / observation_delta;
+
+        let seconds_per_liquidity_delta = U256::from(
+            after
+                .seconds_per_liquidity_cumulative
+                .unchecked_sub(self.seconds_per_liquidity_cumulative)
+                .get(),
+        ) * U256::from(target_delta as u128)
+            / U256::from(observation_delta as u128);
+
+        Self {
+            timestamp: target,
+            tick_cumulative: tick_cumulative as i64,
+            seconds_per_liquidity_cumulative: self.seconds_per_liquidity_cumulative.unchecked_add(
+                SecondsPerLiquidity::new(seconds_per_liquidity_delta.as_u128()),
+            ),
+            initialized: true,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Decode, Encode, TypeInfo)]
+pub struct Oracle {
+    pub observations: Vec<Observation>,
+    pub index: u16,
+    pub cardinality: u16,
+    pub cardinality_next: u16,
+}
+
+impl Oracle {
+    pub fn new(current_timestamp: u64) -> Self {
+        Self {
+            observations: vec![Observation {
+                timestamp: current_timestamp,
+                initialized: true,
+                ..Observation::default()
+            }],
+            index: 0,
+            cardinality: 1,
+            cardinality_next: 1,
+        }
+    }
+
+    pub fn last(&self) -> Observation {
+        self.observations[self.index as usize]
+    }
+
+    // Records state that has been in effect since the last observation, at most once per block
+    pub fn write(&mut self, current_timestamp: u64, tick: i32, liquidity: Liquidity) {
+        let last = self.last();
+
+        if last.timestamp == current_timestamp {
+            return;
+        }
+
+        if self.cardinality_next > self.cardinality && self.index == self.cardinality - 1 {
+            self.cardinality = self.cardinality_next;
+        }
+
+        let index = ((self.index as u32 + 1) % self.cardinality as u32) as u16;
+        self.observations[index as usize] = last.transform(current_timestamp, tick, liquidity);
+        self.index = index;
+    }
+
+    pub fn grow(&mut self, cardinality_next: u16) -> Result<u16, InvariantError> {
+        if cardinality_next > MAX_ORACLE_CARDINALITY {
+            return Err(InvariantError::InvalidOracleCardinality);
+        }
+
+        if cardinality_next <= self.cardinality_next {
+            return Ok(self.cardinality_next);
+        }
+
+        self.observations
+            .resize(cardinality_next as usize, Observation::default());
+        self.cardinality_next = cardinality_next;
+
+        Ok(cardinality_next)
+    }
+
+    pub fn observe(
+        &self,
+        current_timestamp: u64,
+        seconds_agos: &[u64],
+        tick: i32,
+        liquidity: Liquidity,
+    ) -> Result<Vec<(i64, SecondsPerLiquidity)>, InvariantError> {
+        seconds_agos
+            .iter()
+            .map(|seconds_ago| {
+                self.observe_single(current_timestamp, *seconds_ago, tick, liquidity)
+                    .map(|observation| {
+                        (
+                            observation.tick_cumulative,
+                            observation.seconds_per_liquidity_cumulative,
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    fn observe_single(
+        &self,
+        current_timestamp: u64,
+        seconds_ago: u64,
+        tick: i32,
+        liquidity: Liquidity,
+    ) -> Result<Observation, InvariantError> {
+        let target = current_timestamp
+            .checked_sub(seconds_ago)
+            .ok_or(InvariantError::ObservationTooOld)?;
+
+        let last = self.last();
+
+        if last.timestamp <= target {
+            return Ok(if last.timestamp == target {
+                last
+            } else {
+                last.transform(target, tick, liquidity)
+            });
+        }
+
+        let (before, after) = self.get_surrounding_observations(target)?;
+
+        Ok(before.interpolate(&after, target))
+    }
+
+    fn get_surrounding_observations(
+        &self,
+        target: u64,
+    ) -> Result<(Observation, Observation), InvariantError> {
+        let oldest_index = (self.index as u32 + 1) % self.cardinality as u32;
+        let oldest = if self.observations[oldest_index as usize].initialized {
+            self.observations[oldest_index as usize]
+        } else {
+            self.observations[0]
+        };
+
+        if target < oldest.timestamp {
+            return Err(InvariantError::ObservationTooOld);
+        }
+
+        // binary search over the ring buffer ordered from the oldest to the newest observation
+        let cardinality = self.cardinality as u32;
+        let mut left = oldest_index;
+        let mut right = oldest_index + cardinality - 1;
+
+        loop {
+            let middle = (left + right) / 2;
+            let before = self.observations[(middle % cardinality) as usize];
+
+            if !before.initialized {
+                left = middle + 1;
+                continue;
+            }
+
+            let after = self.observations[((middle + 1) % cardinality) as usize];
+
+            if before.timestamp <= target && target <= after.timestamp {
+                return Ok((before, after));
+            }
+
+            if before.timestamp < target {
+                left = middle + 1;
+            } else {
+                right = middle - 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write() {
+        // single slot is overwritten in place
+        {
+            let mut oracle = Oracle::new(100);
+            oracle.write(110, 5, Liquidity::from_integer(1));
+
+            assert_eq!(oracle.index, 0);
+            assert_eq!(oracle.cardinality, 1);
+            assert_eq!(oracle.last().timestamp, 110);
+            assert_eq!(oracle.last().tick_cumulative, 50);
+        }
+        // one write per timestamp
+        {
+            let mut oracle = Oracle::new(100);
+            oracle.write(110, 5, Liquidity::from_integer(1));
+            oracle.write(110, 10, Liquidity::from_integer(1));
+
+            assert_eq!(oracle.last().tick_cumulative, 50);
+        }
+        // cardinality grows once the buffer wraps
+        {
+            let mut oracle = Oracle::new(100);
+            assert_eq!(oracle.grow(3).unwrap(), 3);
+            assert_eq!(oracle.cardinality, 1);
+
+            oracle.write(110, -2, Liquidity::from_integer(1));
+            assert_eq!(oracle.cardinality, 3);
+            assert_eq!(oracle.index, 1);
+
+            oracle.write(120, -2, Liquidity::from_integer(1));
+            oracle.write(130, -2, Liquidity::from_integer(1));
+            assert_eq!(oracle.index, 0);
+            assert_eq!(oracle.last().tick_cumulative, -60);
+        }
+    }
+
+    #[test]
+    fn test_grow() {
+        let mut oracle = Oracle::new(0);
+
+        assert_eq!(oracle.grow(5).unwrap(), 5);
+        assert_eq!(oracle.observations.len(), 5);
+        // never shrinks
+        assert_eq!(oracle.grow(2).unwrap(), 5);
+        assert_eq!(oracle.observations.len(), 5);
+
+        assert_eq!(
+            oracle.grow(MAX_ORACLE_CARDINALITY + 1),
+            Err(InvariantError::InvalidOracleCardinality)
+        );
+    }
+
+    #[test]
+    fn test_observe() {
+        let liquidity = Liquidity::from_integer(1);
+        let mut oracle = Oracle::new(0);
+        oracle.grow(4).unwrap();
+
+        oracle.write(10, 2, liquidity);
+        oracle.write(20, 4, liquidity);
+
+        // current tick is 6 since timestamp 20
+        let result = oracle
+            .observe(30, &[0, 5, 10, 15, 30], 6, liquidity)
+            .unwrap();
+
+        assert_eq!(result[0].0, 20 + 40 + 60);
+        assert_eq!(result[1].0, 20 + 40 + 30);
+        assert_eq!(result[2].0, 20 + 40);
+        assert_eq!(result[3].0, 20 + 20);
+        assert_eq!(result[4].0, 0);
+
+        let (_, seconds_per_liquidity_now) = result[0];
+        let (_, seconds_per_liquidity_start) = result[4];
+        assert_eq!(
+            seconds_per_liquidity_now.unchecked_sub(seconds_per_liquidity_start),
+            SecondsPerLiquidity::calculate_seconds_per_liquidity_global(liquidity, 30, 0).unwrap()
+        );
+
+        assert_eq!(
+            oracle.observe(30, &[31], 6, liquidity),
+            Err(InvariantError::ObservationTooOld)
+        );
+    }
+
+    #[test]
+    fn test_observe_after_wrap() {
+        let liquidity = Liquidity::from_integer(1);
+        let mut oracle = Oracle::new(0);
+        oracle.grow(2).unwrap();
+
+        oracle.write(10, 1, liquidity);
+        oracle.write(20, 1, liquidity);
+        oracle.write(30, 1, liquidity);
+
+        // observations at 20 and 30 are left
+        assert_eq!(oracle.observe(30, &[10], 1, liquidity).unwrap()[0].0, 20);
+        assert_eq!(oracle.observe(30, &[5], 1, liquidity).unwrap()[0].0, 25);
+        assert_eq!(
+            oracle.observe(30, &[11], 1, liquidity),
+            Err(InvariantError::ObservationTooOld)
+        );
+    }
+}
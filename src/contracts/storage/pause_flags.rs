@@ -0,0 +1,70 @@
+use crate::InvariantError;
+use sails_rs::prelude::*;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PausableAction {
+    Swap,
+    NewLiquidity,
+    Withdrawal,
+}
+
+// `all` stops swaps and new liquidity, removing liquidity and withdrawing balances
+// are stopped only by their own switch
+#[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Decode, Encode, TypeInfo)]
+pub struct PauseFlags {
+    pub all: bool,
+    pub swaps: bool,
+    pub new_liquidity: bool,
+    pub withdrawals: bool,
+}
+
+impl PauseFlags {
+    pub fn is_paused(&self, action: PausableAction) -> bool {
+        match action {
+            PausableAction::Swap => self.all || self.swaps,
+            PausableAction::NewLiquidity => self.all || self.new_liquidity,
+            PausableAction::Withdrawal => self.withdrawals,
+        }
+    }
+
+    pub fn check(&self, action: PausableAction) -> Result<(), InvariantError> {
+        if self.is_paused(action) {
+            return Err(InvariantError::ActionPaused);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_paused() {
+        let flags = PauseFlags::default();
+        assert!(!flags.is_paused(PausableAction::Swap));
+        assert!(!flags.is_paused(PausableAction::NewLiquidity));
+        assert!(!flags.is_paused(PausableAction::Withdrawal));
+
+        let flags = PauseFlags {
+            all: true,
+            ..PauseFlags::default()
+        };
+        assert!(flags.is_paused(PausableAction::Swap));
+        assert!(flags.is_paused(PausableAction::NewLiquidity));
+        assert_eq!(flags.check(PausableAction::Withdrawal), Ok(()));
+
+        let flags = PauseFlags {
+            swaps: true,
+            withdrawals: true,
+            ..PauseFlags::default()
+        };
+        assert_eq!(
+            flags.check(PausableAction::Swap),
+            Err(InvariantError::ActionPaused)
+        );
+        assert!(!flags.is_paused(PausableAction::NewLiquidity));
+        assert!(flags.is_paused(PausableAction::Withdrawal));
+    }
+}
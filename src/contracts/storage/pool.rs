@@ -1,6 +1,6 @@
 extern crate alloc;
 
-use crate::{FeeTier, InvariantError, PoolKey, Tick};
+use crate::{DynamicFee, FeeTier, InvariantError, PauseFlags, PoolKey, Tick};
 use decimal::*;
 use math::{
     clamm::*,
@@ -8,6 +8,7 @@ use math::{
     liquidity::Liquidity,
     log::get_tick_at_sqrt_price,
     percentage::Percentage,
+    seconds_per_liquidity::SecondsPerLiquidity,
     sqrt_price::{calculate_sqrt_price, check_tick_to_sqrt_price_relationship, SqrtPrice},
     token_amount::TokenAmount,
 };
@@ -21,11 +22,24 @@ pub struct Pool {
     pub current_tick_index: i32,
     pub fee_growth_global_x: FeeGrowth,
     pub fee_growth_global_y: FeeGrowth,
+    pub seconds_per_liquidity_global: SecondsPerLiquidity,
     pub fee_protocol_token_x: TokenAmount,
     pub fee_protocol_token_y: TokenAmount,
     pub start_timestamp: u64,
     pub last_timestamp: u64,
     pub fee_receiver: ActorId,
+    pub pause: PauseFlags,
+    pub protocol_fee: Option<Percentage>,
+    pub dynamic_fee: Option<DynamicFee>,
+    pub deprecated: bool,
+}
+
+// Deprecated pool accepts only exits, once it's empty it becomes removable
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Decode, Encode, TypeInfo)]
+pub enum PoolStatus {
+    Active,
+    Deprecated,
+    Removable,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -43,11 +57,16 @@ impl Default for Pool {
             current_tick_index: i32::default(),
             fee_growth_global_x: FeeGrowth::default(),
             fee_growth_global_y: FeeGrowth::default(),
+            seconds_per_liquidity_global: SecondsPerLiquidity::default(),
             fee_protocol_token_x: TokenAmount::new(U256::from(0u128)),
             fee_protocol_token_y: TokenAmount::new(U256::from(0u128)),
             start_timestamp: u64::default(),
             last_timestamp: u64::default(),
             fee_receiver: ActorId::from([0x0; 32]),
+            pause: PauseFlags::default(),
+            protocol_fee: None,
+            dynamic_fee: None,
+            deprecated: false,
         }
     }
 }
@@ -78,6 +97,17 @@ impl Pool {
         })
     }
 
+    pub fn effective_protocol_fee(&self, default_protocol_fee: Percentage) -> Percentage {
+        self.protocol_fee.unwrap_or(default_protocol_fee)
+    }
+
+    pub fn swap_fee(&self, fee_tier: FeeTier) -> Percentage {
+        match &self.dynamic_fee {
+            Some(dynamic_fee) => dynamic_fee.fee(fee_tier.fee),
+            None => fee_tier.fee,
+        }
+    }
+
     pub fn add_fee(
         &mut self,
         amount: TokenAmount,
@@ -104,6 +134,27 @@ impl Pool {
         Ok(())
     }
 
+    pub fn update_seconds_per_liquidity_global(
+        &mut self,
+        current_timestamp: u64,
+    ) -> TrackableResult<()> {
+        // time spent without liquidity doesn't count
+        if current_timestamp > self.last_timestamp && !self.liquidity.is_zero() {
+            let seconds_per_liquidity =
+                ok_or_mark_trace!(SecondsPerLiquidity::calculate_seconds_per_liquidity_global(
+                    self.liquidity,
+                    current_timestamp,
+                    self.last_timestamp,
+                ))?;
+            self.seconds_per_liquidity_global = self
+                .seconds_per_liquidity_global
+                .unchecked_add(seconds_per_liquidity);
+        }
+
+        self.last_timestamp = current_timestamp;
+        Ok(())
+    }
+
     pub fn update_liquidity(
         &mut self,
         liquidity_delta: Liquidity,
@@ -596,4 +647,55 @@ mod tests {
             assert_eq!(pool.liquidity, Liquidity::from_integer(5),)
         }
     }
+
+    #[test]
+    fn test_update_seconds_per_liquidity_global() {
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::from_integer(4),
+                last_timestamp: 15,
+                ..Default::default()
+            };
+
+            pool.update_seconds_per_liquidity_global(315360015).unwrap();
+
+            assert_eq!(
+                pool.seconds_per_liquidity_global,
+                SecondsPerLiquidity::new(788400000000000000000000000000000)
+            );
+            assert_eq!(pool.last_timestamp, 315360015);
+        }
+        // empty pool
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::new(U256::from(0)),
+                last_timestamp: 15,
+                ..Default::default()
+            };
+
+            pool.update_seconds_per_liquidity_global(100).unwrap();
+
+            assert_eq!(
+                pool.seconds_per_liquidity_global,
+                SecondsPerLiquidity::new(0)
+            );
+            assert_eq!(pool.last_timestamp, 100);
+        }
+        // same timestamp
+        {
+            let mut pool = Pool {
+                liquidity: Liquidity::from_integer(4),
+                seconds_per_liquidity_global: SecondsPerLiquidity::new(11),
+                last_timestamp: 15,
+                ..Default::default()
+            };
+
+            pool.update_seconds_per_liquidity_global(15).unwrap();
+
+            assert_eq!(
+                pool.seconds_per_liquidity_global,
+                SecondsPerLiquidity::new(11)
+            );
+        }
+    }
 }
@@ -3,6 +3,9 @@ use decimal::*;
 use math::percentage::Percentage;
 use sails_rs::prelude::*;
 
+// Compact id of a pool that its ticks and tickmap are stored under, ids aren't reused
+pub type PoolId = u32;
+
 #[derive(Decode, Encode, TypeInfo, PartialEq, Eq, Clone, Copy, Debug, Hash)]
 pub struct PoolKey {
     pub token_x: ActorId,
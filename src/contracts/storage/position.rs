@@ -7,6 +7,7 @@ use math::{
     types::{
         fee_growth::{calculate_fee_growth_inside, FeeGrowth},
         liquidity::Liquidity,
+        seconds_per_liquidity::{calculate_seconds_per_liquidity_inside, SecondsPerLiquidity},
         sqrt_price::SqrtPrice,
         token_amount::TokenAmount,
     },
@@ -14,6 +15,8 @@ use math::{
 use sails_rs::prelude::*;
 use traceable_result::*;
 
+pub type PositionId = u64;
+
 #[derive(PartialEq, Eq, Debug, Default, Clone, Copy, Decode, Encode, TypeInfo)]
 pub struct Position {
     pub pool_key: PoolKey,
@@ -22,6 +25,7 @@ pub struct Position {
     pub upper_tick_index: i32,
     pub fee_growth_inside_x: FeeGrowth,
     pub fee_growth_inside_y: FeeGrowth,
+    pub seconds_per_liquidity_inside: SecondsPerLiquidity,
     pub last_block_number: u64,
     pub tokens_owed_x: TokenAmount,
     pub tokens_owed_y: TokenAmount,
@@ -39,7 +43,7 @@ impl Position {
         current_timestamp: u64,
         tick_spacing: u16,
     ) -> TrackableResult<(TokenAmount, TokenAmount)> {
-        pool.last_timestamp = current_timestamp;
+        pool.update_seconds_per_liquidity_global(current_timestamp)?;
 
         // calculate dynamically limit allows easy modification
         let max_liquidity_per_tick = calculate_max_liquidity_per_tick(tick_spacing);
@@ -68,6 +72,19 @@ impl Position {
             fee_growth_inside_y,
         )?;
 
+        // fee-only updates keep the snapshot, the seconds inside are measured since the last
+        // liquidity change
+        if !liquidity_delta.is_zero() {
+            self.seconds_per_liquidity_inside = calculate_seconds_per_liquidity_inside(
+                lower_tick.index,
+                upper_tick.index,
+                pool.current_tick_index,
+                lower_tick.seconds_per_liquidity_outside,
+                upper_tick.seconds_per_liquidity_outside,
+                pool.seconds_per_liquidity_global,
+            )?;
+        }
+
         // calculate tokens amounts and update pool liquidity
         ok_or_mark_trace!(pool.update_liquidity(
             liquidity_delta,
@@ -154,6 +171,64 @@ impl Position {
         (tokens_owed_x, tokens_owed_y)
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn increase_liquidity(
+        &mut self,
+        pool: &mut Pool,
+        lower_tick: &mut Tick,
+        upper_tick: &mut Tick,
+        current_timestamp: u64,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+    ) -> Result<(TokenAmount, TokenAmount), InvariantError> {
+        if liquidity_delta.is_zero() {
+            return Err(InvariantError::ZeroLiquidity);
+        }
+
+        if pool.sqrt_price < slippage_limit_lower || pool.sqrt_price > slippage_limit_upper {
+            return Err(InvariantError::PriceLimitReached);
+        }
+
+        Ok(unwrap!(self.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            liquidity_delta,
+            true,
+            current_timestamp,
+            self.pool_key.fee_tier.tick_spacing
+        )))
+    }
+
+    pub fn decrease_liquidity(
+        &mut self,
+        pool: &mut Pool,
+        lower_tick: &mut Tick,
+        upper_tick: &mut Tick,
+        current_timestamp: u64,
+        liquidity_delta: Liquidity,
+    ) -> Result<(TokenAmount, TokenAmount), InvariantError> {
+        if liquidity_delta.is_zero() {
+            return Err(InvariantError::ZeroLiquidity);
+        }
+
+        // withdrawing everything has to go through remove so the ticks get deinitialized
+        if liquidity_delta >= self.liquidity {
+            return Err(InvariantError::InsufficientLiquidity);
+        }
+
+        Ok(unwrap!(self.modify(
+            pool,
+            upper_tick,
+            lower_tick,
+            liquidity_delta,
+            false,
+            current_timestamp,
+            self.pool_key.fee_tier.tick_spacing
+        )))
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn create(
         pool: &mut Pool,
@@ -179,6 +254,7 @@ impl Position {
             upper_tick_index: upper_tick.index,
             fee_growth_inside_x: FeeGrowth::new(0),
             fee_growth_inside_y: FeeGrowth::new(0),
+            seconds_per_liquidity_inside: SecondsPerLiquidity::new(0),
             last_block_number: block_number,
             tokens_owed_x: TokenAmount::new(U256::from(0)),
             tokens_owed_y: TokenAmount::new(U256::from(0)),
@@ -475,5 +551,148 @@ mod tests {
                 TokenAmount::new(U256::from(151167))
             );
         }
+        // seconds per liquidity snapshot is kept when only fees are updated
+        {
+            let seconds_per_liquidity_inside = SecondsPerLiquidity::from_integer(5);
+            let mut position = Position {
+                liquidity: Liquidity::from_integer(1),
+                seconds_per_liquidity_inside,
+                ..Default::default()
+            };
+            let mut pool = Pool {
+                current_tick_index: 0,
+                sqrt_price: SqrtPrice::from_integer(1),
+                ..Default::default()
+            };
+            let mut upper_tick = Tick {
+                index: 10,
+                liquidity_gross: Liquidity::from_integer(1),
+                ..Default::default()
+            };
+            let mut lower_tick = Tick {
+                index: -10,
+                liquidity_gross: Liquidity::from_integer(1),
+                ..Default::default()
+            };
+
+            position
+                .modify(
+                    &mut pool,
+                    &mut upper_tick,
+                    &mut lower_tick,
+                    Liquidity::new(U256::from(0)),
+                    true,
+                    0,
+                    1,
+                )
+                .unwrap();
+            assert_eq!(
+                position.seconds_per_liquidity_inside,
+                seconds_per_liquidity_inside
+            );
+
+            position
+                .modify(
+                    &mut pool,
+                    &mut upper_tick,
+                    &mut lower_tick,
+                    Liquidity::from_integer(1),
+                    true,
+                    0,
+                    1,
+                )
+                .unwrap();
+            assert_eq!(
+                position.seconds_per_liquidity_inside,
+                SecondsPerLiquidity::new(0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_increase_and_decrease_liquidity() {
+        let liquidity = Liquidity::from_integer(1000);
+        let mut position = Position {
+            liquidity,
+            lower_tick_index: -10,
+            upper_tick_index: 10,
+            ..Default::default()
+        };
+        let mut pool = Pool {
+            liquidity,
+            sqrt_price: SqrtPrice::from_integer(1),
+            current_tick_index: 0,
+            ..Default::default()
+        };
+        let mut lower_tick = Tick {
+            index: -10,
+            sign: true,
+            liquidity_change: liquidity,
+            liquidity_gross: liquidity,
+            ..Default::default()
+        };
+        let mut upper_tick = Tick {
+            index: 10,
+            sign: false,
+            liquidity_change: liquidity,
+            liquidity_gross: liquidity,
+            ..Default::default()
+        };
+
+        // slippage
+        {
+            let result = position.increase_liquidity(
+                &mut pool,
+                &mut lower_tick,
+                &mut upper_tick,
+                0,
+                liquidity,
+                SqrtPrice::from_integer(2),
+                SqrtPrice::from_integer(3),
+            );
+            assert_eq!(result, Err(InvariantError::PriceLimitReached));
+        }
+        // increase
+        let (deposited_x, deposited_y) = position
+            .increase_liquidity(
+                &mut pool,
+                &mut lower_tick,
+                &mut upper_tick,
+                0,
+                liquidity,
+                SqrtPrice::from_integer(1),
+                SqrtPrice::from_integer(1),
+            )
+            .unwrap();
+
+        assert!(deposited_x > TokenAmount::new(U256::from(0)));
+        assert!(deposited_y > TokenAmount::new(U256::from(0)));
+        assert_eq!(position.liquidity, liquidity + liquidity);
+        assert_eq!(pool.liquidity, liquidity + liquidity);
+        assert_eq!(lower_tick.liquidity_gross, liquidity + liquidity);
+        assert_eq!(upper_tick.liquidity_change, liquidity + liquidity);
+
+        // whole liquidity can't be withdrawn
+        {
+            let result = position.decrease_liquidity(
+                &mut pool,
+                &mut lower_tick,
+                &mut upper_tick,
+                0,
+                position.liquidity,
+            );
+            assert_eq!(result, Err(InvariantError::InsufficientLiquidity));
+        }
+        // decrease
+        let (withdrawn_x, withdrawn_y) = position
+            .decrease_liquidity(&mut pool, &mut lower_tick, &mut upper_tick, 0, liquidity)
+            .unwrap();
+
+        assert!(withdrawn_x <= deposited_x);
+        assert!(withdrawn_y <= deposited_y);
+        assert_eq!(position.liquidity, liquidity);
+        assert_eq!(pool.liquidity, liquidity);
+        assert_eq!(lower_tick.liquidity_gross, liquidity);
+        assert_eq!(upper_tick.liquidity_change, liquidity);
     }
 }
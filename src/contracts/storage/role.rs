@@ -0,0 +1,12 @@
+use sails_rs::prelude::*;
+
+// Permissions the admin can delegate, the admin itself holds all of them
+#[derive(Encode, Decode, TypeInfo, PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum Role {
+    FeeTierManager,
+    ProtocolFeeManager,
+    Pauser,
+    FeeReceiverManager,
+    PoolManager,
+    DynamicFeeManager,
+}
@@ -0,0 +1,194 @@
+extern crate alloc;
+
+use crate::{
+    Campaign, FeeTier, LimitOrder, Observation, PauseFlags, Pool, PoolKey, Position, PositionId,
+    Role, Stake, Tick,
+};
+use blake2::{digest::consts::U32, Blake2b, Digest};
+use math::{percentage::Percentage, token_amount::TokenAmount};
+use sails_rs::prelude::*;
+
+pub const STATE_CHUNK_SIZE: u32 = 64;
+
+pub type StateChecksum = [u8; 32];
+
+type Blake2b256 = Blake2b<U32>;
+
+// Sections are exported and imported in the order of declaration
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Decode, Encode, TypeInfo)]
+pub enum StateSection {
+    Config,
+    FeeTiers,
+    Pools,
+    Ticks,
+    Tickmap,
+    Positions,
+    Balances,
+    Roles,
+    Approvals,
+    Operators,
+    LimitOrders,
+    Campaigns,
+    Stakes,
+    Oracles,
+    Observations,
+}
+
+impl StateSection {
+    pub fn next(self) -> Option<Self> {
+        match self {
+            StateSection::Config => Some(StateSection::FeeTiers),
+            StateSection::FeeTiers => Some(StateSection::Pools),
+            StateSection::Pools => Some(StateSection::Ticks),
+            StateSection::Ticks => Some(StateSection::Tickmap),
+            StateSection::Tickmap => Some(StateSection::Positions),
+            StateSection::Positions => Some(StateSection::Balances),
+            StateSection::Balances => Some(StateSection::Roles),
+            StateSection::Roles => Some(StateSection::Approvals),
+            StateSection::Approvals => Some(StateSection::Operators),
+            StateSection::Operators => Some(StateSection::LimitOrders),
+            StateSection::LimitOrders => Some(StateSection::Campaigns),
+            StateSection::Campaigns => Some(StateSection::Stakes),
+            StateSection::Stakes => Some(StateSection::Oracles),
+            StateSection::Oracles => Some(StateSection::Observations),
+            StateSection::Observations => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Decode, Encode, TypeInfo)]
+pub struct StateCursor {
+    pub section: StateSection,
+    pub offset: u32,
+}
+
+impl Default for StateCursor {
+    fn default() -> Self {
+        Self {
+            section: StateSection::Config,
+            offset: 0,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Decode, Encode, TypeInfo)]
+pub enum StateChunk {
+    Config {
+        admin: ActorId,
+        protocol_fee: Percentage,
+        pause: PauseFlags,
+        pending_admin: Option<ActorId>,
+        // ids of removed positions and campaigns aren't reused
+        next_position_id: PositionId,
+        next_campaign_id: u64,
+    },
+    FeeTiers(Vec<FeeTier>),
+    Pools(Vec<(PoolKey, Pool)>),
+    Ticks(Vec<(PoolKey, Tick)>),
+    Tickmap(Vec<(PoolKey, u16, u64)>),
+    // positions of an owner are in the order of the owner index
+    Positions(Vec<(ActorId, PositionId, Position)>),
+    // (account, token, amount)
+    Balances(Vec<(ActorId, ActorId, TokenAmount)>),
+    Roles(Vec<(ActorId, Role)>),
+    // (position, spender)
+    Approvals(Vec<(PositionId, ActorId)>),
+    // (owner, operator)
+    Operators(Vec<(ActorId, ActorId)>),
+    LimitOrders(Vec<(PositionId, LimitOrder)>),
+    // rewards left in a campaign are held by the program on its behalf
+    Campaigns(Vec<(u64, Campaign)>),
+    Stakes(Vec<(PositionId, Stake)>),
+    // (pool, index, cardinality, cardinality next), observations are exported separately
+    Oracles(Vec<(PoolKey, u16, u16, u16)>),
+    // (pool, slot, observation)
+    Observations(Vec<(PoolKey, u16, Observation)>),
+}
+
+impl StateChunk {
+    pub fn len(&self) -> u32 {
+        let len = match self {
+            StateChunk::Config { .. } => 1,
+            StateChunk::FeeTiers(entries) => entries.len(),
+            StateChunk::Pools(entries) => entries.len(),
+            StateChunk::Ticks(entries) => entries.len(),
+            StateChunk::Tickmap(entries) => entries.len(),
+            StateChunk::Positions(entries) => entries.len(),
+            StateChunk::Balances(entries) => entries.len(),
+            StateChunk::Roles(entries) => entries.len(),
+            StateChunk::Approvals(entries) => entries.len(),
+            StateChunk::Operators(entries) => entries.len(),
+            StateChunk::LimitOrders(entries) => entries.len(),
+            StateChunk::Campaigns(entries) => entries.len(),
+            StateChunk::Stakes(entries) => entries.len(),
+            StateChunk::Oracles(entries) => entries.len(),
+            StateChunk::Observations(entries) => entries.len(),
+        };
+
+        len as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Blake2b-256 of the encoded chunk
+    pub fn checksum(&self) -> StateChecksum {
+        self.using_encoded(|bytes| Blake2b256::digest(bytes).into())
+    }
+}
+
+// Checksum of the whole state is built from the checksums of the chunks in the order they were
+// exported, starting from the default one
+pub fn chain_state_checksum(
+    checksum: StateChecksum,
+    chunk_checksum: StateChecksum,
+) -> StateChecksum {
+    let mut hasher = Blake2b256::new();
+    hasher.update(checksum);
+    hasher.update(chunk_checksum);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use decimal::*;
+
+    #[test]
+    fn test_checksum() {
+        let fee_tiers = vec![
+            FeeTier::new(Percentage::from_scale(1, 2), 1).unwrap(),
+            FeeTier::new(Percentage::from_scale(5, 3), 10).unwrap(),
+            FeeTier::new(Percentage::from_scale(1, 3), 100).unwrap(),
+        ];
+
+        let whole = StateChunk::FeeTiers(fee_tiers.clone()).checksum();
+        assert_eq!(whole, StateChunk::FeeTiers(fee_tiers.clone()).checksum());
+
+        let reversed = StateChunk::FeeTiers(fee_tiers.iter().rev().copied().collect()).checksum();
+        assert_ne!(whole, reversed);
+
+        let changed = StateChunk::FeeTiers(fee_tiers[..2].to_vec()).checksum();
+        assert_ne!(whole, changed);
+
+        // chained checksums depend on the order of the chunks
+        let first = StateChunk::FeeTiers(fee_tiers[..1].to_vec()).checksum();
+        let second = StateChunk::FeeTiers(fee_tiers[1..].to_vec()).checksum();
+        let in_order = chain_state_checksum(
+            chain_state_checksum(StateChecksum::default(), first),
+            second,
+        );
+        let out_of_order = chain_state_checksum(
+            chain_state_checksum(StateChecksum::default(), second),
+            first,
+        );
+        assert_ne!(in_order, out_of_order);
+
+        assert_ne!(
+            StateChunk::Ticks(vec![]).checksum(),
+            StateChecksum::default()
+        );
+        assert!(StateChunk::Ticks(vec![]).is_empty());
+    }
+}
@@ -3,8 +3,8 @@ extern crate alloc;
 use super::Pool;
 use decimal::*;
 use math::types::{
-    fee_growth::FeeGrowth, liquidity::Liquidity, sqrt_price::calculate_sqrt_price,
-    sqrt_price::SqrtPrice,
+    fee_growth::FeeGrowth, liquidity::Liquidity, seconds_per_liquidity::SecondsPerLiquidity,
+    sqrt_price::calculate_sqrt_price, sqrt_price::SqrtPrice,
 };
 use sails_rs::prelude::*;
 use traceable_result::*;
@@ -22,6 +22,7 @@ pub struct Tick {
     pub fee_growth_outside_x: FeeGrowth,
     pub fee_growth_outside_y: FeeGrowth,
     pub seconds_outside: u64,
+    pub seconds_per_liquidity_outside: SecondsPerLiquidity,
 }
 
 impl Default for Tick {
@@ -35,6 +36,7 @@ impl Default for Tick {
             fee_growth_outside_x: FeeGrowth::new(0),
             fee_growth_outside_y: FeeGrowth::new(0),
             seconds_outside: 0u64,
+            seconds_per_liquidity_outside: SecondsPerLiquidity::new(0),
         }
     }
 }
@@ -95,6 +97,10 @@ impl Tick {
                 true => current_timestamp - pool.start_timestamp,
                 false => 0,
             },
+            seconds_per_liquidity_outside: match below_current_tick {
+                true => pool.seconds_per_liquidity_global,
+                false => SecondsPerLiquidity::new(0),
+            },
             ..Self::default()
         }
     }
@@ -112,7 +118,10 @@ impl Tick {
             .ok_or_else(|| err!("current_timestamp - pool.start_timestamp underflow"))?;
         self.seconds_outside = seconds_passed.wrapping_sub(self.seconds_outside);
 
-        pool.last_timestamp = current_timestamp;
+        pool.update_seconds_per_liquidity_global(current_timestamp)?;
+        self.seconds_per_liquidity_outside = pool
+            .seconds_per_liquidity_global
+            .unchecked_sub(self.seconds_per_liquidity_outside);
 
         // When going to higher tick net_liquidity should be added and for going lower subtracted
         if (pool.current_tick_index >= self.index) ^ self.sign {
@@ -222,6 +231,9 @@ mod tests {
                 last_timestamp: 315360015,
                 start_timestamp: 4,
                 current_tick_index: 7,
+                seconds_per_liquidity_global: SecondsPerLiquidity::new(
+                    788400000000000000000000000000000,
+                ),
                 ..Default::default()
             };
             let result_tick = Tick {
@@ -229,6 +241,9 @@ mod tests {
                 fee_growth_outside_y: FeeGrowth::new(10),
                 index: 3,
                 seconds_outside: 315360006,
+                seconds_per_liquidity_outside: SecondsPerLiquidity::new(
+                    788400000000000000000000000000000,
+                ),
                 liquidity_change: Liquidity::from_integer(U256::from(1)),
                 ..Default::default()
             };
@@ -305,6 +320,9 @@ mod tests {
                 last_timestamp: 31536000,
                 start_timestamp: 15,
                 current_tick_index: 9,
+                seconds_per_liquidity_global: SecondsPerLiquidity::new(
+                    22525707857142857142857142857142,
+                ),
                 ..Default::default()
             };
             let result_tick = Tick {
@@ -312,6 +330,9 @@ mod tests {
                 fee_growth_outside_y: FeeGrowth::new(340282366920938463463374607431766958269u128),
                 index: 45,
                 seconds_outside: 31535911,
+                seconds_per_liquidity_outside: SecondsPerLiquidity::new(
+                    22525707857142857142857142857142,
+                ),
                 liquidity_change: Liquidity::new(U256::from(10)),
                 ..Default::default()
             };
@@ -336,6 +357,9 @@ mod tests {
                 fee_growth_outside_y: FeeGrowth::new(256),
                 index: 45,
                 seconds_outside: 74,
+                seconds_per_liquidity_outside: SecondsPerLiquidity::new(
+                    22525713142857142857142857142857143857,
+                ),
                 liquidity_change: Liquidity::new(U256::from(10)),
                 ..Default::default()
             };
@@ -346,6 +370,9 @@ mod tests {
                 last_timestamp: 315360000,
                 start_timestamp: 15,
                 current_tick_index: 9,
+                seconds_per_liquidity_global: SecondsPerLiquidity::new(
+                    22525713142857142857142857142857142857,
+                ),
                 ..Default::default()
             };
             let result_tick = Tick {
@@ -353,6 +380,9 @@ mod tests {
                 fee_growth_outside_y: FeeGrowth::new(108),
                 index: 45,
                 seconds_outside: 315359911,
+                seconds_per_liquidity_outside: SecondsPerLiquidity::new(
+                    340282366920938463463374607431768210456,
+                ),
                 liquidity_change: Liquidity::new(U256::from(10)),
                 ..Default::default()
             };
@@ -17,11 +17,11 @@ fn test_add_multiple_fee_tiers() {
     let third_fee_tier = FeeTier::new(Percentage::from_scale(2, 4), 4).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, first_fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let res = add_fee_tier(&invariant, ADMIN, second_fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let res = add_fee_tier(&invariant, ADMIN, third_fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let fee_tiers = get_fee_tiers(&invariant);
     assert_eq!(
@@ -45,7 +45,7 @@ fn test_add_existing_fee_tier() {
     let second_fee_tier = FeeTier::new(Percentage::from_scale(2, 4), 1).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, first_fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let res = add_fee_tier(&invariant, ADMIN, second_fee_tier);
     res.assert_panicked_with(InvariantError::FeeTierAlreadyExist);
 }
@@ -60,7 +60,7 @@ fn test_add_fee_tier_not_admin() {
     let first_fee_tier = FeeTier::new(Percentage::from_scale(2, 4), 1).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, first_fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 }
 
 #[test]
@@ -73,7 +73,7 @@ fn test_add_fee_tier_zero_fee() {
     let fee_tier = FeeTier::new(Percentage::new(0), 1).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 }
 #[test]
 fn test_add_fee_tier_tick_spacing_zero() {
@@ -0,0 +1,210 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::System;
+use io::{InvariantOp, InvariantOpResult};
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+    MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+fn rerange_ops(pool_key: PoolKey) -> Vec<InvariantOp> {
+    vec![
+        InvariantOp::ClaimFee { position_id: 0 },
+        InvariantOp::RemovePosition { position_id: 0 },
+        InvariantOp::CreatePosition {
+            pool_key,
+            lower_tick: -20,
+            upper_tick: 10,
+            liquidity_delta: Liquidity::from_integer(500000),
+            slippage_limit_lower: SqrtPrice::new(MIN_SQRT_PRICE.into()),
+            slippage_limit_upper: SqrtPrice::new(MAX_SQRT_PRICE.into()),
+        },
+        InvariantOp::RemovePosition { position_id: 5 },
+    ]
+}
+
+#[test]
+fn test_batch() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    let res = batch(&invariant, REGULAR_USER_1, rerange_ops(pool_key), false);
+    res.assert_success();
+    let results = res
+        .last_event()
+        .decoded_event::<Vec<Result<InvariantOpResult, InvariantError>>>()
+        .unwrap();
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(
+        results[0],
+        Ok(InvariantOpResult::ClaimFee(
+            TokenAmount::new(U256::from(5)),
+            TokenAmount::new(U256::from(0))
+        ))
+    );
+    assert!(matches!(
+        results[1],
+        Ok(InvariantOpResult::RemovePosition(_, _))
+    ));
+    assert_eq!(results[3], Err(InvariantError::PositionNotFound));
+
+    let positions = get_all_positions(&invariant, REGULAR_USER_1.into());
+    assert_eq!(positions.len(), 1);
+    assert_eq!(
+        results[2],
+        Ok(InvariantOpResult::CreatePosition(positions[0]))
+    );
+    assert_eq!(positions[0].liquidity, Liquidity::from_integer(500000));
+}
+
+#[test]
+fn test_batch_atomic() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let pool_before = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let position_before = get_position(&invariant, 0).unwrap();
+
+    batch(&invariant, REGULAR_USER_1, rerange_ops(pool_key), true)
+        .assert_panicked_with(InvariantError::PositionNotFound);
+
+    // nothing from the batch is applied
+    assert_eq!(
+        get_pool(&invariant, token_x, token_y, fee_tier).unwrap(),
+        pool_before
+    );
+    assert_eq!(get_position(&invariant, 0), Ok(position_before));
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_1), vec![]);
+
+    let mut ops = rerange_ops(pool_key);
+    ops.pop();
+    ops.push(InvariantOp::TransferPosition {
+        position_id: 1,
+        receiver: REGULAR_USER_2.into(),
+    });
+
+    let res = batch(&invariant, REGULAR_USER_1, ops, true);
+    res.assert_success();
+    let results = res
+        .last_event()
+        .decoded_event::<Vec<Result<InvariantOpResult, InvariantError>>>()
+        .unwrap();
+    assert_eq!(results[3], Ok(InvariantOpResult::TransferPosition));
+
+    assert_eq!(get_all_positions(&invariant, REGULAR_USER_1.into()), vec![]);
+    assert_eq!(
+        get_all_positions(&invariant, REGULAR_USER_2.into()).len(),
+        1
+    );
+}
+
+#[test]
+fn test_batch_failed_operation_keeps_state() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    let total_reward = TokenAmount::from_integer(1000);
+    mint(&token_x_program, REGULAR_USER_2, total_reward.get()).assert_success();
+    increase_allowance(
+        &token_x_program,
+        REGULAR_USER_2,
+        INVARIANT_ID,
+        total_reward.get(),
+    )
+    .assert_success();
+    deposit_single_token(
+        &invariant,
+        REGULAR_USER_2,
+        TOKEN_X_ID,
+        total_reward.get(),
+        None::<&str>,
+    )
+    .unwrap();
+
+    let start_timestamp = sys.block_timestamp() + 1_000;
+    let res = create_campaign(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        token_x,
+        total_reward,
+        start_timestamp,
+        start_timestamp + 1_000_000,
+    );
+    res.assert_success();
+    let campaign_id = res.last_event().decoded_event::<u64>().unwrap();
+
+    while sys.block_timestamp() < start_timestamp {
+        sys.spend_blocks(1);
+    }
+    stake(&invariant, REGULAR_USER_1, 0, campaign_id).assert_success();
+
+    let pool_before = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let position_before = get_position(&invariant, 0).unwrap();
+    let lower_tick_before = get_tick(&invariant, pool_key, -20).unwrap();
+    let upper_tick_before = get_tick(&invariant, pool_key, 10).unwrap();
+
+    let res = batch(
+        &invariant,
+        REGULAR_USER_1,
+        vec![InvariantOp::RemovePosition { position_id: 0 }],
+        false,
+    );
+    res.assert_success();
+    let results = res
+        .last_event()
+        .decoded_event::<Vec<Result<InvariantOpResult, InvariantError>>>()
+        .unwrap();
+    assert_eq!(results, vec![Err(InvariantError::PositionStaked)]);
+
+    // the failed removal didn't touch the pool or its ticks
+    assert_eq!(
+        get_pool(&invariant, token_x, token_y, fee_tier).unwrap(),
+        pool_before
+    );
+    assert_eq!(get_position(&invariant, 0), Ok(position_before));
+    assert_eq!(get_tick(&invariant, pool_key, -20), Ok(lower_tick_before));
+    assert_eq!(get_tick(&invariant, pool_key, 10), Ok(upper_tick_before));
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_1), vec![]);
+}
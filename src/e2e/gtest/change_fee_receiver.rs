@@ -21,7 +21,7 @@ fn test_change_fee_receiver() {
         tick_spacing: 1,
     };
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_sqrt_price = calculate_sqrt_price(0).unwrap();
 
@@ -34,7 +34,7 @@ fn test_change_fee_receiver() {
         init_sqrt_price,
         0,
     );
-    res.assert_single_event()
+    res.assert_event_and_reply()
         .assert_empty()
         .assert_to(REGULAR_USER_1);
 
@@ -43,7 +43,7 @@ fn test_change_fee_receiver() {
 
     let pool_key = PoolKey::new(token_0, token_1, fee_tier).unwrap();
     change_fee_receiver(&invariant, ADMIN, pool_key, REGULAR_USER_1.into())
-        .assert_single_event()
+        .assert_event_and_reply()
         .assert_empty()
         .assert_to(ADMIN);
 
@@ -65,7 +65,7 @@ fn test_change_fee_receiver_not_admin() {
         tick_spacing: 1,
     };
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_sqrt_price = calculate_sqrt_price(0).unwrap();
 
@@ -78,7 +78,7 @@ fn test_change_fee_receiver_not_admin() {
         init_sqrt_price,
         0,
     );
-    res.assert_single_event()
+    res.assert_event_and_reply()
         .assert_empty()
         .assert_to(REGULAR_USER_1);
 
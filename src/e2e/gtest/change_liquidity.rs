@@ -0,0 +1,214 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+    MAX_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+fn internal_balance(invariant: &Program, user: u64, token: ActorId) -> TokenAmount {
+    get_user_balances(invariant, user)
+        .into_iter()
+        .find(|(balance_token, _)| *balance_token == token)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default()
+}
+
+fn deposit_tokens(invariant: &Program, token_x_program: &Program, token_y_program: &Program) {
+    let amount = U256::from(10u128.pow(10));
+
+    mint(token_x_program, REGULAR_USER_1, amount).assert_success();
+    mint(token_y_program, REGULAR_USER_1, amount).assert_success();
+    increase_allowance(token_x_program, REGULAR_USER_1, INVARIANT_ID, amount).assert_success();
+    increase_allowance(token_y_program, REGULAR_USER_1, INVARIANT_ID, amount).assert_success();
+
+    deposit_single_token(invariant, REGULAR_USER_1, TOKEN_X_ID, amount, None::<&str>).unwrap();
+    deposit_single_token(invariant, REGULAR_USER_1, TOKEN_Y_ID, amount, None::<&str>).unwrap();
+}
+
+#[test]
+fn test_increase_and_decrease_liquidity() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_cross_position(&invariant, &token_x_program, &token_y_program);
+    deposit_tokens(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let liquidity_delta = Liquidity::from_integer(1000000);
+
+    let pool_before = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let position_before = get_position(&invariant, 0).unwrap();
+    let other_position = get_position(&invariant, 1).unwrap();
+    let balance_x_before = internal_balance(&invariant, REGULAR_USER_1, token_x);
+    let balance_y_before = internal_balance(&invariant, REGULAR_USER_1, token_y);
+
+    // increase
+    let res = increase_liquidity(
+        &invariant,
+        REGULAR_USER_1,
+        0,
+        liquidity_delta,
+        pool_before.sqrt_price,
+        pool_before.sqrt_price,
+    );
+    res.assert_success();
+
+    let events = res.emitted_events();
+    assert_eq!(events.len(), 2);
+
+    let increased_event = events[0]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<PositionLiquidityIncreasedEvent>()
+        .unwrap();
+    assert_eq!(
+        increased_event,
+        PositionLiquidityIncreasedEvent {
+            timestamp: increased_event.timestamp,
+            address: REGULAR_USER_1.into(),
+            pool_key,
+            position_id: 0,
+            liquidity_delta,
+            lower_tick: position_before.lower_tick_index,
+            upper_tick: position_before.upper_tick_index,
+            current_sqrt_price: pool_before.sqrt_price,
+        }
+    );
+
+    let position = get_position(&invariant, 0).unwrap();
+    assert_eq!(
+        position.liquidity,
+        position_before.liquidity + liquidity_delta
+    );
+    assert_eq!(position.lower_tick_index, position_before.lower_tick_index);
+    assert_eq!(position.upper_tick_index, position_before.upper_tick_index);
+    assert_eq!(get_position(&invariant, 1).unwrap(), other_position);
+
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.liquidity, pool_before.liquidity + liquidity_delta);
+
+    let deposited_x = balance_x_before - internal_balance(&invariant, REGULAR_USER_1, token_x);
+    let deposited_y = balance_y_before - internal_balance(&invariant, REGULAR_USER_1, token_y);
+    assert!(deposited_x > TokenAmount::new(U256::from(0)));
+    assert!(deposited_y > TokenAmount::new(U256::from(0)));
+
+    // decrease
+    let res = decrease_liquidity(&invariant, REGULAR_USER_1, 0, liquidity_delta);
+    res.assert_success();
+
+    let events = res.emitted_events();
+    assert_eq!(events.len(), 2);
+
+    events[0]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<PositionLiquidityDecreasedEvent>()
+        .unwrap();
+    let (withdrawn_x, withdrawn_y) = events[1]
+        .assert_to(REGULAR_USER_1)
+        .decoded_event::<(TokenAmount, TokenAmount)>()
+        .unwrap();
+
+    // rounding is always in favor of the pool
+    assert!(
+        withdrawn_x <= deposited_x && withdrawn_x + TokenAmount::new(U256::from(1)) >= deposited_x
+    );
+    assert!(
+        withdrawn_y <= deposited_y && withdrawn_y + TokenAmount::new(U256::from(1)) >= deposited_y
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_x),
+        balance_x_before - deposited_x + withdrawn_x
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_y),
+        balance_y_before - deposited_y + withdrawn_y
+    );
+
+    let position = get_position(&invariant, 0).unwrap();
+    assert_eq!(position.liquidity, position_before.liquidity);
+
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.liquidity, pool_before.liquidity);
+}
+
+#[test]
+fn test_change_liquidity_errors() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let position = get_position(&invariant, 0).unwrap();
+
+    increase_liquidity(
+        &invariant,
+        REGULAR_USER_1,
+        0,
+        Liquidity::new(U256::from(0)),
+        pool.sqrt_price,
+        pool.sqrt_price,
+    )
+    .assert_panicked_with(InvariantError::ZeroLiquidity);
+
+    increase_liquidity(
+        &invariant,
+        REGULAR_USER_1,
+        0,
+        Liquidity::from_integer(1),
+        pool.sqrt_price + SqrtPrice::new(1),
+        SqrtPrice::new(MAX_SQRT_PRICE),
+    )
+    .assert_panicked_with(InvariantError::PriceLimitReached);
+
+    // tokens were withdrawn after the position was created
+    increase_liquidity(
+        &invariant,
+        REGULAR_USER_1,
+        0,
+        Liquidity::from_integer(1),
+        pool.sqrt_price,
+        pool.sqrt_price,
+    )
+    .assert_panicked_with(InvariantError::NoBalanceForTheToken);
+
+    increase_liquidity(
+        &invariant,
+        REGULAR_USER_2,
+        0,
+        Liquidity::from_integer(1),
+        pool.sqrt_price,
+        pool.sqrt_price,
+    )
+    .assert_panicked_with(InvariantError::PositionNotFound);
+
+    decrease_liquidity(&invariant, REGULAR_USER_1, 0, Liquidity::new(U256::from(0)))
+        .assert_panicked_with(InvariantError::ZeroLiquidity);
+
+    decrease_liquidity(&invariant, REGULAR_USER_1, 0, position.liquidity)
+        .assert_panicked_with(InvariantError::InsufficientLiquidity);
+
+    decrease_liquidity(&invariant, REGULAR_USER_1, 1, Liquidity::from_integer(1))
+        .assert_panicked_with(InvariantError::PositionNotFound);
+
+    assert_eq!(get_position(&invariant, 0).unwrap(), position);
+}
@@ -9,7 +9,11 @@ fn test_change_protocol_fee() {
     let sys = System::new();
     let invariant = init_invariant(&sys, Percentage(0));
     let res = change_protocol_fee(&invariant, ADMIN, Percentage(1));
-    res.assert_single_event().assert_to(ADMIN).assert_empty();
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<ProtocolFeeChangedEvent>()
+        .unwrap();
+    assert_eq!(event.protocol_fee, Percentage(1));
 
     assert_eq!(get_protocol_fee(&invariant), Percentage(1));
 }
@@ -53,7 +53,7 @@ fn test_claim() {
     let user_amount_after_claim = balance_of(&token_x_program, REGULAR_USER_1);
     let invariant_amount_after_claim = balance_of(&token_x_program, INVARIANT_ID);
 
-    let position = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let position = get_position(&invariant, 0).unwrap();
 
     assert_eq!(
         user_amount_after_claim - expected_tokens_claimed,
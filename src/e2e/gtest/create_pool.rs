@@ -24,7 +24,7 @@ fn test_create_pool() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let res = create_pool(
         &invariant,
@@ -35,7 +35,7 @@ fn test_create_pool() {
         init_sqrt_price,
         init_tick,
     );
-    res.assert_single_event()
+    res.assert_event_and_reply()
         .assert_empty()
         .assert_to(REGULAR_USER_1);
 
@@ -71,7 +71,7 @@ fn test_create_pool_x_to_y_and_y_to_x() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let res = create_pool(
         &invariant,
@@ -82,7 +82,7 @@ fn test_create_pool_x_to_y_and_y_to_x() {
         init_sqrt_price,
         init_tick,
     );
-    res.assert_single_event()
+    res.assert_event_and_reply()
         .assert_empty()
         .assert_to(REGULAR_USER_1);
 
@@ -117,7 +117,7 @@ fn test_create_pool_with_same_tokens() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let res = create_pool(
         &invariant,
@@ -175,7 +175,7 @@ fn test_create_pool_init_tick_not_divisible_by_tick_spacing() {
     let fee_tier = FeeTier::new(Percentage::from_scale(5, 1), 3).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_tick = 2;
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
@@ -207,7 +207,7 @@ fn test_create_pool_init_sqrt_price_minimal_difference_from_tick() {
     let fee_tier = FeeTier::new(Percentage::from_scale(5, 1), 3).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_tick = 0;
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap() + SqrtPrice::new(1);
@@ -221,7 +221,7 @@ fn test_create_pool_init_sqrt_price_minimal_difference_from_tick() {
         init_sqrt_price,
         init_tick,
     );
-    res.assert_single_event()
+    res.assert_event_and_reply()
         .assert_empty()
         .assert_to(REGULAR_USER_1);
 
@@ -246,7 +246,7 @@ fn test_create_pool_init_sqrt_price_has_closer_init_tick() {
     let fee_tier = FeeTier::new(Percentage::from_scale(5, 1), 1).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_tick = 2;
     let init_sqrt_price = SqrtPrice::new(1000175003749000000000000u128);
@@ -272,7 +272,7 @@ fn test_create_pool_init_sqrt_price_has_closer_init_tick() {
         init_sqrt_price,
         correct_tick_index,
     );
-    res.assert_single_event()
+    res.assert_event_and_reply()
         .assert_empty()
         .assert_to(REGULAR_USER_1);
 
@@ -297,7 +297,7 @@ fn test_create_pool_init_sqrt_price_has_closer_init_tick_spacing_over_one() {
     let fee_tier = FeeTier::new(Percentage::from_scale(5, 1), 3).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_tick = 0;
     let init_sqrt_price = SqrtPrice::new(1000225003749000000000000u128);
@@ -323,7 +323,7 @@ fn test_create_pool_init_sqrt_price_has_closer_init_tick_spacing_over_one() {
         init_sqrt_price,
         correct_tick_index,
     );
-    res.assert_single_event()
+    res.assert_event_and_reply()
         .assert_empty()
         .assert_to(REGULAR_USER_1);
 
@@ -40,7 +40,7 @@ fn test_cross_both_sides() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -328,7 +328,7 @@ fn test_cross_both_sides_not_cross_case() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -0,0 +1,81 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use io::SwapHop;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+    MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+#[test]
+fn test_deadline() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+
+    let expired = Some(sys.block_timestamp() - 1);
+    let pending = Some(sys.block_timestamp() + 100_000);
+
+    create_position_with_deadline(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -10,
+        10,
+        Liquidity::from_integer(1),
+        pool.sqrt_price,
+        pool.sqrt_price,
+        expired,
+    )
+    .assert_panicked_with(InvariantError::DeadlineExceeded);
+
+    swap_with_deadline(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        TokenAmount::from_integer(10),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE.into()),
+        expired,
+    )
+    .assert_panicked_with(InvariantError::DeadlineExceeded);
+
+    swap_route_with_deadline(
+        &invariant,
+        REGULAR_USER_2,
+        TokenAmount::from_integer(10),
+        TokenAmount::from_integer(1),
+        Percentage::new(0),
+        vec![SwapHop {
+            pool_key,
+            x_to_y: true,
+        }],
+        expired,
+    )
+    .assert_panicked_with(InvariantError::DeadlineExceeded);
+
+    remove_position_with_deadline(&invariant, REGULAR_USER_1, 0, expired)
+        .assert_panicked_with(InvariantError::DeadlineExceeded);
+
+    assert_eq!(
+        get_pool(&invariant, token_x, token_y, fee_tier).unwrap(),
+        pool
+    );
+
+    remove_position_with_deadline(&invariant, REGULAR_USER_1, 0, pending).assert_success();
+}
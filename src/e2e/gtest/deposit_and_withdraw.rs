@@ -0,0 +1,217 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::calculate_sqrt_price,
+    token_amount::TokenAmount,
+};
+use sails_rs::prelude::*;
+
+fn internal_balance(invariant: &Program, user: u64, token: ActorId) -> TokenAmount {
+    get_user_balances(invariant, user)
+        .into_iter()
+        .find(|(balance_token, _)| *balance_token == token)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default()
+}
+
+fn approve_tokens(token_x_program: &Program, token_y_program: &Program, user: u64) -> U256 {
+    let amount = U256::from(10u128.pow(10));
+
+    mint(token_x_program, user, amount).assert_success();
+    mint(token_y_program, user, amount).assert_success();
+    increase_allowance(token_x_program, user, INVARIANT_ID, amount).assert_success();
+    increase_allowance(token_y_program, user, INVARIANT_ID, amount).assert_success();
+
+    amount
+}
+
+#[test]
+fn test_deposit_and_create_position() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    let minted = approve_tokens(&token_x_program, &token_y_program, REGULAR_USER_1);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let sqrt_price = calculate_sqrt_price(0).unwrap();
+    let amount = TokenAmount::from_integer(1000);
+
+    let res = deposit_and_create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -10,
+        10,
+        Liquidity::from_integer(1000000),
+        sqrt_price,
+        sqrt_price,
+        amount,
+        amount,
+    );
+    res.assert_success();
+    let (position, unused_x, unused_y) = res
+        .last_event()
+        .decoded_event::<(Position, TokenAmount, TokenAmount)>()
+        .unwrap();
+
+    assert_eq!(get_position(&invariant, 0), Ok(position));
+    assert!(!unused_x.is_zero());
+    assert!(!unused_y.is_zero());
+
+    // only the amount used by the position is kept by the contract
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_1), vec![]);
+    assert_eq!(
+        balance_of(&token_x_program, REGULAR_USER_1),
+        minted - (amount - unused_x).get()
+    );
+    assert_eq!(
+        balance_of(&token_y_program, REGULAR_USER_1),
+        minted - (amount - unused_y).get()
+    );
+    assert_eq!(
+        balance_of(&token_x_program, INVARIANT_ID),
+        (amount - unused_x).get()
+    );
+}
+
+#[test]
+fn test_deposit_and_create_position_fails() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    approve_tokens(&token_x_program, &token_y_program, REGULAR_USER_1);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let sqrt_price = calculate_sqrt_price(0).unwrap();
+    let amount = TokenAmount::from_integer(1000);
+
+    deposit_and_create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        10,
+        10,
+        Liquidity::from_integer(1000000),
+        sqrt_price,
+        sqrt_price,
+        amount,
+        amount,
+    )
+    .assert_panicked_with(InvariantError::InvalidTickIndex);
+
+    // deposited tokens are recoverable from the internal balance
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_x),
+        amount
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_y),
+        amount
+    );
+    assert_eq!(get_all_positions(&invariant, REGULAR_USER_1.into()), vec![]);
+
+    let unknown_pool_key = PoolKey::new(
+        token_x,
+        token_y,
+        FeeTier::new(Percentage::from_scale(1, 2), 1).unwrap(),
+    )
+    .unwrap();
+
+    // nothing is deposited when the pool doesn't exist
+    deposit_and_create_position(
+        &invariant,
+        REGULAR_USER_1,
+        unknown_pool_key,
+        -10,
+        10,
+        Liquidity::from_integer(1000000),
+        sqrt_price,
+        sqrt_price,
+        amount,
+        amount,
+    )
+    .assert_panicked_with(InvariantError::PoolNotFound);
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_x),
+        amount
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_y),
+        amount
+    );
+}
+
+#[test]
+fn test_claim_fee_and_remove_position_with_withdraw() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    let balance_x_before = balance_of(&token_x_program, REGULAR_USER_1);
+    let balance_y_before = balance_of(&token_y_program, REGULAR_USER_1);
+
+    let res = claim_fee_and_withdraw(&invariant, REGULAR_USER_1, 0);
+    res.assert_success();
+    assert_eq!(
+        res.last_event()
+            .decoded_event::<(TokenAmount, TokenAmount)>()
+            .unwrap(),
+        (
+            TokenAmount::new(U256::from(5)),
+            TokenAmount::new(U256::from(0))
+        )
+    );
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_1), vec![]);
+    assert_eq!(
+        balance_of(&token_x_program, REGULAR_USER_1),
+        balance_x_before + U256::from(5)
+    );
+
+    let res = remove_position_and_withdraw(&invariant, REGULAR_USER_1, 0);
+    res.assert_success();
+    let (amount_x, amount_y) = res
+        .last_event()
+        .decoded_event::<(TokenAmount, TokenAmount)>()
+        .unwrap();
+
+    assert_eq!(get_all_positions(&invariant, REGULAR_USER_1.into()), vec![]);
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_1), vec![]);
+    assert_eq!(
+        balance_of(&token_x_program, REGULAR_USER_1),
+        balance_x_before + U256::from(5) + amount_x.get()
+    );
+    assert_eq!(
+        balance_of(&token_y_program, REGULAR_USER_1),
+        balance_y_before + amount_y.get()
+    );
+
+    remove_position_and_withdraw(&invariant, REGULAR_USER_1, 0)
+        .assert_panicked_with(InvariantError::PositionNotFound);
+}
@@ -0,0 +1,160 @@
+use crate::test_helpers::gtest::*;
+use contracts::{Role, *};
+use decimal::*;
+use gtest::*;
+use io::CalculateSwapResult;
+use math::{
+    percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount, MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+fn quote_and_swap(
+    invariant: &Program,
+    pool_key: PoolKey,
+    amount: TokenAmount,
+) -> CalculateSwapResult {
+    let sqrt_price_limit = SqrtPrice::new(MIN_SQRT_PRICE.into());
+    let quote_result = quote(
+        invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        amount,
+        true,
+        sqrt_price_limit,
+    )
+    .unwrap();
+
+    let res = swap(
+        invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        amount,
+        true,
+        sqrt_price_limit,
+    );
+    res.assert_success();
+    let result = res
+        .last_event()
+        .decoded_event::<CalculateSwapResult>()
+        .unwrap();
+
+    // quotes are exact under the dynamic fee
+    assert_eq!(result.amount_in, quote_result.amount_in);
+    assert_eq!(result.amount_out, quote_result.amount_out);
+    assert_eq!(result.target_sqrt_price, quote_result.target_sqrt_price);
+
+    result
+}
+
+#[test]
+fn test_dynamic_fee() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    let amount = U256::from(600);
+    mint(&token_x_program, REGULAR_USER_2, amount).assert_success();
+    increase_allowance(&token_x_program, REGULAR_USER_2, INVARIANT_ID, amount).assert_success();
+    deposit_single_token(&invariant, REGULAR_USER_2, TOKEN_X_ID, amount, None::<&str>).unwrap();
+
+    let dynamic_fee = DynamicFee::new(
+        Percentage::from_scale(1, 3),
+        Percentage::from_scale(5, 2),
+        Percentage::from_scale(1, 3),
+    )
+    .unwrap();
+
+    change_pool_dynamic_fee(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        Some(dynamic_fee.clone()),
+    )
+    .assert_panicked_with(InvariantError::NotAdmin);
+    change_pool_dynamic_fee(
+        &invariant,
+        ADMIN,
+        pool_key,
+        Some(DynamicFee {
+            min_fee: Percentage::from_scale(5, 2),
+            max_fee: Percentage::from_scale(1, 3),
+            ..dynamic_fee.clone()
+        }),
+    )
+    .assert_panicked_with(InvariantError::InvalidFee);
+
+    let swap_amount = TokenAmount::new(U256::from(300));
+    let static_quote = quote(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        swap_amount,
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE.into()),
+    )
+    .unwrap();
+
+    let res = change_pool_dynamic_fee(&invariant, ADMIN, pool_key, Some(dynamic_fee.clone()));
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<PoolDynamicFeeChangedEvent>()
+        .unwrap();
+    assert_eq!(event.pool_key, pool_key);
+    assert_eq!(event.dynamic_fee, Some(dynamic_fee.clone()));
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.dynamic_fee, Some(dynamic_fee));
+
+    // no movement is recorded yet, the fee tier fee is used
+    let first = quote_and_swap(&invariant, pool_key, swap_amount);
+    assert_eq!(first.amount_out, static_quote.amount_out);
+
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let recorded = pool.dynamic_fee.unwrap();
+    assert_eq!(
+        recorded.tick_movements,
+        vec![pool.current_tick_index.unsigned_abs()]
+    );
+    assert!(recorded.fee(fee_tier.fee) > fee_tier.fee);
+
+    // price movement of the previous swap raises the fee
+    let second = quote_and_swap(&invariant, pool_key, swap_amount);
+    assert!(second.fee > first.fee);
+
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.dynamic_fee.unwrap().tick_movements.len(), 2);
+
+    // the fee tier manager can't change the dynamic fee, only its own role can
+    grant_role(
+        &invariant,
+        ADMIN,
+        REGULAR_USER_1.into(),
+        Role::FeeTierManager,
+    )
+    .assert_success();
+    change_pool_dynamic_fee(&invariant, REGULAR_USER_1, pool_key, None)
+        .assert_panicked_with(InvariantError::NotAdmin);
+    grant_role(
+        &invariant,
+        ADMIN,
+        REGULAR_USER_1.into(),
+        Role::DynamicFeeManager,
+    )
+    .assert_success();
+    change_pool_dynamic_fee(&invariant, REGULAR_USER_1, pool_key, None).assert_success();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.dynamic_fee, None);
+}
@@ -0,0 +1,119 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount};
+use sails_rs::ActorId;
+
+#[test]
+fn test_fee_tier_and_pool_events() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(5, 1), 1).unwrap();
+    let res = add_fee_tier(&invariant, ADMIN, fee_tier);
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<FeeTierAddedEvent>()
+        .unwrap();
+    assert_eq!(event.fee_tier, fee_tier);
+
+    let init_sqrt_price = SqrtPrice::from_integer(1);
+    let res = create_pool(
+        &invariant,
+        REGULAR_USER_1,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        0,
+    );
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<PoolCreatedEvent>()
+        .unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    assert_eq!(event.address, REGULAR_USER_1.into());
+    assert_eq!(event.pool_key, pool_key);
+    assert_eq!(event.init_sqrt_price, init_sqrt_price);
+    assert_eq!(event.init_tick, 0);
+
+    let res = change_fee_receiver(&invariant, ADMIN, pool_key, REGULAR_USER_2.into());
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<FeeReceiverChangedEvent>()
+        .unwrap();
+    assert_eq!(event.pool_key, pool_key);
+    assert_eq!(event.fee_receiver, REGULAR_USER_2.into());
+
+    let res = remove_fee_tier(&invariant, ADMIN, fee_tier);
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<FeeTierRemovedEvent>()
+        .unwrap();
+    assert_eq!(event.fee_tier, fee_tier);
+}
+
+#[test]
+fn test_fee_and_position_events() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    let res = claim_fee_and_withdraw(&invariant, REGULAR_USER_1, 0);
+    res.assert_success();
+    let events = res.emitted_events();
+    assert_eq!(events.len(), 3);
+    let claimed = events[0]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<FeeClaimedEvent>()
+        .unwrap();
+    assert_eq!(claimed.address, REGULAR_USER_1.into());
+    assert_eq!(claimed.position_id, 0);
+    assert_eq!(claimed.pool_key, pool_key);
+    assert_eq!(claimed.amount_x, TokenAmount::new(U256::from(5)));
+    assert_eq!(claimed.amount_y, TokenAmount::new(U256::from(0)));
+    let withdrawn = events[1]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<TokenWithdrawnEvent>()
+        .unwrap();
+    assert_eq!(withdrawn.address, REGULAR_USER_1.into());
+    assert_eq!(withdrawn.token, token_x);
+    assert_eq!(withdrawn.amount, claimed.amount_x);
+
+    let res = withdraw_protocol_fee(&invariant, ADMIN, pool_key);
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<ProtocolFeeWithdrawnEvent>()
+        .unwrap();
+    assert_eq!(event.address, ADMIN.into());
+    assert_eq!(event.pool_key, pool_key);
+    assert_eq!(event.amount_x, TokenAmount::new(U256::from(1)));
+    assert_eq!(event.amount_y, TokenAmount::new(U256::from(0)));
+
+    let res = transfer_position(&invariant, REGULAR_USER_1, 0, REGULAR_USER_2);
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<PositionTransferredEvent>()
+        .unwrap();
+    assert_eq!(event.position_id, 0);
+    assert_eq!(event.from, REGULAR_USER_1.into());
+    assert_eq!(event.to, REGULAR_USER_2.into());
+}
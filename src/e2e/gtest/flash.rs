@@ -0,0 +1,312 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity,
+    percentage::Percentage,
+    sqrt_price::{calculate_sqrt_price, SqrtPrice},
+    token_amount::TokenAmount,
+    MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+fn internal_balance(invariant: &Program, user: u64, token: ActorId) -> TokenAmount {
+    get_user_balances(invariant, user)
+        .into_iter()
+        .find(|(balance_token, _)| *balance_token == token)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default()
+}
+
+fn init_zero_fee_pool(
+    invariant: &Program,
+    token_x_program: &Program,
+    token_y_program: &Program,
+) -> PoolKey {
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+    let fee_tier = FeeTier::new(Percentage::new(0), 1).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    add_fee_tier(invariant, ADMIN, fee_tier).assert_success();
+    create_pool(
+        invariant,
+        REGULAR_USER_1,
+        token_x,
+        token_y,
+        fee_tier,
+        calculate_sqrt_price(0).unwrap(),
+        0,
+    )
+    .assert_success();
+
+    let amount = U256::from(10u128.pow(10));
+    mint(token_x_program, REGULAR_USER_1, amount).assert_success();
+    mint(token_y_program, REGULAR_USER_1, amount).assert_success();
+    increase_allowance(token_x_program, REGULAR_USER_1, INVARIANT_ID, amount).assert_success();
+    increase_allowance(token_y_program, REGULAR_USER_1, INVARIANT_ID, amount).assert_success();
+    deposit_single_token(invariant, REGULAR_USER_1, TOKEN_X_ID, amount, None::<&str>).unwrap();
+    deposit_single_token(invariant, REGULAR_USER_1, TOKEN_Y_ID, amount, None::<&str>).unwrap();
+
+    let sqrt_price = calculate_sqrt_price(0).unwrap();
+    create_position(
+        invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -1000,
+        1000,
+        Liquidity::from_integer(1000000),
+        sqrt_price,
+        sqrt_price,
+    )
+    .assert_success();
+
+    pool_key
+}
+
+fn reply_to_flash(sys: &System, receiver: u64, payload: Vec<u8>) -> RunResult {
+    let log = Log::builder()
+        .source(INVARIANT_ID)
+        .dest(receiver)
+        .payload_bytes(payload);
+    sys.get_mailbox(receiver)
+        .reply_bytes(log, Vec::<u8>::new(), 0)
+        .unwrap()
+}
+
+#[test]
+fn test_flash() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    let pool_key = init_zero_fee_pool(&invariant, &token_x_program, &token_y_program);
+    let amount_x = TokenAmount::from_integer(1000);
+    let amount_y = TokenAmount::from_integer(500);
+
+    flash(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        amount_x,
+        amount_y,
+        REGULAR_USER_2,
+        vec![1, 2, 3],
+    )
+    .assert_success();
+
+    // the lent balance can't be used before the loan is repaid
+    assert_eq!(
+        get_flash_loan(&invariant, REGULAR_USER_2).unwrap(),
+        FlashLoan::create(pool_key, amount_x, amount_y).unwrap()
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_2, token_x),
+        amount_x
+    );
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        amount_x,
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE.into()),
+    )
+    .assert_panicked_with(InvariantError::FlashLoanActive);
+    withdraw_single_token(
+        &invariant,
+        REGULAR_USER_2,
+        token_x,
+        None,
+        Some(InvariantError::FlashLoanActive),
+    );
+
+    let res = reply_to_flash(&sys, REGULAR_USER_2, vec![1, 2, 3]);
+    res.assert_success();
+
+    let events = res.emitted_events();
+    let flash_loan_event = events[events.len() - 2]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<FlashLoanEvent>()
+        .unwrap();
+    assert_eq!(
+        flash_loan_event,
+        FlashLoanEvent {
+            timestamp: flash_loan_event.timestamp,
+            address: REGULAR_USER_2.into(),
+            pool_key,
+            amount_x,
+            amount_y,
+            fee_x: TokenAmount::new(U256::from(0)),
+            fee_y: TokenAmount::new(U256::from(0)),
+        }
+    );
+    assert_eq!(
+        res.last_event()
+            .decoded_event::<(TokenAmount, TokenAmount)>()
+            .unwrap(),
+        (
+            TokenAmount::new(U256::from(0)),
+            TokenAmount::new(U256::from(0))
+        )
+    );
+
+    // the loan was returned from the lent tokens
+    assert_eq!(
+        get_flash_loan(&invariant, REGULAR_USER_2),
+        Err(InvariantError::FlashLoanNotFound)
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_2, token_x),
+        TokenAmount::default()
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_2, token_y),
+        TokenAmount::default()
+    );
+}
+
+#[test]
+fn test_flash_not_repaid() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let pool_before = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let amount_x = TokenAmount::from_integer(100);
+
+    // the receiver doesn't have the fee
+    flash(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        amount_x,
+        TokenAmount::new(U256::from(0)),
+        REGULAR_USER_2,
+        vec![],
+    )
+    .assert_success();
+
+    let res = reply_to_flash(&sys, REGULAR_USER_2, vec![]);
+    assert_eq!(
+        res.last_event(),
+        TestEvent {
+            payload: InvariantError::FlashLoanNotRepaid.encode(),
+            source: ActorId::from(INVARIANT_ID).into(),
+            destination: ActorId::from(REGULAR_USER_2).into(),
+        }
+    );
+
+    // the whole loan is taken back within the same call
+    assert_eq!(
+        get_flash_loan(&invariant, REGULAR_USER_2),
+        Err(InvariantError::FlashLoanNotFound)
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_2, token_x),
+        TokenAmount::default()
+    );
+    assert_eq!(
+        get_pool(&invariant, token_x, token_y, fee_tier).unwrap(),
+        pool_before
+    );
+}
+
+#[test]
+fn test_flash_errors() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let amount = TokenAmount::from_integer(100);
+
+    flash(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        amount,
+        amount,
+        REGULAR_USER_2,
+        vec![],
+    )
+    .assert_panicked_with(InvariantError::PoolNotFound);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+
+    flash(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        amount,
+        amount,
+        REGULAR_USER_2,
+        vec![],
+    )
+    .assert_panicked_with(InvariantError::ZeroLiquidity);
+
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    // only the caller can receive the loan
+    flash(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        amount,
+        amount,
+        REGULAR_USER_1,
+        vec![],
+    )
+    .assert_panicked_with(InvariantError::NotFlashLoanReceiver);
+
+    flash(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        TokenAmount::new(U256::from(0)),
+        TokenAmount::new(U256::from(0)),
+        REGULAR_USER_2,
+        vec![],
+    )
+    .assert_panicked_with(InvariantError::AmountIsZero);
+
+    // the position holds less than 1000 of token x
+    flash(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        TokenAmount::from_integer(1000),
+        TokenAmount::new(U256::from(0)),
+        REGULAR_USER_2,
+        vec![],
+    )
+    .assert_panicked_with(InvariantError::InsufficientPoolReserves);
+
+    assert_eq!(
+        get_flash_loan(&invariant, REGULAR_USER_2),
+        Err(InvariantError::FlashLoanNotFound)
+    );
+}
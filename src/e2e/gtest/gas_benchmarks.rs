@@ -0,0 +1,195 @@
+use crate::{send_request, test_helpers::gtest::*};
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+    MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+// Gas burned by the same calls on d8b251a, where ticks and the tickmap were keyed by the whole
+// pool key and the tickmap queries went through every possible chunk
+const BASELINE_SWAP_GAS: [(i32, u64); 3] =
+    [(1, 6_000_000_000), (8, 9_500_000_000), (32, 21_000_000_000)];
+// (get_tickmap, get_liquidity_ticks_amount) for tick spacing 1 and 100
+const BASELINE_TICKMAP_QUERIES_GAS: [(u64, u64); 2] =
+    [(2_500_000_000, 2_000_000_000), (700_000_000, 650_000_000)];
+
+fn gas_burned(res: RunResult) -> u64 {
+    res.assert_success();
+    // gtest keeps the amount private, its display is the only way to read it
+    res.main_gas_burned()
+        .to_string()
+        .parse()
+        .expect("Gas is displayed as a number")
+}
+
+fn init_balances(invariant: &Program, token_x_program: &Program, token_y_program: &Program) {
+    let amount = U256::from(10u128.pow(10));
+    for user in [REGULAR_USER_1, REGULAR_USER_2] {
+        for (token, token_program) in [(TOKEN_X_ID, token_x_program), (TOKEN_Y_ID, token_y_program)]
+        {
+            mint(token_program, user, amount).assert_success();
+            increase_allowance(token_program, user, INVARIANT_ID, amount).assert_success();
+            deposit_single_token(invariant, user, token, amount, None::<&str>).unwrap();
+        }
+    }
+}
+
+// Gas of a swap that crosses ticks of `crossed` narrow positions placed right below the price
+fn swap_gas(crossed: i32) -> u64 {
+    let sys = System::new();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+    init_balances(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(1, 4), 1).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    add_fee_tier(&invariant, ADMIN, fee_tier).assert_success();
+    create_pool(
+        &invariant,
+        REGULAR_USER_1,
+        token_x,
+        token_y,
+        fee_tier,
+        SqrtPrice::from_integer(1),
+        0,
+    )
+    .assert_success();
+
+    let liquidity = Liquidity::from_integer(1000000);
+    let sqrt_price = SqrtPrice::from_integer(1);
+    let ranges = (0..crossed).map(|i| (-2 * (i + 1), -2 * i));
+    for (lower_tick, upper_tick) in [(-1000, 1000)].into_iter().chain(ranges) {
+        create_position(
+            &invariant,
+            REGULAR_USER_1,
+            pool_key,
+            lower_tick,
+            upper_tick,
+            liquidity,
+            sqrt_price,
+            sqrt_price,
+        )
+        .assert_success();
+    }
+
+    // each of the ranges takes about 200 of token x, the rest moves the price past the last one
+    let amount = TokenAmount::new(U256::from(200 * crossed as u128 + 100));
+    let gas = gas_burned(swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        amount,
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+    ));
+
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert!(pool.current_tick_index < -2 * crossed);
+
+    gas
+}
+
+#[test]
+fn test_gas_swap_crossing_ticks() {
+    let base = swap_gas(1);
+    assert!(base < BASELINE_SWAP_GAS[0].1);
+
+    let mut previous = base;
+    for &(crossed, baseline) in &BASELINE_SWAP_GAS[1..] {
+        let gas = swap_gas(crossed);
+        assert!(gas > previous);
+        assert!(gas < baseline);
+
+        // crossing a tick costs less than the swap itself
+        let per_tick = (gas - base) / (crossed as u64 - 1);
+        assert!(per_tick < base);
+        previous = gas;
+    }
+}
+
+#[test]
+fn test_gas_tickmap_queries() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+    init_balances(&invariant, &token_x_program, &token_y_program);
+
+    let dense_fee_tier = FeeTier::new(Percentage::from_scale(1, 4), 1).unwrap();
+    let sparse_fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 100).unwrap();
+
+    let mut gas = vec![];
+    for fee_tier in [dense_fee_tier, sparse_fee_tier] {
+        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+        add_fee_tier(&invariant, ADMIN, fee_tier).assert_success();
+        create_pool(
+            &invariant,
+            REGULAR_USER_1,
+            token_x,
+            token_y,
+            fee_tier,
+            SqrtPrice::from_integer(1),
+            0,
+        )
+        .assert_success();
+
+        // every tick is in its own chunk
+        let chunk_span = 64 * fee_tier.tick_spacing as i32;
+        for i in 1..=4 {
+            create_position(
+                &invariant,
+                REGULAR_USER_1,
+                pool_key,
+                -i * chunk_span,
+                i * chunk_span,
+                Liquidity::from_integer(1000),
+                SqrtPrice::from_integer(1),
+                SqrtPrice::from_integer(1),
+            )
+            .assert_success();
+        }
+        assert_eq!(get_tickmap(&invariant, pool_key).len(), 8);
+        assert_eq!(get_liquidity_ticks_amount(&invariant, pool_key), 8);
+
+        let tickmap_gas = gas_burned(send_request!(
+            program: invariant,
+            user: PROGRAM_OWNER,
+            service_name: "Service",
+            action: "GetTickmap",
+            payload: (pool_key)
+        ));
+        let liquidity_ticks_gas = gas_burned(send_request!(
+            program: invariant,
+            user: PROGRAM_OWNER,
+            service_name: "Service",
+            action: "GetLiquidityTicksAmount",
+            payload: (pool_key)
+        ));
+        gas.push((tickmap_gas, liquidity_ticks_gas));
+    }
+
+    // only populated chunks are visited, so a pool with a hundred times more possible chunks
+    // costs about the same to query
+    let (dense, sparse) = (gas[0], gas[1]);
+    assert!(dense.0 * 5 < sparse.0 * 6);
+    assert!(dense.1 * 5 < sparse.1 * 6);
+
+    for ((tickmap_gas, liquidity_ticks_gas), (tickmap_baseline, liquidity_ticks_baseline)) in
+        gas.into_iter().zip(BASELINE_TICKMAP_QUERIES_GAS)
+    {
+        assert!(tickmap_gas < tickmap_baseline);
+        assert!(liquidity_ticks_gas < liquidity_ticks_baseline);
+    }
+}
@@ -34,7 +34,7 @@ fn test_get_pool() {
         init_sqrt_price,
         init_tick,
     );
-    res.assert_single_event()
+    res.assert_event_and_reply()
         .assert_empty()
         .assert_to(REGULAR_USER_1);
 
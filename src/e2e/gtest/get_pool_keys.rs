@@ -21,7 +21,7 @@ fn test_get_pool_keys() {
         tick_spacing: 1,
     };
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let init_sqrt_price = calculate_sqrt_price(0).unwrap();
     let init_tick = 0;
 
@@ -34,7 +34,7 @@ fn test_get_pool_keys() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -30,14 +30,14 @@ fn test_get_position_with_associates() {
     let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
     let (lower_tick_index, upper_tick_index) = (-20, 10);
 
-    let position_regular = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let position_regular = get_position(&invariant, 0).unwrap();
 
     let pool_regular = get_pool(&invariant, token_y, token_x, fee_tier).unwrap();
     let lower_tick_regular = get_tick(&invariant, pool_key, lower_tick_index).unwrap();
     let upper_tick_regular = get_tick(&invariant, pool_key, upper_tick_index).unwrap();
 
     let (position, pool, lower_tick, upper_tick) =
-        get_position_with_associates(&invariant, REGULAR_USER_1, 0).unwrap();
+        get_position_with_associates(&invariant, 0).unwrap();
 
     assert_eq!(position_regular, position);
     assert_eq!(pool_regular, pool);
@@ -56,7 +56,7 @@ fn test_position_does_not_exist() {
     let token_y: ActorId = TOKEN_Y_ID.into();
 
     init_basic_pool(&invariant, &token_x, &token_y);
-    let result = get_position_with_associates(&invariant, REGULAR_USER_1, 0);
+    let result = get_position_with_associates(&invariant, 0);
 
     assert_eq!(result, Err(InvariantError::PositionNotFound));
 }
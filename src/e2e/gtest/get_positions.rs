@@ -7,7 +7,7 @@ use math::types::{
     percentage::Percentage,
     sqrt_price::{calculate_sqrt_price, SqrtPrice},
 };
-use sails_rs::ActorId;
+use sails_rs::prelude::*;
 
 #[test]
 fn test_get_positions() {
@@ -456,3 +456,72 @@ fn test_get_positions_with_offset_more_than_exist() {
     assert_eq!(result.0[0].1.len(), 1);
     assert_eq!(result.1, 2);
 }
+
+#[test]
+fn test_get_positions_ids_after_remove() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let invariant = init_invariant(&sys, Percentage::new(0));
+    let (token_x_program, token_y_program) = init_tokens_with_mint(&sys, (500.into(), 500.into()));
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let fee_tier = FeeTier::new(Percentage::new(0), 1).unwrap();
+
+    add_fee_tier(&invariant, ADMIN, fee_tier).assert_success();
+
+    let init_tick = 10;
+    let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
+    create_pool(
+        &invariant,
+        REGULAR_USER_1,
+        token_x,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        init_tick,
+    )
+    .assert_success();
+
+    increase_allowance(&token_x_program, REGULAR_USER_1, INVARIANT_ID, 500.into()).assert_success();
+    increase_allowance(&token_y_program, REGULAR_USER_1, INVARIANT_ID, 500.into()).assert_success();
+
+    deposit_token_pair(
+        &invariant,
+        REGULAR_USER_1,
+        token_x,
+        500.into(),
+        token_y,
+        500.into(),
+        None::<&str>,
+    );
+
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    for (lower_tick, upper_tick) in [(-10, 10), (-20, 20), (-30, 30)] {
+        create_position(
+            &invariant,
+            REGULAR_USER_1,
+            pool_key,
+            lower_tick,
+            upper_tick,
+            Liquidity::new(10.into()),
+            SqrtPrice::new(0),
+            SqrtPrice::max_instance(),
+        )
+        .assert_success();
+    }
+
+    // the last position takes the index of the removed one and keeps its id
+    remove_position(&invariant, REGULAR_USER_1, 0).assert_success();
+
+    let result = get_positions(&invariant, REGULAR_USER_1, 2, 0).unwrap();
+    let ids: Vec<_> = result.0[0].1.iter().map(|(_, id)| *id).collect();
+    assert_eq!(ids, vec![2, 1]);
+    assert_eq!(result.0[0].1[0].0.lower_tick_index, -30);
+
+    let result = get_positions(&invariant, REGULAR_USER_1, 1, 1).unwrap();
+    assert_eq!(result.0[0].1[0].1, 1);
+    assert_eq!(result.1, 2);
+}
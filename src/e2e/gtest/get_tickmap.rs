@@ -27,7 +27,7 @@ fn test_get_tickmap() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -37,7 +37,7 @@ fn test_get_tickmap() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
     increase_allowance(
@@ -111,7 +111,7 @@ fn test_get_tickmap_tick_spacing_over_one() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -121,7 +121,7 @@ fn test_get_tickmap_tick_spacing_over_one() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
     increase_allowance(
@@ -210,7 +210,7 @@ fn test_get_tickmap_edge_ticks_intialized() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -220,7 +220,7 @@ fn test_get_tickmap_edge_ticks_intialized() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
     increase_allowance(
@@ -309,7 +309,7 @@ fn test_get_tickmap_more_chunks_above() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -319,7 +319,7 @@ fn test_get_tickmap_more_chunks_above() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
     increase_allowance(
@@ -391,7 +391,7 @@ fn test_get_tickmap_more_chunks_below() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -401,7 +401,7 @@ fn test_get_tickmap_more_chunks_below() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
     increase_allowance(
@@ -0,0 +1,284 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{liquidity::Liquidity, percentage::Percentage, token_amount::TokenAmount};
+use sails_rs::prelude::*;
+
+fn internal_balance(invariant: &Program, user: u64, token: ActorId) -> TokenAmount {
+    get_user_balances(invariant, user)
+        .into_iter()
+        .find(|(balance_token, _)| *balance_token == token)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default()
+}
+
+fn spend_until(sys: &System, timestamp: u64) {
+    while sys.block_timestamp() < timestamp {
+        sys.spend_blocks(1);
+    }
+}
+
+fn init_campaign(
+    sys: &System,
+    invariant: &Program,
+    token_x_program: &Program,
+    total_reward: TokenAmount,
+) -> (u64, Campaign) {
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    mint(token_x_program, REGULAR_USER_2, total_reward.get()).assert_success();
+    increase_allowance(
+        token_x_program,
+        REGULAR_USER_2,
+        INVARIANT_ID,
+        total_reward.get(),
+    )
+    .assert_success();
+    deposit_single_token(
+        invariant,
+        REGULAR_USER_2,
+        TOKEN_X_ID,
+        total_reward.get(),
+        None::<&str>,
+    )
+    .unwrap();
+
+    let start_timestamp = sys.block_timestamp() + 100_000;
+    let end_timestamp = start_timestamp + 1_000_000;
+
+    let res = create_campaign(
+        invariant,
+        REGULAR_USER_2,
+        pool_key,
+        token_x,
+        total_reward,
+        start_timestamp,
+        end_timestamp,
+    );
+    res.assert_success();
+    let campaign_id = res.last_event().decoded_event::<u64>().unwrap();
+
+    (campaign_id, get_campaign(invariant, campaign_id).unwrap())
+}
+
+#[test]
+fn test_incentives_campaign() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let total_reward = TokenAmount::from_integer(1_000_000);
+    let (campaign_id, campaign) = init_campaign(&sys, &invariant, &token_x_program, total_reward);
+
+    assert_eq!(campaign.creator, REGULAR_USER_2.into());
+    assert_eq!(campaign.total_reward_unclaimed, total_reward);
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_2, token_x),
+        TokenAmount::default()
+    );
+
+    stake(&invariant, REGULAR_USER_1, 0, campaign_id)
+        .assert_panicked_with(InvariantError::CampaignNotActive);
+
+    spend_until(&sys, campaign.start_timestamp);
+
+    stake(&invariant, REGULAR_USER_1, 0, campaign_id).assert_success();
+    stake(&invariant, REGULAR_USER_1, 0, campaign_id)
+        .assert_panicked_with(InvariantError::PositionStaked);
+    remove_position(&invariant, REGULAR_USER_1, 0)
+        .assert_panicked_with(InvariantError::PositionStaked);
+    decrease_liquidity(&invariant, REGULAR_USER_1, 0, Liquidity::from_integer(1))
+        .assert_panicked_with(InvariantError::PositionStaked);
+    assert_eq!(
+        get_campaign(&invariant, campaign_id)
+            .unwrap()
+            .number_of_stakes,
+        1
+    );
+
+    sys.spend_blocks(10);
+
+    assert!(get_pending_reward(&invariant, 0).unwrap() > TokenAmount::default());
+
+    let balance_before = internal_balance(&invariant, REGULAR_USER_1, token_x);
+    let res = claim_reward(&invariant, REGULAR_USER_1, 0);
+    res.assert_success();
+    let claimed = res.last_event().decoded_event::<TokenAmount>().unwrap();
+    assert!(claimed > TokenAmount::default());
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_x),
+        balance_before + claimed
+    );
+
+    end_campaign(&invariant, REGULAR_USER_2, campaign_id)
+        .assert_panicked_with(InvariantError::CampaignStillActive);
+    unstake(&invariant, REGULAR_USER_2, 0).assert_panicked_with(InvariantError::PositionNotFound);
+
+    spend_until(&sys, campaign.end_timestamp);
+
+    // the creator unstakes the position of the staker, the reward goes to the owner
+    let balance_before = internal_balance(&invariant, REGULAR_USER_1, token_x);
+    let res = unstake(&invariant, REGULAR_USER_2, 0);
+    res.assert_success();
+    let unstaked = res.last_event().decoded_event::<TokenAmount>().unwrap();
+    assert!(unstaked > TokenAmount::default());
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_x),
+        balance_before + unstaked
+    );
+    assert_eq!(
+        get_stake(&invariant, 0),
+        Err(InvariantError::PositionNotStaked)
+    );
+
+    let campaign = get_campaign(&invariant, campaign_id).unwrap();
+    assert_eq!(campaign.number_of_stakes, 0);
+    assert_eq!(
+        campaign.total_reward_unclaimed + claimed + unstaked,
+        total_reward
+    );
+
+    let res = end_campaign(&invariant, REGULAR_USER_1, campaign_id);
+    res.assert_success();
+    let refund = res.last_event().decoded_event::<TokenAmount>().unwrap();
+    assert_eq!(refund, campaign.total_reward_unclaimed);
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_2, token_x),
+        refund
+    );
+    assert_eq!(
+        get_campaign(&invariant, campaign_id),
+        Err(InvariantError::CampaignNotFound)
+    );
+
+    remove_position(&invariant, REGULAR_USER_1, 0).assert_success();
+}
+
+#[test]
+fn test_incentives_stake_keeps_position_id() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_cross_position(&invariant, &token_x_program, &token_y_program);
+
+    let (campaign_id, campaign) = init_campaign(
+        &sys,
+        &invariant,
+        &token_x_program,
+        TokenAmount::from_integer(1000),
+    );
+    spend_until(&sys, campaign.start_timestamp);
+
+    stake(&invariant, REGULAR_USER_1, 1, campaign_id).assert_success();
+    let staked_position = get_position(&invariant, 1).unwrap();
+
+    // the staked position takes the freed index but keeps its id
+    remove_position(&invariant, REGULAR_USER_1, 0).assert_success();
+
+    assert_eq!(
+        get_position_by_index(&invariant, REGULAR_USER_1.into(), 0).unwrap(),
+        staked_position
+    );
+    assert_eq!(get_position(&invariant, 1).unwrap(), staked_position);
+    assert_eq!(get_stake(&invariant, 1).unwrap().campaign_id, campaign_id);
+    get_stake(&invariant, 0).unwrap_err();
+
+    transfer_position(&invariant, REGULAR_USER_1, 1, REGULAR_USER_2)
+        .assert_panicked_with(InvariantError::PositionStaked);
+    stake(&invariant, REGULAR_USER_2, 1, campaign_id)
+        .assert_panicked_with(InvariantError::PositionNotFound);
+}
+
+#[test]
+fn test_incentives_invalid_campaign() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, _token_y_program) = init_tokens(&sys);
+    init_basic_pool(&invariant, &token_x, &token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let total_reward = TokenAmount::from_integer(1000);
+
+    // no balance to fund the campaign
+    create_campaign(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        token_x,
+        total_reward,
+        sys.block_timestamp() + 100_000,
+        sys.block_timestamp() + 200_000,
+    )
+    .assert_panicked_with(InvariantError::NoBalanceForTheToken);
+
+    mint(&token_x_program, REGULAR_USER_2, total_reward.get()).assert_success();
+    increase_allowance(
+        &token_x_program,
+        REGULAR_USER_2,
+        INVARIANT_ID,
+        total_reward.get(),
+    )
+    .assert_success();
+    deposit_single_token(
+        &invariant,
+        REGULAR_USER_2,
+        TOKEN_X_ID,
+        total_reward.get(),
+        None::<&str>,
+    )
+    .unwrap();
+
+    create_campaign(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        token_x,
+        total_reward,
+        sys.block_timestamp() + 200_000,
+        sys.block_timestamp() + 100_000,
+    )
+    .assert_panicked_with(InvariantError::InvalidCampaignTime);
+
+    let other_pool_key = PoolKey::new(
+        token_x,
+        token_y,
+        FeeTier::new(Percentage::from_scale(1, 2), 1).unwrap(),
+    )
+    .unwrap();
+    create_campaign(
+        &invariant,
+        REGULAR_USER_2,
+        other_pool_key,
+        token_x,
+        total_reward,
+        sys.block_timestamp() + 100_000,
+        sys.block_timestamp() + 200_000,
+    )
+    .assert_panicked_with(InvariantError::PoolNotFound);
+}
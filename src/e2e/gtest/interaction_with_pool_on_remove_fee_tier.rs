@@ -71,22 +71,24 @@ fn test_interaction_with_pool_on_removed_fee_tier() {
 
     // Transfer position
     init_basic_position(&invariant, &token_x_program, &token_y_program);
-    let transferred_index = 0;
+    let transferred_position_id = 1;
     let owner_list_before = get_all_positions(&invariant, REGULAR_USER_1.into());
     let recipient_list_before = get_all_positions(&invariant, REGULAR_USER_2.into());
-    let removed_position =
-        get_position(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap();
+    let removed_position = get_position(&invariant, transferred_position_id).unwrap();
 
     transfer_position(
         &invariant,
         REGULAR_USER_1,
-        transferred_index,
+        transferred_position_id,
         REGULAR_USER_2,
     )
     .assert_success();
 
-    let recipient_position =
-        get_position(&invariant, REGULAR_USER_2.into(), transferred_index).unwrap();
+    let recipient_position = get_position(&invariant, transferred_position_id).unwrap();
+    assert_eq!(
+        get_position_owner(&invariant, transferred_position_id),
+        Ok(REGULAR_USER_2.into())
+    );
     let owner_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
     let recipient_list_after = get_all_positions(&invariant, REGULAR_USER_2.into());
 
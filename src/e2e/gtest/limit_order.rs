@@ -0,0 +1,274 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+    MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+fn deposit_tokens(
+    invariant: &Program,
+    token_x_program: &Program,
+    token_y_program: &Program,
+    user: u64,
+) {
+    let amount = U256::from(10u128.pow(10));
+
+    mint(token_x_program, user, amount).assert_success();
+    mint(token_y_program, user, amount).assert_success();
+    increase_allowance(token_x_program, user, INVARIANT_ID, amount).assert_success();
+    increase_allowance(token_y_program, user, INVARIANT_ID, amount).assert_success();
+
+    deposit_single_token(invariant, user, TOKEN_X_ID, amount, None::<&str>).unwrap();
+    deposit_single_token(invariant, user, TOKEN_Y_ID, amount, None::<&str>).unwrap();
+}
+
+fn get_pool_by_key(invariant: &Program, pool_key: PoolKey) -> Pool {
+    get_pool(
+        invariant,
+        pool_key.token_x,
+        pool_key.token_y,
+        pool_key.fee_tier,
+    )
+    .unwrap()
+}
+
+fn init_limit_order_pool(sys: &System) -> (Program<'_>, PoolKey) {
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    deposit_tokens(
+        &invariant,
+        &token_x_program,
+        &token_y_program,
+        REGULAR_USER_1,
+    );
+    deposit_tokens(
+        &invariant,
+        &token_x_program,
+        &token_y_program,
+        REGULAR_USER_2,
+    );
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+
+    create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -100,
+        100,
+        Liquidity::from_integer(1000000),
+        pool.sqrt_price,
+        pool.sqrt_price,
+    )
+    .assert_success();
+
+    (invariant, pool_key)
+}
+
+#[test]
+fn test_limit_order_settles_on_cross() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let (invariant, pool_key) = init_limit_order_pool(&sys);
+    let liquidity = Liquidity::from_integer(1000000);
+
+    // selling x above the current price
+    create_limit_order(&invariant, REGULAR_USER_1, pool_key, 10, liquidity).assert_success();
+    let limit_order = get_limit_order(&invariant, 1).unwrap();
+    assert_eq!(
+        limit_order,
+        LimitOrder {
+            pool_key,
+            x_to_y: true,
+            settlement_tick_index: 20,
+            settled: false,
+        }
+    );
+    let position = get_position(&invariant, 1).unwrap();
+    assert_eq!(position.lower_tick_index, 10);
+    assert_eq!(position.upper_tick_index, 20);
+
+    // moving into the range fills the order only partially
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        false,
+        TokenAmount::from_integer(700),
+        true,
+        SqrtPrice::new(MAX_SQRT_PRICE),
+    )
+    .assert_success();
+    let pool = get_pool_by_key(&invariant, pool_key);
+    assert_eq!(pool.current_tick_index, 10);
+    assert!(!get_limit_order(&invariant, 1).unwrap().settled);
+
+    let res = swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        false,
+        TokenAmount::from_integer(1300),
+        true,
+        SqrtPrice::new(MAX_SQRT_PRICE),
+    );
+    res.assert_success();
+    let pool = get_pool_by_key(&invariant, pool_key);
+    assert!(pool.current_tick_index >= 20);
+    assert!(get_limit_order(&invariant, 1).unwrap().settled);
+
+    let settled_position = get_position(&invariant, 1).unwrap();
+    let events = res.emitted_events();
+    let event = events[events.len() - 2]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<LimitOrderSettledEvent>()
+        .unwrap();
+    assert_eq!(event.pool_key, pool_key);
+    assert_eq!(event.position_id, 1);
+    assert_eq!(event.amount_x, settled_position.tokens_owed_x);
+    assert_eq!(event.amount_y, settled_position.tokens_owed_y);
+    assert_eq!(settled_position.liquidity, Liquidity::new(U256::from(0)));
+    assert_eq!(
+        settled_position.tokens_owed_x,
+        TokenAmount::new(U256::from(0))
+    );
+    assert!(settled_position.tokens_owed_y > TokenAmount::from_integer(500));
+    assert!(!is_tick_initialized(&invariant, pool_key, 10));
+    assert!(!is_tick_initialized(&invariant, pool_key, 20));
+
+    // the order does not flip back when the price returns
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        TokenAmount::from_integer(3000),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+    )
+    .assert_success();
+    let pool = get_pool_by_key(&invariant, pool_key);
+    assert!(pool.current_tick_index < 10);
+    assert_eq!(get_position(&invariant, 1).unwrap(), settled_position);
+
+    increase_liquidity(
+        &invariant,
+        REGULAR_USER_1,
+        1,
+        liquidity,
+        pool.sqrt_price,
+        pool.sqrt_price,
+    )
+    .assert_panicked_with(InvariantError::LimitOrderSettled);
+
+    let (claimed_x, claimed_y) =
+        claim_fee(&invariant, REGULAR_USER_1, 1, None::<InvariantError>).unwrap();
+    assert_eq!(claimed_x, TokenAmount::new(U256::from(0)));
+    assert_eq!(claimed_y, settled_position.tokens_owed_y);
+
+    remove_position(&invariant, REGULAR_USER_1, 1).assert_success();
+    assert_eq!(
+        get_limit_order(&invariant, 1),
+        Err(InvariantError::PositionNotFound)
+    );
+}
+
+#[test]
+fn test_limit_order_below_current_price() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let (invariant, pool_key) = init_limit_order_pool(&sys);
+
+    // selling y below the current price
+    create_limit_order(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -20,
+        Liquidity::from_integer(1000000),
+    )
+    .assert_success();
+    let limit_order = get_limit_order(&invariant, 1).unwrap();
+    assert!(!limit_order.x_to_y);
+    assert_eq!(limit_order.settlement_tick_index, -20);
+
+    // swaps moving the price away from the range do not settle it
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        false,
+        TokenAmount::from_integer(1000),
+        true,
+        SqrtPrice::new(MAX_SQRT_PRICE),
+    )
+    .assert_success();
+    assert!(!get_limit_order(&invariant, 1).unwrap().settled);
+
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        TokenAmount::from_integer(4000),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+    )
+    .assert_success();
+    let pool = get_pool_by_key(&invariant, pool_key);
+    assert!(pool.current_tick_index < -20);
+    assert!(get_limit_order(&invariant, 1).unwrap().settled);
+
+    let position = get_position(&invariant, 1).unwrap();
+    assert!(position.tokens_owed_x > TokenAmount::new(U256::from(0)));
+    assert_eq!(position.tokens_owed_y, TokenAmount::new(U256::from(0)));
+
+    let res = remove_position(&invariant, REGULAR_USER_1, 1);
+    res.assert_success();
+    let amounts = res
+        .emitted_events()
+        .last()
+        .unwrap()
+        .decoded_event::<(TokenAmount, TokenAmount)>()
+        .unwrap();
+    assert_eq!(amounts, (position.tokens_owed_x, position.tokens_owed_y));
+}
+
+#[test]
+fn test_invalid_limit_order() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let (invariant, pool_key) = init_limit_order_pool(&sys);
+    let liquidity = Liquidity::from_integer(1000);
+
+    // the current tick is inside of the range
+    create_limit_order(&invariant, REGULAR_USER_1, pool_key, 0, liquidity)
+        .assert_panicked_with(InvariantError::InvalidLimitOrder);
+    create_limit_order(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        10,
+        Liquidity::new(U256::from(0)),
+    )
+    .assert_panicked_with(InvariantError::ZeroLiquidity);
+
+    // regular positions are not limit orders
+    assert_eq!(
+        get_limit_order(&invariant, 0),
+        Err(InvariantError::PositionNotFound)
+    );
+}
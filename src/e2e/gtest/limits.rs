@@ -65,7 +65,7 @@ fn test_limits_big_deposit_both_tokens() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -181,7 +181,7 @@ fn test_deposit_limits_at_upper_limit() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -261,7 +261,7 @@ fn test_limits_big_deposit_and_swaps() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -417,7 +417,7 @@ fn test_limits_full_range_with_max_liquidity() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -52,7 +52,7 @@ fn test_liquidity_gap() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
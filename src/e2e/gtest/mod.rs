@@ -29,3 +29,23 @@ pub mod get_positions;
 pub mod swap_route;
 pub mod get_position_with_associates;
 pub mod balances_vara;
+pub mod oracle;
+pub mod seconds_per_liquidity;
+pub mod incentives;
+pub mod change_liquidity;
+pub mod position_approvals;
+pub mod position_nft;
+pub mod limit_order;
+pub mod flash;
+pub mod split_route;
+pub mod deadline;
+pub mod swap_withdraw;
+pub mod deposit_and_withdraw;
+pub mod batch;
+pub mod pause;
+pub mod roles;
+pub mod dynamic_fee;
+pub mod pool_status;
+pub mod events;
+pub mod state_migration;
+pub mod gas_benchmarks;
@@ -0,0 +1,120 @@
+use crate::invariant_service::{ORACLE_SLOT_PRICE, VARA_ADDRESS};
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{percentage::Percentage, token_amount::TokenAmount};
+use sails_rs::prelude::*;
+
+#[test]
+fn test_observe() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    increase_observation_cardinality(&invariant, ADMIN, pool_key, 10, 0).assert_success();
+    assert_eq!(
+        get_oracle(&invariant, pool_key).unwrap().cardinality_next,
+        10
+    );
+
+    sys.spend_blocks(10);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    sys.spend_blocks(10);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+    sys.spend_blocks(10);
+
+    let oracle = get_oracle(&invariant, pool_key).unwrap();
+    assert_eq!(oracle.cardinality, 10);
+    assert_eq!(oracle.index, 2);
+
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let last = oracle.last();
+    let now = sys.block_timestamp();
+
+    let result = observe(&invariant, pool_key, vec![0, now - last.timestamp]).unwrap();
+    assert_eq!(result[1].0, last.tick_cumulative);
+    assert_eq!(
+        result[0].0,
+        last.tick_cumulative + pool.current_tick_index as i64 * (now - last.timestamp) as i64
+    );
+    assert_eq!(
+        observe(
+            &invariant,
+            pool_key,
+            vec![now - oracle.observations[0].timestamp + 1]
+        ),
+        Err(InvariantError::ObservationTooOld)
+    );
+}
+
+#[test]
+fn test_increase_observation_cardinality_paid() {
+    let sys = System::new();
+    sys.init_logger();
+    sys.mint_to(REGULAR_USER_1, 1_000_000_000_000_000);
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    init_basic_pool(&invariant, &token_x, &token_y);
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    increase_observation_cardinality(&invariant, REGULAR_USER_1, pool_key, 5, 0)
+        .assert_panicked_with(InvariantError::InsufficientOracleFee);
+
+    // four new slots, anything paid above their price is returned to the caller
+    let fee = 4 * ORACLE_SLOT_PRICE;
+    let overpaid = 1_000_000;
+    let res =
+        increase_observation_cardinality(&invariant, REGULAR_USER_1, pool_key, 5, fee + overpaid);
+    res.assert_success();
+    // the value is sent in a separate message before the reply
+    let events = res.emitted_events();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1].decoded_event::<u16>().unwrap(), 5);
+    assert_eq!(
+        get_oracle(&invariant, pool_key).unwrap().cardinality_next,
+        5
+    );
+    assert_eq!(
+        get_user_balances(&invariant, ADMIN),
+        vec![(VARA_ADDRESS, TokenAmount::new(fee.into()))]
+    );
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_1), vec![]);
+
+    let balance = sys.balance_of(REGULAR_USER_1);
+    let log =
+        Log::builder().payload_bytes(("Service", "IncreaseObservationCardinality", 5u16).encode());
+    sys.get_mailbox(REGULAR_USER_1).claim_value(log).unwrap();
+    assert_eq!(sys.balance_of(REGULAR_USER_1), balance + overpaid);
+    assert_eq!(sys.balance_of(INVARIANT_ID), fee);
+}
+
+#[test]
+fn test_increase_observation_cardinality_limit() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    init_basic_pool(&invariant, &token_x, &token_y);
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    increase_observation_cardinality(&invariant, ADMIN, pool_key, MAX_ORACLE_CARDINALITY + 1, 0)
+        .assert_panicked_with(InvariantError::InvalidOracleCardinality);
+}
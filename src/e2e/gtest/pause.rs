@@ -0,0 +1,200 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::calculate_sqrt_price,
+    sqrt_price::SqrtPrice, token_amount::TokenAmount, MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+fn deposit_token_x(invariant: &Program, token_x_program: &Program, user: u64) {
+    let amount = U256::from(1000);
+
+    mint(token_x_program, user, amount).assert_success();
+    increase_allowance(token_x_program, user, INVARIANT_ID, amount).assert_success();
+    deposit_single_token(invariant, user, TOKEN_X_ID, amount, None::<&str>).unwrap();
+}
+
+#[test]
+fn test_global_pause() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    deposit_token_x(&invariant, &token_x_program, REGULAR_USER_2);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let sqrt_price = calculate_sqrt_price(0).unwrap();
+    let pause = PauseFlags {
+        all: true,
+        ..PauseFlags::default()
+    };
+
+    change_pause_flags(&invariant, REGULAR_USER_1, None, pause)
+        .assert_panicked_with(InvariantError::NotAdmin);
+
+    let res = change_pause_flags(&invariant, ADMIN, None, pause);
+    res.assert_success();
+    let events = res.emitted_events();
+    let event = events[events.len() - 2]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<PauseFlagsChangedEvent>()
+        .unwrap();
+    assert_eq!(
+        event,
+        PauseFlagsChangedEvent {
+            timestamp: event.timestamp,
+            pool_key: None,
+            pause,
+        }
+    );
+    assert_eq!(get_pause_flags(&invariant, None), Ok(pause));
+    assert_eq!(
+        get_pause_flags(&invariant, Some(pool_key)),
+        Ok(PauseFlags::default())
+    );
+
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        TokenAmount::new(U256::from(10)),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE.into()),
+    )
+    .assert_panicked_with(InvariantError::ActionPaused);
+
+    create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -10,
+        10,
+        Liquidity::from_integer(1),
+        sqrt_price,
+        sqrt_price,
+    )
+    .assert_panicked_with(InvariantError::ActionPaused);
+
+    let other_fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 1).unwrap();
+    add_fee_tier(&invariant, ADMIN, other_fee_tier).assert_success();
+    create_pool(
+        &invariant,
+        REGULAR_USER_1,
+        token_x,
+        token_y,
+        other_fee_tier,
+        sqrt_price,
+        0,
+    )
+    .assert_panicked_with(InvariantError::ActionPaused);
+
+    // exits stay open
+    remove_position(&invariant, REGULAR_USER_1, 0).assert_success();
+    withdraw_single_token(&invariant, REGULAR_USER_2, TOKEN_X_ID, None, None::<&str>).unwrap();
+
+    change_pause_flags(&invariant, ADMIN, None, PauseFlags::default()).assert_success();
+    create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -10,
+        10,
+        Liquidity::from_integer(1),
+        sqrt_price,
+        sqrt_price,
+    )
+    .assert_success();
+}
+
+#[test]
+fn test_pool_pause() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    deposit_token_x(&invariant, &token_x_program, REGULAR_USER_2);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let amount = TokenAmount::new(U256::from(10));
+    let sqrt_price_limit = SqrtPrice::new(MIN_SQRT_PRICE.into());
+    let pause = PauseFlags {
+        swaps: true,
+        withdrawals: true,
+        ..PauseFlags::default()
+    };
+
+    let unknown_pool_key = PoolKey::new(
+        token_x,
+        token_y,
+        FeeTier::new(Percentage::from_scale(1, 2), 1).unwrap(),
+    )
+    .unwrap();
+    change_pause_flags(&invariant, ADMIN, Some(unknown_pool_key), pause)
+        .assert_panicked_with(InvariantError::PoolNotFound);
+
+    change_pause_flags(&invariant, ADMIN, Some(pool_key), pause).assert_success();
+    assert_eq!(get_pause_flags(&invariant, Some(pool_key)), Ok(pause));
+    assert_eq!(get_pause_flags(&invariant, None), Ok(PauseFlags::default()));
+    assert_eq!(
+        get_pool(&invariant, token_x, token_y, fee_tier)
+            .unwrap()
+            .pause,
+        pause
+    );
+
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        amount,
+        true,
+        sqrt_price_limit,
+    )
+    .assert_panicked_with(InvariantError::ActionPaused);
+    // quotes are still available
+    assert!(quote(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        amount,
+        true,
+        sqrt_price_limit
+    )
+    .is_ok());
+
+    remove_position(&invariant, REGULAR_USER_1, 0)
+        .assert_panicked_with(InvariantError::ActionPaused);
+    claim_fee(
+        &invariant,
+        REGULAR_USER_1,
+        0,
+        Some(InvariantError::ActionPaused),
+    );
+
+    // balances are withdrawn under the global flags
+    withdraw_single_token(&invariant, REGULAR_USER_2, TOKEN_X_ID, None, None::<&str>).unwrap();
+
+    change_pause_flags(&invariant, ADMIN, Some(pool_key), PauseFlags::default()).assert_success();
+    remove_position(&invariant, REGULAR_USER_1, 0).assert_success();
+}
@@ -0,0 +1,280 @@
+use crate::test_helpers::gtest::*;
+use contracts::{Role, *};
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::calculate_sqrt_price,
+    sqrt_price::SqrtPrice, token_amount::TokenAmount, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+#[test]
+fn test_deprecate_and_remove_pool() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let sqrt_price = calculate_sqrt_price(0).unwrap();
+
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Ok(PoolStatus::Active)
+    );
+    remove_pool(&invariant, REGULAR_USER_2, pool_key)
+        .assert_panicked_with(InvariantError::PoolNotDeprecated);
+
+    deprecate_pool(&invariant, REGULAR_USER_1, pool_key)
+        .assert_panicked_with(InvariantError::NotAdmin);
+    grant_role(
+        &invariant,
+        ADMIN,
+        REGULAR_USER_1.into(),
+        Role::FeeTierManager,
+    )
+    .assert_success();
+    deprecate_pool(&invariant, REGULAR_USER_1, pool_key)
+        .assert_panicked_with(InvariantError::NotAdmin);
+
+    let res = deprecate_pool(&invariant, ADMIN, pool_key);
+    res.assert_success();
+    let events = res.emitted_events();
+    let event = events[events.len() - 2]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<PoolDeprecatedEvent>()
+        .unwrap();
+    assert_eq!(event.pool_key, pool_key);
+
+    deprecate_pool(&invariant, ADMIN, pool_key)
+        .assert_panicked_with(InvariantError::PoolDeprecated);
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Ok(PoolStatus::Deprecated)
+    );
+    assert!(
+        get_pool(&invariant, token_x, token_y, fee_tier)
+            .unwrap()
+            .deprecated
+    );
+
+    let amount = U256::from(10);
+    mint(&token_x_program, REGULAR_USER_2, amount).assert_success();
+    increase_allowance(&token_x_program, REGULAR_USER_2, INVARIANT_ID, amount).assert_success();
+    deposit_single_token(&invariant, REGULAR_USER_2, TOKEN_X_ID, amount, None::<&str>).unwrap();
+
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        TokenAmount::new(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE.into()),
+    )
+    .assert_panicked_with(InvariantError::PoolDeprecated);
+    create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -10,
+        10,
+        Liquidity::from_integer(1),
+        sqrt_price,
+        sqrt_price,
+    )
+    .assert_panicked_with(InvariantError::PoolDeprecated);
+
+    remove_pool(&invariant, REGULAR_USER_2, pool_key)
+        .assert_panicked_with(InvariantError::PoolNotEmpty);
+
+    // exits stay open
+    claim_fee(&invariant, REGULAR_USER_1, 0, None::<InvariantError>).unwrap();
+    remove_position(&invariant, REGULAR_USER_1, 0).assert_success();
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Ok(PoolStatus::Removable)
+    );
+
+    let res = remove_pool(&invariant, REGULAR_USER_2, pool_key);
+    res.assert_success();
+    let events = res.emitted_events();
+    let event = events[events.len() - 2]
+        .assert_to(EVENT_ADDRESS)
+        .decoded_event::<PoolRemovedEvent>()
+        .unwrap();
+    assert_eq!(event.pool_key, pool_key);
+
+    assert_eq!(
+        get_pool(&invariant, token_x, token_y, fee_tier),
+        Err(InvariantError::PoolNotFound)
+    );
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Err(InvariantError::PoolNotFound)
+    );
+    assert_eq!(get_pool_keys(&invariant, u16::MAX, 0), (vec![], 0));
+    assert_eq!(get_tickmap(&invariant, pool_key), vec![]);
+
+    // uncollected protocol fee goes to the fee receiver
+    assert_eq!(
+        get_user_balances(&invariant, ADMIN),
+        vec![(token_x, TokenAmount::new(U256::from(1)))]
+    );
+
+    remove_pool(&invariant, REGULAR_USER_2, pool_key)
+        .assert_panicked_with(InvariantError::PoolNotFound);
+
+    // the same pool can be created again while its fee tier exists
+    create_pool(
+        &invariant,
+        REGULAR_USER_1,
+        token_x,
+        token_y,
+        fee_tier,
+        sqrt_price,
+        0,
+    )
+    .assert_success();
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Ok(PoolStatus::Active)
+    );
+}
+
+#[test]
+fn test_remove_pool_with_settled_limit_order_and_campaign() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let sqrt_price = calculate_sqrt_price(0).unwrap();
+
+    let amount = U256::from(10u128.pow(10));
+    for user in [REGULAR_USER_1, REGULAR_USER_2] {
+        for (token, token_program) in [
+            (TOKEN_X_ID, &token_x_program),
+            (TOKEN_Y_ID, &token_y_program),
+        ] {
+            mint(token_program, user, amount).assert_success();
+            increase_allowance(token_program, user, INVARIANT_ID, amount).assert_success();
+            deposit_single_token(&invariant, user, token, amount, None::<&str>).unwrap();
+        }
+    }
+
+    create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -100,
+        100,
+        Liquidity::from_integer(1000000),
+        sqrt_price,
+        sqrt_price,
+    )
+    .assert_success();
+    create_limit_order(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        10,
+        Liquidity::from_integer(1000000),
+    )
+    .assert_success();
+
+    let start_timestamp = sys.block_timestamp() + 1_000;
+    let end_timestamp = start_timestamp + 1_000;
+    let res = create_campaign(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        token_x,
+        TokenAmount::from_integer(1000),
+        start_timestamp,
+        end_timestamp,
+    );
+    res.assert_success();
+    let campaign_id = res.last_event().decoded_event::<u64>().unwrap();
+
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        false,
+        TokenAmount::from_integer(2000),
+        true,
+        SqrtPrice::new(MAX_SQRT_PRICE),
+    )
+    .assert_success();
+    assert!(get_limit_order(&invariant, 1).unwrap().settled);
+
+    deprecate_pool(&invariant, ADMIN, pool_key).assert_success();
+    remove_position(&invariant, REGULAR_USER_1, 0).assert_success();
+
+    // the settled limit order holds no ticks, but it still belongs to the pool
+    assert_eq!(get_tickmap(&invariant, pool_key), vec![]);
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Ok(PoolStatus::Deprecated)
+    );
+    remove_pool(&invariant, REGULAR_USER_2, pool_key)
+        .assert_panicked_with(InvariantError::PoolNotEmpty);
+
+    remove_position(&invariant, REGULAR_USER_1, 1).assert_success();
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Ok(PoolStatus::Deprecated)
+    );
+    remove_pool(&invariant, REGULAR_USER_2, pool_key)
+        .assert_panicked_with(InvariantError::PoolNotEmpty);
+
+    while sys.block_timestamp() < end_timestamp {
+        sys.spend_blocks(1);
+    }
+    end_campaign(&invariant, REGULAR_USER_2, campaign_id).assert_success();
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Ok(PoolStatus::Removable)
+    );
+    remove_pool(&invariant, REGULAR_USER_2, pool_key).assert_success();
+}
+
+#[test]
+fn test_deprecate_pool_by_pool_manager() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    init_basic_pool(&invariant, &token_x, &token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    grant_role(&invariant, ADMIN, REGULAR_USER_1.into(), Role::PoolManager).assert_success();
+    deprecate_pool(&invariant, REGULAR_USER_1, pool_key).assert_success();
+    assert_eq!(
+        get_pool_status(&invariant, pool_key),
+        Ok(PoolStatus::Removable)
+    );
+}
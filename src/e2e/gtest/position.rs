@@ -6,6 +6,7 @@ use math::{
     fee_growth::FeeGrowth,
     liquidity::Liquidity,
     percentage::Percentage,
+    seconds_per_liquidity::SecondsPerLiquidity,
     sqrt_price::{calculate_sqrt_price, SqrtPrice},
     token_amount::TokenAmount,
     MIN_SQRT_PRICE,
@@ -30,7 +31,7 @@ fn test_create_position() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -40,7 +41,7 @@ fn test_create_position() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
     increase_allowance(
@@ -107,6 +108,7 @@ fn test_create_position() {
         &position_created_event,
         &PositionCreatedEvent {
             address: REGULAR_USER_1.into(),
+            position_id: 0,
             pool_key,
             liquidity_delta: Liquidity::new(U256::from(10)),
             timestamp: 0,
@@ -130,6 +132,7 @@ fn test_create_position() {
             upper_tick_index: 10,
             fee_growth_inside_x: FeeGrowth::new(0),
             fee_growth_inside_y: FeeGrowth::new(0),
+            seconds_per_liquidity_inside: SecondsPerLiquidity::new(0),
             last_block_number: 0,
             tokens_owed_x: TokenAmount::new(U256::from(0)),
             tokens_owed_y: TokenAmount::new(U256::from(0)),
@@ -155,7 +158,7 @@ fn test_position_same_upper_and_lower_tick() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -165,7 +168,7 @@ fn test_position_same_upper_and_lower_tick() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -222,7 +225,7 @@ fn test_position_same_upper_and_lower_tick() {
     )
     .unwrap();
 
-    get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap_err();
+    get_position(&invariant, 0).unwrap_err();
 }
 
 #[test]
@@ -244,7 +247,7 @@ fn test_position_below_current_tick() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -254,7 +257,7 @@ fn test_position_below_current_tick() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -318,6 +321,7 @@ fn test_position_below_current_tick() {
         &position_created_event,
         &PositionCreatedEvent {
             address: REGULAR_USER_1.into(),
+            position_id: 0,
             pool_key,
             liquidity_delta,
             timestamp: 0,
@@ -341,6 +345,7 @@ fn test_position_below_current_tick() {
             upper_tick_index,
             fee_growth_inside_x: FeeGrowth::new(0),
             fee_growth_inside_y: FeeGrowth::new(0),
+            seconds_per_liquidity_inside: SecondsPerLiquidity::new(0),
             last_block_number: 0 as u64,
             tokens_owed_x: TokenAmount::new(U256::from(0)),
             tokens_owed_y: TokenAmount::new(U256::from(0)),
@@ -360,7 +365,7 @@ fn test_position_below_current_tick() {
 
     let pool_state = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
 
-    let position_state = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let position_state = get_position(&invariant, 0).unwrap();
 
     let lower_tick = get_tick(&invariant, pool_key, lower_tick_index).unwrap();
     let upper_tick = get_tick(&invariant, pool_key, upper_tick_index).unwrap();
@@ -423,7 +428,7 @@ fn test_position_within_current_tick() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -433,7 +438,7 @@ fn test_position_within_current_tick() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -496,7 +501,7 @@ fn test_position_within_current_tick() {
 
     let pool_state = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
 
-    let position_state = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let position_state = get_position(&invariant, 0).unwrap();
 
     let lower_tick = get_tick(&invariant, pool_key, lower_tick_index).unwrap();
     let upper_tick = get_tick(&invariant, pool_key, upper_tick_index).unwrap();
@@ -558,7 +563,7 @@ fn test_position_above_current_tick() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let _res = create_pool(
         &invariant,
@@ -569,7 +574,7 @@ fn test_position_above_current_tick() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -629,6 +634,7 @@ fn test_position_above_current_tick() {
         &position_created_event,
         &PositionCreatedEvent {
             address: REGULAR_USER_1.into(),
+            position_id: 0,
             pool_key,
             liquidity_delta,
             timestamp: 0,
@@ -652,6 +658,7 @@ fn test_position_above_current_tick() {
             upper_tick_index,
             fee_growth_inside_x: FeeGrowth::new(0),
             fee_growth_inside_y: FeeGrowth::new(0),
+            seconds_per_liquidity_inside: SecondsPerLiquidity::new(0),
             last_block_number: 0 as u64,
             tokens_owed_x: TokenAmount::new(U256::from(0)),
             tokens_owed_y: TokenAmount::new(U256::from(0)),
@@ -671,7 +678,7 @@ fn test_position_above_current_tick() {
 
     let pool_state = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
 
-    let position_state = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let position_state = get_position(&invariant, 0).unwrap();
 
     let lower_tick = get_tick(&invariant, pool_key, lower_tick_index).unwrap();
     let upper_tick = get_tick(&invariant, pool_key, upper_tick_index).unwrap();
@@ -732,7 +739,7 @@ fn test_create_position_not_enough_token_x() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -742,7 +749,7 @@ fn test_create_position_not_enough_token_x() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -796,7 +803,7 @@ fn test_create_position_not_enough_token_x() {
 
     get_tick(&invariant, pool_key, lower_tick_index).unwrap_err();
     get_tick(&invariant, pool_key, upper_tick_index).unwrap_err();
-    get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap_err();
+    get_position(&invariant, 0).unwrap_err();
 
     assert_eq!(
         vec![
@@ -852,7 +859,7 @@ fn test_create_position_not_enough_token_y() {
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
     let _res = create_pool(
         &invariant,
         REGULAR_USER_1,
@@ -862,7 +869,7 @@ fn test_create_position_not_enough_token_y() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -940,7 +947,7 @@ fn test_create_position_not_enough_token_y() {
 
     get_tick(&invariant, pool_key, lower_tick_index).unwrap_err();
     get_tick(&invariant, pool_key, upper_tick_index).unwrap_err();
-    get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap_err();
+    get_position(&invariant, 0).unwrap_err();
 
     let user_1_x = balance_of(&token_x_program, REGULAR_USER_1.into());
     let user_1_y = balance_of(&token_y_program, REGULAR_USER_1.into());
@@ -969,7 +976,7 @@ fn test_remove_position() {
     let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
     let init_tick = 0;
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
-    let remove_position_index = 0;
+    let remove_position_id = 0;
 
     let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
 
@@ -984,7 +991,7 @@ fn test_remove_position() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -1096,7 +1103,7 @@ fn test_remove_position() {
     )
     .unwrap();
 
-    let position_state = get_position(&invariant, REGULAR_USER_1.into(), 1).unwrap();
+    let position_state = get_position(&invariant, 1).unwrap();
 
     // Check position
     assert!(position_state.lower_tick_index == incorrect_lower_tick_index);
@@ -1161,7 +1168,7 @@ fn test_remove_position() {
     assert_eq!(get_user_balances(&invariant, REGULAR_USER_1), vec![]);
 
     // Remove position
-    remove_position(&invariant, REGULAR_USER_1, remove_position_index).assert_success();
+    remove_position(&invariant, REGULAR_USER_1, remove_position_id).assert_success();
 
     assert_eq!(
         withdraw_token_pair(
@@ -1212,3 +1219,81 @@ fn test_remove_position() {
     assert!(pool_state.liquidity == liquidity_delta);
     assert!(pool_state.current_tick_index == -10);
 }
+
+#[test]
+fn test_position_id_is_stable() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_cross_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let amount = U256::from(10u128.pow(10));
+
+    mint(&token_x_program, REGULAR_USER_1, amount).assert_success();
+    mint(&token_y_program, REGULAR_USER_1, amount).assert_success();
+    increase_allowance(&token_x_program, REGULAR_USER_1, INVARIANT_ID, amount).assert_success();
+    increase_allowance(&token_y_program, REGULAR_USER_1, INVARIANT_ID, amount).assert_success();
+    deposit_token_pair(
+        &invariant,
+        REGULAR_USER_1,
+        token_x,
+        amount,
+        token_y,
+        amount,
+        None::<&str>,
+    )
+    .unwrap();
+
+    create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -10,
+        10,
+        Liquidity::from_integer(1000),
+        pool.sqrt_price,
+        pool.sqrt_price,
+    )
+    .assert_success();
+
+    assert_eq!(
+        get_position_ids(&invariant, REGULAR_USER_1.into()),
+        vec![0, 1, 2]
+    );
+    let last_position = get_position(&invariant, 2).unwrap();
+
+    remove_position(&invariant, REGULAR_USER_1, 0).assert_success();
+
+    assert_eq!(
+        get_position_ids(&invariant, REGULAR_USER_1.into()),
+        vec![2, 1]
+    );
+    assert_eq!(get_position_id(&invariant, REGULAR_USER_1.into(), 0), Ok(2));
+    assert_eq!(get_position(&invariant, 2).unwrap(), last_position);
+    assert_eq!(
+        get_position(&invariant, 0),
+        Err(InvariantError::PositionNotFound)
+    );
+
+    transfer_position(&invariant, REGULAR_USER_1, 2, REGULAR_USER_2).assert_success();
+
+    assert_eq!(get_position_owner(&invariant, 2), Ok(REGULAR_USER_2.into()));
+    assert_eq!(get_position_ids(&invariant, REGULAR_USER_1.into()), vec![1]);
+    assert_eq!(get_position_ids(&invariant, REGULAR_USER_2.into()), vec![2]);
+    assert_eq!(get_position(&invariant, 2).unwrap(), last_position);
+
+    remove_position(&invariant, REGULAR_USER_1, 2)
+        .assert_panicked_with(InvariantError::PositionNotFound);
+    remove_position(&invariant, REGULAR_USER_2, 2).assert_success();
+}
@@ -0,0 +1,161 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{percentage::Percentage, token_amount::TokenAmount};
+use sails_rs::prelude::*;
+
+fn internal_balance(invariant: &Program, user: u64, token: ActorId) -> TokenAmount {
+    get_user_balances(invariant, user)
+        .into_iter()
+        .find(|(balance_token, _)| *balance_token == token)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_approved_account_acts_for_owner() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    claim_fee(
+        &invariant,
+        REGULAR_USER_2,
+        0,
+        Some(InvariantError::PositionNotFound),
+    );
+    approve_position(&invariant, REGULAR_USER_2, 0, REGULAR_USER_2)
+        .assert_panicked_with(InvariantError::PositionNotFound);
+
+    let res = approve_position(&invariant, REGULAR_USER_1, 0, REGULAR_USER_2);
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<PositionApprovedEvent>()
+        .unwrap();
+    assert_eq!(event.position_id, 0);
+    assert_eq!(event.owner, REGULAR_USER_1.into());
+    assert_eq!(event.spender, REGULAR_USER_2.into());
+    assert_eq!(
+        get_position_approved(&invariant, 0),
+        Some(REGULAR_USER_2.into())
+    );
+
+    let spender_balances = get_user_balances(&invariant, REGULAR_USER_2);
+
+    // proceeds are credited to the owner
+    let (claimed_x, _) = claim_fee(&invariant, REGULAR_USER_2, 0, None::<InvariantError>).unwrap();
+    assert_eq!(claimed_x, TokenAmount::new(U256::from(5)));
+    assert_eq!(
+        get_user_balances(&invariant, REGULAR_USER_1),
+        vec![(token_x, claimed_x)]
+    );
+    assert_eq!(
+        get_user_balances(&invariant, REGULAR_USER_2),
+        spender_balances
+    );
+
+    let res = remove_position(&invariant, REGULAR_USER_2, 0);
+    res.assert_success();
+    let (amount_x, amount_y) = res
+        .emitted_events()
+        .last()
+        .unwrap()
+        .decoded_event::<(TokenAmount, TokenAmount)>()
+        .unwrap();
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_x),
+        claimed_x + amount_x
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_y),
+        amount_y
+    );
+    assert_eq!(
+        get_user_balances(&invariant, REGULAR_USER_2),
+        spender_balances
+    );
+    assert_eq!(
+        get_position(&invariant, 0),
+        Err(InvariantError::PositionNotFound)
+    );
+    assert_eq!(get_position_approved(&invariant, 0), None);
+}
+
+#[test]
+fn test_operator_acts_for_owner() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_cross_position(&invariant, &token_x_program, &token_y_program);
+
+    let res = set_position_operator(&invariant, REGULAR_USER_1, REGULAR_USER_2, true);
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<OperatorSetEvent>()
+        .unwrap();
+    assert_eq!(event.owner, REGULAR_USER_1.into());
+    assert_eq!(event.operator, REGULAR_USER_2.into());
+    assert!(event.approved);
+    assert!(is_position_operator(
+        &invariant,
+        REGULAR_USER_1,
+        REGULAR_USER_2
+    ));
+    assert!(!is_position_operator(
+        &invariant,
+        REGULAR_USER_2,
+        REGULAR_USER_1
+    ));
+
+    // operators can approve positions of the owner
+    approve_position(&invariant, REGULAR_USER_2, 1, ADMIN).assert_success();
+    assert_eq!(get_position_approved(&invariant, 1), Some(ADMIN.into()));
+
+    transfer_position(&invariant, REGULAR_USER_2, 0, ADMIN).assert_success();
+    assert_eq!(get_position_owner(&invariant, 0), Ok(ADMIN.into()));
+    claim_fee(
+        &invariant,
+        REGULAR_USER_2,
+        0,
+        Some(InvariantError::PositionNotFound),
+    );
+
+    let res = set_position_operator(&invariant, REGULAR_USER_1, REGULAR_USER_2, false);
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<OperatorSetEvent>()
+        .unwrap();
+    assert!(!event.approved);
+    assert!(!is_position_operator(
+        &invariant,
+        REGULAR_USER_1,
+        REGULAR_USER_2
+    ));
+    transfer_position(&invariant, REGULAR_USER_2, 1, REGULAR_USER_2)
+        .assert_panicked_with(InvariantError::PositionNotFound);
+
+    transfer_position(&invariant, ADMIN, 1, ADMIN).assert_success();
+    assert_eq!(get_position_ids(&invariant, ADMIN.into()), vec![0, 1]);
+    assert_eq!(
+        get_position_ids(&invariant, REGULAR_USER_1.into()),
+        Vec::<PositionId>::new()
+    );
+}
@@ -18,7 +18,7 @@ fn test_remove_position_from_empty_list() {
 
     let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 3).unwrap();
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_tick = -23028;
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
@@ -32,7 +32,7 @@ fn test_remove_position_from_empty_list() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -53,7 +53,7 @@ fn test_add_multiple_positions() {
 
     let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 3).unwrap();
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_tick = -23028;
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
@@ -67,7 +67,7 @@ fn test_add_multiple_positions() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -158,7 +158,9 @@ fn test_add_multiple_positions() {
     let mut position_list_before = get_all_positions(&invariant, REGULAR_USER_1.into());
     let last_position = position_list_before.last().unwrap();
 
-    remove_position(&invariant, REGULAR_USER_1, position_index_to_remove).assert_success();
+    let position_id_to_remove =
+        get_position_id(&invariant, REGULAR_USER_1.into(), position_index_to_remove).unwrap();
+    remove_position(&invariant, REGULAR_USER_1, position_id_to_remove).assert_success();
 
     let position_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
     let tested_position = position_list_after[position_index_to_remove as usize];
@@ -190,7 +192,9 @@ fn test_add_multiple_positions() {
     let mut position_list_before = get_all_positions(&invariant, REGULAR_USER_1.into());
     let position_to_remove = position_list_before.len() - 1;
 
-    remove_position(&invariant, REGULAR_USER_1, position_to_remove as u32).assert_success();
+    let position_id_to_remove =
+        get_position_id(&invariant, REGULAR_USER_1.into(), position_to_remove as u32).unwrap();
+    remove_position(&invariant, REGULAR_USER_1, position_id_to_remove).assert_success();
 
     let position_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
     assert_eq!(position_list_before.len() - 1, position_list_after.len());
@@ -201,7 +205,8 @@ fn test_add_multiple_positions() {
     let list_len_before = get_all_positions(&invariant, REGULAR_USER_1.into()).len();
 
     for i in (0..list_len_before).rev() {
-        remove_position(&invariant, REGULAR_USER_1, i as u32).assert_success();
+        let position_id = get_position_id(&invariant, REGULAR_USER_1.into(), i as u32).unwrap();
+        remove_position(&invariant, REGULAR_USER_1, position_id).assert_success();
     }
 
     let list_len_after = get_all_positions(&invariant, REGULAR_USER_1.into()).len();
@@ -251,7 +256,7 @@ fn test_only_owner_can_modify_position_list() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -341,7 +346,9 @@ fn test_only_owner_can_modify_position_list() {
     let position_index_to_remove = 2;
     let mut position_list_before = get_all_positions(&invariant, REGULAR_USER_1.into());
     let last_position = position_list_before.last().unwrap();
-    remove_position(&invariant, REGULAR_USER_1, position_index_to_remove).assert_success();
+    let position_id_to_remove =
+        get_position_id(&invariant, REGULAR_USER_1.into(), position_index_to_remove).unwrap();
+    remove_position(&invariant, REGULAR_USER_1, position_id_to_remove).assert_success();
 
     let position_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
     let tested_position = position_list_after[position_index_to_remove as usize];
@@ -372,7 +379,9 @@ fn test_only_owner_can_modify_position_list() {
     let position_list_before = get_all_positions(&invariant, REGULAR_USER_1.into());
     let position_to_remove = position_list_before.len() - 1;
 
-    remove_position(&invariant, REGULAR_USER_2, position_to_remove as u32)
+    let position_id_to_remove =
+        get_position_id(&invariant, REGULAR_USER_1.into(), position_to_remove as u32).unwrap();
+    remove_position(&invariant, REGULAR_USER_2, position_id_to_remove)
         .assert_panicked_with(InvariantError::PositionNotFound);
 }
 
@@ -403,7 +412,7 @@ fn test_transfer_position_ownership() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -493,23 +502,25 @@ fn test_transfer_position_ownership() {
     let owner_list_before = get_all_positions(&invariant, REGULAR_USER_1.into());
     let mut recipient_list_before = get_all_positions(&invariant, REGULAR_USER_2.into());
     let transferred_position =
-        get_position(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap();
+        get_position_by_index(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap();
     let last_position_before = owner_list_before[owner_list_before.len() - 1];
 
     transfer_position(
         &invariant,
         REGULAR_USER_1,
-        transferred_index,
+        get_position_id(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap(),
         REGULAR_USER_2,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
     let recipient_list_after = get_all_positions(&invariant, REGULAR_USER_2.into());
     let mut owner_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
-    let recipient_position = get_position(&invariant, REGULAR_USER_2.into(), 0).unwrap();
-    let owner_first_position_after = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let recipient_position =
+        get_position_by_index(&invariant, REGULAR_USER_2.into(), 0).unwrap();
+    let owner_first_position_after =
+        get_position_by_index(&invariant, REGULAR_USER_1.into(), 0).unwrap();
 
     assert_eq!(recipient_list_after.len(), 1);
     assert_eq!(recipient_list_after.len(), recipient_list_before.len() + 1);
@@ -532,29 +543,29 @@ fn test_transfer_position_ownership() {
     let owner_list_before = get_all_positions(&invariant, REGULAR_USER_1.into());
     let mut recipient_list_before = get_all_positions(&invariant, REGULAR_USER_2.into());
     let transferred_position =
-        get_position(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap();
+        get_position_by_index(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap();
     let last_position_before = owner_list_before[owner_list_before.len() - 1];
 
     transfer_position(
         &invariant,
         REGULAR_USER_1,
-        transferred_index,
+        get_position_id(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap(),
         REGULAR_USER_2,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
     let recipient_list_after = get_all_positions(&invariant, REGULAR_USER_2.into());
     let mut owner_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
-    let recipient_position = get_position(
+    let recipient_position = get_position_by_index(
         &invariant,
         REGULAR_USER_2.into(),
         recipient_list_after.len() as u32 - 1,
     )
     .unwrap();
     let owner_middle_position_after =
-        get_position(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap();
+        get_position_by_index(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap();
 
     assert_eq!(recipient_list_after.len(), 2);
     assert_eq!(recipient_list_after.len(), recipient_list_before.len() + 1);
@@ -577,28 +588,29 @@ fn test_transfer_position_ownership() {
     let transferred_index = owner_list_before.len() - 1;
     let mut recipient_list_before = get_all_positions(&invariant, REGULAR_USER_2.into());
     let transferred_position =
-        get_position(&invariant, REGULAR_USER_1.into(), transferred_index as u32).unwrap();
+        get_position_by_index(&invariant, REGULAR_USER_1.into(), transferred_index as u32).unwrap();
     let first_position_before = owner_list_before[0];
 
     transfer_position(
         &invariant,
         REGULAR_USER_1,
-        transferred_index as u32,
+        get_position_id(&invariant, REGULAR_USER_1.into(), transferred_index as u32).unwrap(),
         REGULAR_USER_2,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
     let recipient_list_after = get_all_positions(&invariant, REGULAR_USER_2.into());
     let mut owner_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
-    let recipient_position = get_position(
+    let recipient_position = get_position_by_index(
         &invariant,
         REGULAR_USER_2.into(),
         recipient_list_after.len() as u32 - 1,
     )
     .unwrap();
-    let owner_first_position_after = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let owner_first_position_after =
+        get_position_by_index(&invariant, REGULAR_USER_1.into(), 0).unwrap();
 
     assert_eq!(recipient_list_after.len(), 3);
     assert_eq!(recipient_list_after.len(), recipient_list_before.len() + 1);
@@ -619,27 +631,27 @@ fn test_transfer_position_ownership() {
     let transferred_index = 0;
     let mut recipient_list_before = get_all_positions(&invariant, REGULAR_USER_2.into());
     let transferred_position =
-        get_position(&invariant, REGULAR_USER_1.into(), transferred_index as u32).unwrap();
+        get_position_by_index(&invariant, REGULAR_USER_1.into(), transferred_index as u32).unwrap();
 
     transfer_position(
         &invariant,
         REGULAR_USER_1,
-        transferred_index,
+        get_position_id(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap(),
         REGULAR_USER_2,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
     let recipient_list_after = get_all_positions(&invariant, REGULAR_USER_2.into());
     let mut owner_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
-    let recipient_position = get_position(
+    let recipient_position = get_position_by_index(
         &invariant,
         REGULAR_USER_2.into(),
         recipient_list_after.len() as u32 - 1,
     )
     .unwrap();
-    get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap_err();
+    get_position_by_index(&invariant, REGULAR_USER_1.into(), 0).unwrap_err();
 
     assert_eq!(recipient_list_after.len(), 4);
     assert_eq!(recipient_list_after.len(), recipient_list_before.len() + 1);
@@ -659,28 +671,29 @@ fn test_transfer_position_ownership() {
     let transferred_index = 0;
     let mut recipient_list_before = get_all_positions(&invariant, REGULAR_USER_1.into());
     let transferred_position =
-        get_position(&invariant, REGULAR_USER_2.into(), transferred_index as u32).unwrap();
+        get_position_by_index(&invariant, REGULAR_USER_2.into(), transferred_index as u32).unwrap();
     let last_position_before = owner_list_before.last().unwrap();
 
     transfer_position(
         &invariant,
         REGULAR_USER_2,
-        transferred_index,
+        get_position_id(&invariant, REGULAR_USER_2.into(), transferred_index).unwrap(),
         REGULAR_USER_1,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_2);
 
     let recipient_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
     let mut owner_list_after = get_all_positions(&invariant, REGULAR_USER_2.into());
-    let recipient_position = get_position(
+    let recipient_position = get_position_by_index(
         &invariant,
         REGULAR_USER_1.into(),
         recipient_list_after.len() as u32 - 1,
     )
     .unwrap();
-    let owner_first_position_after = get_position(&invariant, REGULAR_USER_2.into(), 0).unwrap();
+    let owner_first_position_after =
+        get_position_by_index(&invariant, REGULAR_USER_2.into(), 0).unwrap();
 
     assert_eq!(recipient_list_after.len(), 1);
     assert_eq!(recipient_list_after.len(), recipient_list_before.len() + 1);
@@ -726,7 +739,7 @@ fn test_only_owner_can_transfer_position() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -819,14 +832,14 @@ fn test_only_owner_can_transfer_position() {
     transfer_position(
         &invariant,
         REGULAR_USER_2,
-        transferred_index,
+        get_position_id(&invariant, REGULAR_USER_1.into(), transferred_index).unwrap(),
         REGULAR_USER_1,
     )
     .assert_panicked_with(InvariantError::PositionNotFound);
 
     let recipient_list_after = get_all_positions(&invariant, REGULAR_USER_2.into());
     let owner_list_after = get_all_positions(&invariant, REGULAR_USER_1.into());
-    get_position(&invariant, REGULAR_USER_2.into(), 0).unwrap_err();
+    get_position_by_index(&invariant, REGULAR_USER_2.into(), 0).unwrap_err();
 
     assert_eq!(recipient_list_after.len(), 0);
     assert_eq!(owner_list_after.len(), 4);
@@ -862,7 +875,7 @@ fn test_multiple_positions_on_same_tick() {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -913,7 +926,7 @@ fn test_multiple_positions_on_same_tick() {
     )
     .assert_success();
 
-    let first_position = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let first_position = get_position_by_index(&invariant, REGULAR_USER_1.into(), 0).unwrap();
 
     create_position(
         &invariant,
@@ -927,7 +940,7 @@ fn test_multiple_positions_on_same_tick() {
     )
     .assert_success();
 
-    let second_position = get_position(&invariant, REGULAR_USER_1.into(), 1).unwrap();
+    let second_position = get_position_by_index(&invariant, REGULAR_USER_1.into(), 1).unwrap();
 
     create_position(
         &invariant,
@@ -941,7 +954,7 @@ fn test_multiple_positions_on_same_tick() {
     )
     .assert_success();
 
-    let third_position = get_position(&invariant, REGULAR_USER_1.into(), 2).unwrap();
+    let third_position = get_position_by_index(&invariant, REGULAR_USER_1.into(), 2).unwrap();
 
     assert_eq!(
         first_position.lower_tick_index,
@@ -1025,7 +1038,7 @@ fn test_multiple_positions_on_same_tick() {
     )
     .assert_success();
 
-    let first_position = get_position(&invariant, REGULAR_USER_1.into(), 0).unwrap();
+    let first_position = get_position_by_index(&invariant, REGULAR_USER_1.into(), 0).unwrap();
 
     // Check first position
     assert_eq!(first_position.pool_key, pool_key);
@@ -1050,7 +1063,7 @@ fn test_multiple_positions_on_same_tick() {
     )
     .assert_success();
 
-    let second_position = get_position(&invariant, REGULAR_USER_1.into(), 4).unwrap();
+    let second_position = get_position_by_index(&invariant, REGULAR_USER_1.into(), 4).unwrap();
 
     // Check second position
     assert_eq!(second_position.pool_key, pool_key);
@@ -1075,7 +1088,7 @@ fn test_multiple_positions_on_same_tick() {
     )
     .assert_success();
 
-    let third_position = get_position(&invariant, REGULAR_USER_1.into(), 5).unwrap();
+    let third_position = get_position_by_index(&invariant, REGULAR_USER_1.into(), 5).unwrap();
 
     // Check third position
     assert_eq!(third_position.pool_key, pool_key);
@@ -0,0 +1,140 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use io::PositionMetadata;
+use math::{percentage::Percentage, token_amount::TokenAmount};
+use sails_rs::prelude::*;
+
+#[test]
+fn test_position_nft_metadata() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let position = get_position(&invariant, 0).unwrap();
+
+    assert_eq!(nft_owner_of(&invariant, 0), Ok(REGULAR_USER_1.into()));
+    assert_eq!(
+        nft_owner_of(&invariant, 1),
+        Err(InvariantError::PositionNotFound)
+    );
+    assert_eq!(nft_balance_of(&invariant, REGULAR_USER_1), 1);
+    assert_eq!(nft_balance_of(&invariant, REGULAR_USER_2), 0);
+
+    let metadata = nft_token_metadata(&invariant, 0).unwrap();
+    assert_eq!(
+        metadata,
+        PositionMetadata {
+            token_id: 0,
+            owner: REGULAR_USER_1.into(),
+            pool_key: position.pool_key,
+            lower_tick_index: position.lower_tick_index,
+            upper_tick_index: position.upper_tick_index,
+            liquidity: position.liquidity,
+            uncollected_fee_x: TokenAmount::new(U256::from(5)),
+            uncollected_fee_y: TokenAmount::new(U256::from(0)),
+            current_sqrt_price: pool.sqrt_price,
+        }
+    );
+
+    // metadata reads the fees without claiming them
+    let (claimed_x, claimed_y) =
+        claim_fee(&invariant, REGULAR_USER_1, 0, None::<InvariantError>).unwrap();
+    assert_eq!(claimed_x, metadata.uncollected_fee_x);
+    assert_eq!(claimed_y, metadata.uncollected_fee_y);
+    assert_eq!(
+        nft_token_metadata(&invariant, 0).unwrap().uncollected_fee_x,
+        TokenAmount::new(U256::from(0))
+    );
+}
+
+#[test]
+fn test_position_nft_transfer() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    nft_transfer_from(&invariant, REGULAR_USER_2, REGULAR_USER_1, ADMIN, 0)
+        .assert_panicked_with(InvariantError::PositionNotFound);
+
+    let res = nft_approve(&invariant, REGULAR_USER_1, REGULAR_USER_2, 0);
+    res.assert_success();
+    assert_eq!(
+        res.emitted_events()[0]
+            .assert_to(EVENT_ADDRESS)
+            .decoded_event::<ApprovalEvent>()
+            .unwrap(),
+        ApprovalEvent {
+            owner: REGULAR_USER_1.into(),
+            approved: REGULAR_USER_2.into(),
+            token_id: 0,
+        }
+    );
+    assert_eq!(nft_get_approved(&invariant, 0), Some(REGULAR_USER_2.into()));
+
+    // from has to be the owner
+    nft_transfer_from(&invariant, REGULAR_USER_2, REGULAR_USER_2, ADMIN, 0)
+        .assert_panicked_with(InvariantError::PositionNotFound);
+
+    let res = nft_transfer_from(&invariant, REGULAR_USER_2, REGULAR_USER_1, ADMIN, 0);
+    res.assert_success();
+    let events = res.emitted_events();
+    assert_eq!(events.len(), 3);
+    let (route, _, event) = <(String, String, PositionTransferredEvent)>::decode(
+        &mut events[0].assert_to(EVENT_ADDRESS).payload.as_slice(),
+    )
+    .unwrap();
+    assert_eq!(route, "Service");
+    assert_eq!(
+        event,
+        PositionTransferredEvent {
+            timestamp: event.timestamp,
+            position_id: 0,
+            from: REGULAR_USER_1.into(),
+            to: ADMIN.into(),
+        }
+    );
+    assert_eq!(
+        events[1]
+            .assert_to(EVENT_ADDRESS)
+            .decoded_event::<TransferEvent>()
+            .unwrap(),
+        TransferEvent {
+            from: REGULAR_USER_1.into(),
+            to: ADMIN.into(),
+            token_id: 0,
+        }
+    );
+    assert_eq!(nft_owner_of(&invariant, 0), Ok(ADMIN.into()));
+    assert_eq!(nft_get_approved(&invariant, 0), None);
+    assert_eq!(nft_balance_of(&invariant, REGULAR_USER_1), 0);
+    assert_eq!(nft_balance_of(&invariant, ADMIN), 1);
+
+    nft_set_approval_for_all(&invariant, ADMIN, REGULAR_USER_2, true).assert_success();
+    assert!(nft_is_approved_for_all(&invariant, ADMIN, REGULAR_USER_2));
+
+    nft_transfer_from(&invariant, REGULAR_USER_2, ADMIN, REGULAR_USER_2, 0).assert_success();
+    assert_eq!(nft_owner_of(&invariant, 0), Ok(REGULAR_USER_2.into()));
+    assert_eq!(get_position_ids(&invariant, REGULAR_USER_2.into()), vec![0]);
+    assert!(get_position_ids(&invariant, ADMIN.into()).is_empty());
+}
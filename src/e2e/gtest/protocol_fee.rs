@@ -131,3 +131,62 @@ fn test_withdraw_fee_not_deployer() {
         TokenAmount::new(U256::from(0))
     );
 }
+
+#[test]
+fn test_pool_protocol_fee() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let protocol_fee = Percentage::from_scale(1, 2);
+    let invariant = init_invariant(&sys, protocol_fee);
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    // the pool reports the global protocol fee until it's overridden
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.protocol_fee, Some(protocol_fee));
+
+    change_pool_protocol_fee(&invariant, REGULAR_USER_1, pool_key, Some(Percentage(0)))
+        .assert_panicked_with(InvariantError::NotAdmin);
+    let res = change_pool_protocol_fee(&invariant, ADMIN, pool_key, Some(Percentage(0)));
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<PoolProtocolFeeChangedEvent>()
+        .unwrap();
+    assert_eq!(event.pool_key, pool_key);
+    assert_eq!(event.protocol_fee, Some(Percentage(0)));
+
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.protocol_fee, Some(Percentage(0)));
+    assert_eq!(get_protocol_fee(&invariant), protocol_fee);
+
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    // the whole fee goes to liquidity providers
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.fee_protocol_token_x, TokenAmount::new(U256::from(0)));
+    assert_eq!(pool.fee_protocol_token_y, TokenAmount::new(U256::from(0)));
+
+    let res = change_pool_protocol_fee(&invariant, ADMIN, pool_key, None);
+    res.assert_event_and_reply();
+    let event = res.emitted_events()[0]
+        .decoded_event::<PoolProtocolFeeChangedEvent>()
+        .unwrap();
+    assert_eq!(event.protocol_fee, None);
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.protocol_fee, Some(protocol_fee));
+
+    // the global protocol fee applies to pools without an override
+    let new_protocol_fee = Percentage::from_scale(2, 2);
+    change_protocol_fee(&invariant, ADMIN, new_protocol_fee).assert_success();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    assert_eq!(pool.protocol_fee, Some(new_protocol_fee));
+}
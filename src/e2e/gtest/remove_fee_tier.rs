@@ -14,10 +14,10 @@ fn test_remove_fee_tier() {
 
     let fee_tier = FeeTier::new(Percentage::from_scale(2, 4), 1).unwrap();
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     remove_fee_tier(&invariant, ADMIN, fee_tier)
-        .assert_single_event()
+        .assert_event_and_reply()
         .assert_empty()
         .assert_to(ADMIN);
 
@@ -45,7 +45,7 @@ fn test_remove_fee_tier_not_admin() {
 
     let fee_tier = FeeTier::new(Percentage::from_scale(2, 4), 1).unwrap();
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     remove_fee_tier(&invariant, REGULAR_USER_1, fee_tier)
         .assert_panicked_with(InvariantError::NotAdmin);
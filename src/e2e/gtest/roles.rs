@@ -0,0 +1,76 @@
+use crate::test_helpers::gtest::*;
+use contracts::{Role, *};
+use decimal::*;
+use gtest::*;
+use math::percentage::Percentage;
+use sails_rs::prelude::*;
+
+#[test]
+fn test_admin_transfer() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let new_admin = ActorId::from(REGULAR_USER_1);
+
+    propose_admin(&invariant, REGULAR_USER_1, new_admin)
+        .assert_panicked_with(InvariantError::NotAdmin);
+    accept_admin(&invariant, REGULAR_USER_1).assert_panicked_with(InvariantError::NotPendingAdmin);
+
+    propose_admin(&invariant, ADMIN, new_admin).assert_success();
+    assert_eq!(get_pending_admin(&invariant), Some(new_admin));
+    // admin stays in charge until the transfer is accepted
+    assert_eq!(get_admin(&invariant), ActorId::from(ADMIN));
+
+    accept_admin(&invariant, REGULAR_USER_2).assert_panicked_with(InvariantError::NotPendingAdmin);
+
+    let res = accept_admin(&invariant, REGULAR_USER_1);
+    res.assert_success();
+    assert_eq!(
+        res.last_event().decoded_event::<ActorId>().unwrap(),
+        new_admin
+    );
+    assert_eq!(get_admin(&invariant), new_admin);
+    assert_eq!(get_pending_admin(&invariant), None);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(1, 2), 1).unwrap();
+    add_fee_tier(&invariant, ADMIN, fee_tier).assert_panicked_with(InvariantError::NotAdmin);
+    add_fee_tier(&invariant, REGULAR_USER_1, fee_tier).assert_success();
+}
+
+#[test]
+fn test_grant_and_revoke_role() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let account = ActorId::from(REGULAR_USER_1);
+    let pause = PauseFlags {
+        all: true,
+        ..PauseFlags::default()
+    };
+
+    grant_role(&invariant, REGULAR_USER_1, account, Role::Pauser)
+        .assert_panicked_with(InvariantError::NotAdmin);
+    change_pause_flags(&invariant, REGULAR_USER_1, None, pause)
+        .assert_panicked_with(InvariantError::NotAdmin);
+
+    grant_role(&invariant, ADMIN, account, Role::Pauser).assert_success();
+    grant_role(&invariant, ADMIN, account, Role::Pauser)
+        .assert_panicked_with(InvariantError::RoleAlreadyGranted);
+    assert_eq!(get_roles(&invariant, account), vec![Role::Pauser]);
+
+    change_pause_flags(&invariant, REGULAR_USER_1, None, pause).assert_success();
+    assert_eq!(get_pause_flags(&invariant, None), Ok(pause));
+    // roles only cover their own actions
+    change_protocol_fee(&invariant, REGULAR_USER_1, Percentage::from_scale(2, 2))
+        .assert_panicked_with(InvariantError::NotAdmin);
+
+    revoke_role(&invariant, ADMIN, account, Role::Pauser).assert_success();
+    revoke_role(&invariant, ADMIN, account, Role::Pauser)
+        .assert_panicked_with(InvariantError::RoleNotGranted);
+    assert_eq!(get_roles(&invariant, account), vec![]);
+
+    change_pause_flags(&invariant, REGULAR_USER_1, None, PauseFlags::default())
+        .assert_panicked_with(InvariantError::NotAdmin);
+}
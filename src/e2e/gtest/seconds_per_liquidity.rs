@@ -0,0 +1,102 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, seconds_per_liquidity::SecondsPerLiquidity,
+};
+use sails_rs::prelude::*;
+
+#[test]
+fn test_position_seconds_inside() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+
+    // position above the current tick
+    create_position(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        20,
+        40,
+        Liquidity::from_integer(1000),
+        pool.sqrt_price,
+        pool.sqrt_price,
+    )
+    .assert_success();
+
+    let position = get_position(&invariant, 1).unwrap();
+    assert_eq!(
+        position.seconds_per_liquidity_inside,
+        SecondsPerLiquidity::new(0)
+    );
+
+    sys.spend_blocks(10);
+    let in_range_before = get_position_seconds_inside(&invariant, 0).unwrap();
+    sys.spend_blocks(10);
+    let in_range_after = get_position_seconds_inside(&invariant, 0).unwrap();
+
+    assert!(in_range_before > SecondsPerLiquidity::new(0));
+    assert!(in_range_after > in_range_before);
+
+    // claiming fees doesn't reset the time spent in range
+    claim_fee(&invariant, REGULAR_USER_1, 0, None::<InvariantError>).unwrap();
+    assert!(get_position_seconds_inside(&invariant, 0).unwrap() >= in_range_after);
+    assert_eq!(
+        get_position_seconds_inside(&invariant, 1).unwrap(),
+        SecondsPerLiquidity::new(0)
+    );
+
+    // never crossed and initialized above the current tick
+    let upper_tick = get_tick(&invariant, pool_key, 10).unwrap();
+    assert_eq!(
+        upper_tick.seconds_per_liquidity_outside,
+        SecondsPerLiquidity::new(0)
+    );
+
+    assert_eq!(
+        get_position_seconds_inside(&invariant, 2),
+        Err(InvariantError::PositionNotFound)
+    );
+}
+
+#[test]
+fn test_cross_updates_seconds_per_liquidity() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    sys.spend_blocks(10);
+    init_cross_position(&invariant, &token_x_program, &token_y_program);
+    sys.spend_blocks(10);
+    init_cross_swap(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let pool = get_pool(&invariant, token_x, token_y, fee_tier).unwrap();
+    let middle_tick = get_tick(&invariant, pool_key, -10).unwrap();
+
+    // crossed while the pool had liquidity
+    assert!(middle_tick.seconds_per_liquidity_outside > SecondsPerLiquidity::new(0));
+    assert!(pool.seconds_per_liquidity_global > middle_tick.seconds_per_liquidity_outside);
+}
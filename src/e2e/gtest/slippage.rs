@@ -2,8 +2,10 @@ use crate::test_helpers::gtest::*;
 use contracts::*;
 use decimal::*;
 use gtest::*;
+use io::CalculateSwapResult;
 use math::{
     percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount, MAX_SQRT_PRICE,
+    MIN_SQRT_PRICE,
 };
 use sails_rs::ActorId;
 
@@ -149,3 +151,127 @@ fn test_swap_exact_limit() {
         true,
     );
 }
+
+#[test]
+fn test_swap_with_slippage() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    let amount = U256::from(1000);
+    mint(&token_x_program, REGULAR_USER_2, amount).assert_success();
+    increase_allowance(&token_x_program, REGULAR_USER_2, INVARIANT_ID, amount).assert_success();
+    deposit_single_token(&invariant, REGULAR_USER_2, TOKEN_X_ID, amount, None::<&str>).unwrap();
+
+    let min_sqrt_price = SqrtPrice::new(MIN_SQRT_PRICE.into());
+    let slippage = Percentage::from_scale(1, 2);
+
+    // exact input
+    let swap_amount = TokenAmount::new(U256::from(100));
+    let expected = quote(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        swap_amount,
+        true,
+        min_sqrt_price,
+    )
+    .unwrap();
+
+    swap_with_slippage(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        swap_amount,
+        true,
+        expected.amount_out + TokenAmount::new(U256::from(1)),
+        slippage,
+    )
+    .assert_panicked_with(InvariantError::AmountUnderMinimumAmountOut);
+
+    // the swap moves the price further than no slippage allows
+    swap_with_slippage(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        swap_amount,
+        true,
+        expected.amount_out,
+        Percentage::new(0),
+    )
+    .assert_panicked_with(InvariantError::PriceLimitReached);
+
+    let res = swap_with_slippage(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        swap_amount,
+        true,
+        expected.amount_out,
+        slippage,
+    );
+    res.assert_success();
+    let result = res
+        .last_event()
+        .decoded_event::<CalculateSwapResult>()
+        .unwrap();
+    assert_eq!(result.amount_in, expected.amount_in);
+    assert_eq!(result.amount_out, expected.amount_out);
+
+    // exact output
+    let swap_amount = TokenAmount::new(U256::from(50));
+    let expected = quote(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        swap_amount,
+        false,
+        min_sqrt_price,
+    )
+    .unwrap();
+
+    swap_with_slippage(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        swap_amount,
+        false,
+        expected.amount_in - TokenAmount::new(U256::from(1)),
+        slippage,
+    )
+    .assert_panicked_with(InvariantError::AmountOverMaximumAmountIn);
+
+    let res = swap_with_slippage(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        swap_amount,
+        false,
+        expected.amount_in,
+        slippage,
+    );
+    res.assert_success();
+    let result = res
+        .last_event()
+        .decoded_event::<CalculateSwapResult>()
+        .unwrap();
+    assert_eq!(result.amount_in, expected.amount_in);
+    assert_eq!(result.amount_out, swap_amount);
+}
@@ -0,0 +1,191 @@
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use io::SwapHop;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::calculate_sqrt_price,
+    token_amount::TokenAmount,
+};
+use sails_rs::prelude::*;
+
+fn internal_balance(invariant: &Program, user: u64, token: ActorId) -> TokenAmount {
+    get_user_balances(invariant, user)
+        .into_iter()
+        .find(|(balance_token, _)| *balance_token == token)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default()
+}
+
+fn deposit_tokens(
+    invariant: &Program,
+    token_x_program: &Program,
+    token_y_program: &Program,
+    user: u64,
+) {
+    let amount = U256::from(10u128.pow(10));
+
+    mint(token_x_program, user, amount).assert_success();
+    mint(token_y_program, user, amount).assert_success();
+    increase_allowance(token_x_program, user, INVARIANT_ID, amount).assert_success();
+    increase_allowance(token_y_program, user, INVARIANT_ID, amount).assert_success();
+
+    deposit_single_token(invariant, user, TOKEN_X_ID, amount, None::<&str>).unwrap();
+    deposit_single_token(invariant, user, TOKEN_Y_ID, amount, None::<&str>).unwrap();
+}
+
+// Two pools of the pair with the same fee and liquidity, differing only in tick spacing
+fn init_split_route_pools(sys: &System) -> (Program<'_>, Vec<Vec<SwapHop>>) {
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(sys);
+
+    deposit_tokens(
+        &invariant,
+        &token_x_program,
+        &token_y_program,
+        REGULAR_USER_1,
+    );
+    deposit_tokens(
+        &invariant,
+        &token_x_program,
+        &token_y_program,
+        REGULAR_USER_2,
+    );
+
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+    let mut routes = vec![];
+
+    for tick_spacing in [10, 1] {
+        let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), tick_spacing).unwrap();
+        let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+        add_fee_tier(&invariant, ADMIN, fee_tier).assert_success();
+        create_pool(
+            &invariant,
+            REGULAR_USER_1,
+            token_x,
+            token_y,
+            fee_tier,
+            init_sqrt_price,
+            0,
+        )
+        .assert_success();
+        create_position(
+            &invariant,
+            REGULAR_USER_1,
+            pool_key,
+            -1000,
+            1000,
+            Liquidity::from_integer(1000000),
+            init_sqrt_price,
+            init_sqrt_price,
+        )
+        .assert_success();
+
+        routes.push(vec![SwapHop {
+            pool_key,
+            x_to_y: true,
+        }]);
+    }
+
+    (invariant, routes)
+}
+
+#[test]
+fn test_split_route() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let (invariant, routes) = init_split_route_pools(&sys);
+    let amount_in = TokenAmount::from_integer(10000);
+
+    let proposal = propose_split_route(&invariant, amount_in, routes.clone()).unwrap();
+    // a large trade is worth splitting between equal pools
+    assert_eq!(proposal.len(), 2);
+    assert_eq!(proposal[0].1, routes[0]);
+    assert_eq!(proposal[1].1, routes[1]);
+    assert_eq!(proposal[0].0 + proposal[1].0, Percentage::from_integer(1));
+
+    let expected_amount_out = quote_split_route(&invariant, amount_in, proposal.clone()).unwrap();
+    let single_route_amount_out = quote_route(&invariant, amount_in, routes[0].clone()).unwrap();
+    assert!(expected_amount_out > single_route_amount_out);
+
+    swap_split_route(
+        &invariant,
+        REGULAR_USER_2,
+        amount_in,
+        expected_amount_out + TokenAmount::from_integer(1),
+        Percentage::new(0),
+        proposal.clone(),
+    )
+    .assert_panicked_with(InvariantError::AmountUnderMinimumAmountOut);
+
+    let balance_x_before = internal_balance(&invariant, REGULAR_USER_2, token_x);
+    let balance_y_before = internal_balance(&invariant, REGULAR_USER_2, token_y);
+
+    let res = swap_split_route(
+        &invariant,
+        REGULAR_USER_2,
+        amount_in,
+        expected_amount_out,
+        Percentage::new(0),
+        proposal,
+    );
+    res.assert_success();
+    assert_eq!(
+        res.last_event().decoded_event::<TokenAmount>().unwrap(),
+        expected_amount_out
+    );
+
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_2, token_x),
+        balance_x_before - amount_in
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_2, token_y),
+        balance_y_before + expected_amount_out
+    );
+}
+
+#[test]
+fn test_split_route_invalid_weights() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let (invariant, routes) = init_split_route_pools(&sys);
+    let amount_in = TokenAmount::from_integer(10000);
+
+    let invalid_routes = vec![
+        (Percentage::from_scale(5, 1), routes[0].clone()),
+        (Percentage::from_scale(4, 1), routes[1].clone()),
+    ];
+
+    assert_eq!(
+        quote_split_route(&invariant, amount_in, invalid_routes.clone()),
+        Err(InvariantError::InvalidSplitRoute)
+    );
+    assert_eq!(
+        quote_split_route(&invariant, amount_in, vec![]),
+        Err(InvariantError::InvalidSplitRoute)
+    );
+    assert_eq!(
+        propose_split_route(&invariant, amount_in, vec![]),
+        Err(InvariantError::InvalidSplitRoute)
+    );
+
+    swap_split_route(
+        &invariant,
+        REGULAR_USER_2,
+        amount_in,
+        TokenAmount::new(U256::from(0)),
+        Percentage::new(0),
+        invalid_routes,
+    )
+    .assert_panicked_with(InvariantError::InvalidSplitRoute);
+}
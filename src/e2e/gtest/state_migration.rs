@@ -0,0 +1,271 @@
+use crate::test_helpers::gtest::*;
+use contracts::{Role, *};
+use decimal::*;
+use gtest::*;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+    MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+const MIGRATED_INVARIANT_ID: u64 = 205;
+
+#[test]
+fn test_state_migration() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    increase_observation_cardinality(&invariant, ADMIN, pool_key, 4, 0).assert_success();
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+    sys.spend_blocks(10);
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+
+    let amount = U256::from(10u128.pow(10));
+    mint(&token_x_program, REGULAR_USER_1, amount).assert_success();
+    increase_allowance(&token_x_program, REGULAR_USER_1, INVARIANT_ID, amount).assert_success();
+    deposit_single_token(&invariant, REGULAR_USER_1, TOKEN_X_ID, amount, None::<&str>).unwrap();
+    create_limit_order(
+        &invariant,
+        REGULAR_USER_1,
+        pool_key,
+        10,
+        Liquidity::from_integer(1000),
+    )
+    .assert_success();
+
+    let total_reward = TokenAmount::from_integer(1000);
+    mint(&token_x_program, REGULAR_USER_2, total_reward.get()).assert_success();
+    increase_allowance(
+        &token_x_program,
+        REGULAR_USER_2,
+        INVARIANT_ID,
+        total_reward.get(),
+    )
+    .assert_success();
+    deposit_single_token(
+        &invariant,
+        REGULAR_USER_2,
+        TOKEN_X_ID,
+        total_reward.get(),
+        None::<&str>,
+    )
+    .unwrap();
+    let start_timestamp = sys.block_timestamp() + 1_000;
+    let res = create_campaign(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        token_x,
+        total_reward,
+        start_timestamp,
+        start_timestamp + 1_000_000,
+    );
+    res.assert_success();
+    let campaign_id = res.last_event().decoded_event::<u64>().unwrap();
+    while sys.block_timestamp() < start_timestamp {
+        sys.spend_blocks(1);
+    }
+    stake(&invariant, REGULAR_USER_1, 0, campaign_id).assert_success();
+
+    grant_role(&invariant, ADMIN, REGULAR_USER_2.into(), Role::Pauser).assert_success();
+    propose_admin(&invariant, ADMIN, REGULAR_USER_2.into()).assert_success();
+    approve_position(&invariant, REGULAR_USER_1, 1, REGULAR_USER_2).assert_success();
+    set_position_operator(&invariant, REGULAR_USER_1, REGULAR_USER_2, true).assert_success();
+
+    assert_eq!(
+        export_state(&invariant, ADMIN, None),
+        Err(InvariantError::StateExportNotStarted)
+    );
+    start_state_export(&invariant, REGULAR_USER_1).assert_panicked_with(InvariantError::NotAdmin);
+    start_state_export(&invariant, ADMIN).assert_success();
+    assert_eq!(
+        export_state(&invariant, REGULAR_USER_1, None),
+        Err(InvariantError::NotAdmin)
+    );
+
+    // the source program is frozen, so the chunks form a consistent snapshot
+    swap(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        TokenAmount::from_integer(10),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE),
+    )
+    .assert_panicked_with(InvariantError::StateExportInProgress);
+    deposit_single_token(
+        &invariant,
+        REGULAR_USER_1,
+        TOKEN_X_ID,
+        U256::from(1),
+        Some(InvariantError::StateExportInProgress),
+    );
+    unstake(&invariant, REGULAR_USER_1, 0)
+        .assert_panicked_with(InvariantError::StateExportInProgress);
+
+    let (chunks, checksum) = export_all_state(&invariant, ADMIN);
+    assert_eq!(
+        export_all_state(&invariant, ADMIN),
+        (chunks.clone(), checksum)
+    );
+    assert_eq!(chunks.len(), 15);
+    assert!(matches!(chunks[0], StateChunk::Config { .. }));
+    assert!(matches!(
+        chunks.last().unwrap(),
+        StateChunk::Observations(_)
+    ));
+
+    import_state(&invariant, ADMIN, chunks[0].clone())
+        .assert_panicked_with(InvariantError::StateNotEmpty);
+
+    let migrated = init_invariant_with_id(&sys, MIGRATED_INVARIANT_ID, Percentage::new(0));
+
+    import_state(&migrated, REGULAR_USER_1, chunks[0].clone())
+        .assert_panicked_with(InvariantError::NotAdmin);
+    finalize_state_import(&migrated, ADMIN, checksum)
+        .assert_panicked_with(InvariantError::StateImportNotStarted);
+
+    for chunk in chunks {
+        import_state(&migrated, ADMIN, chunk).assert_success();
+    }
+
+    // the program stays locked until the import is finalized
+    create_pool(
+        &migrated,
+        REGULAR_USER_1,
+        token_x,
+        token_y,
+        fee_tier,
+        SqrtPrice::from_integer(1),
+        0,
+    )
+    .assert_panicked_with(InvariantError::StateImportInProgress);
+    deposit_single_token(
+        &migrated,
+        REGULAR_USER_1,
+        TOKEN_X_ID,
+        U256::from(1),
+        Some(InvariantError::StateImportInProgress),
+    );
+    transfer_position(&migrated, REGULAR_USER_1, 0, REGULAR_USER_2)
+        .assert_panicked_with(InvariantError::StateImportInProgress);
+
+    let mut wrong_checksum = checksum;
+    wrong_checksum[0] ^= 1;
+    finalize_state_import(&migrated, ADMIN, wrong_checksum)
+        .assert_panicked_with(InvariantError::StateChecksumMismatch);
+    finalize_state_import(&migrated, ADMIN, checksum).assert_success();
+
+    create_pool(
+        &migrated,
+        REGULAR_USER_1,
+        token_x,
+        token_y,
+        fee_tier,
+        SqrtPrice::from_integer(1),
+        0,
+    )
+    .assert_panicked_with(InvariantError::PoolAlreadyExist);
+    import_state(&migrated, ADMIN, StateChunk::FeeTiers(vec![fee_tier]))
+        .assert_panicked_with(InvariantError::StateNotEmpty);
+
+    assert_eq!(get_protocol_fee(&migrated), get_protocol_fee(&invariant));
+    assert_eq!(get_fee_tiers(&migrated), get_fee_tiers(&invariant));
+    assert_eq!(
+        get_pool(&migrated, token_x, token_y, fee_tier),
+        get_pool(&invariant, token_x, token_y, fee_tier)
+    );
+    assert_eq!(
+        get_tickmap(&migrated, pool_key),
+        get_tickmap(&invariant, pool_key)
+    );
+    for index in [-20, 10] {
+        assert_eq!(
+            get_tick(&migrated, pool_key, index),
+            get_tick(&invariant, pool_key, index)
+        );
+    }
+    for position_id in [0, 1] {
+        assert_eq!(
+            get_position(&migrated, position_id),
+            get_position(&invariant, position_id)
+        );
+    }
+    assert_eq!(get_pending_admin(&migrated), Some(REGULAR_USER_2.into()));
+    assert_eq!(
+        get_roles(&migrated, REGULAR_USER_2.into()),
+        vec![Role::Pauser]
+    );
+    assert_eq!(
+        get_position_approved(&migrated, 1),
+        Some(REGULAR_USER_2.into())
+    );
+    assert!(is_position_operator(
+        &migrated,
+        REGULAR_USER_1,
+        REGULAR_USER_2
+    ));
+    assert_eq!(
+        get_limit_order(&migrated, 1),
+        get_limit_order(&invariant, 1)
+    );
+    assert_eq!(
+        get_campaign(&migrated, campaign_id),
+        get_campaign(&invariant, campaign_id)
+    );
+    assert_eq!(get_stake(&migrated, 0), get_stake(&invariant, 0));
+    assert_eq!(
+        get_oracle(&migrated, pool_key),
+        get_oracle(&invariant, pool_key)
+    );
+    for user in [REGULAR_USER_1, REGULAR_USER_2] {
+        let mut balances = get_user_balances(&migrated, user);
+        let mut expected_balances = get_user_balances(&invariant, user);
+        balances.sort_by_key(|(token, _)| *token);
+        expected_balances.sort_by_key(|(token, _)| *token);
+        assert_eq!(balances, expected_balances);
+    }
+}
+
+#[test]
+fn test_state_export_freeze() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    finish_state_export(&invariant, ADMIN)
+        .assert_panicked_with(InvariantError::StateExportNotStarted);
+    start_state_export(&invariant, ADMIN).assert_success();
+    finish_state_export(&invariant, REGULAR_USER_1).assert_panicked_with(InvariantError::NotAdmin);
+    transfer_position(&invariant, REGULAR_USER_1, 0, REGULAR_USER_2)
+        .assert_panicked_with(InvariantError::StateExportInProgress);
+    approve_position(&invariant, REGULAR_USER_1, 0, REGULAR_USER_2)
+        .assert_panicked_with(InvariantError::StateExportInProgress);
+
+    finish_state_export(&invariant, ADMIN).assert_success();
+    assert_eq!(
+        export_state(&invariant, ADMIN, None),
+        Err(InvariantError::StateExportNotStarted)
+    );
+    init_basic_swap(&invariant, &token_x_program, &token_y_program);
+}
@@ -1,7 +1,7 @@
 use crate::test_helpers::gtest::*;
-use contracts::{FeeTier, PoolKey};
+use contracts::{FeeTier, InvariantError, PoolKey};
 use decimal::*;
-use gtest::System;
+use gtest::{Program, System};
 use io::SwapHop;
 use math::types::{
     liquidity::Liquidity, percentage::Percentage, sqrt_price::calculate_sqrt_price,
@@ -231,3 +231,157 @@ fn swap_route_test() {
     assert_eq!(user_2_amount_y_after - user_2_amount_y_before, 4.into());
     assert_eq!(user_2_amount_z_after - user_2_amount_z_before, 0.into());
 }
+
+fn internal_balance(invariant: &Program, user: u64, token: ActorId) -> TokenAmount {
+    get_user_balances(invariant, user)
+        .into_iter()
+        .find(|(balance_token, _)| *balance_token == token)
+        .map(|(_, amount)| amount)
+        .unwrap_or_default()
+}
+
+fn init_route_pools(sys: &System) -> (Program<'_>, Vec<SwapHop>) {
+    let (invariant, token_x_program, token_y_program, token_z_program) =
+        init_invariant_and_3_tokens(sys);
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+    let token_z = ActorId::from(TOKEN_Z_ID);
+
+    for token_program in [&token_x_program, &token_y_program, &token_z_program] {
+        mint(token_program, REGULAR_USER_2, u64::MAX.into()).assert_success();
+        increase_allowance(token_program, REGULAR_USER_2, INVARIANT_ID, u64::MAX.into())
+            .assert_success();
+    }
+    for token in [token_x, token_y, token_z] {
+        deposit_single_token(
+            &invariant,
+            REGULAR_USER_2,
+            token,
+            u64::MAX.into(),
+            None::<&str>,
+        );
+    }
+
+    let amount = 1000;
+    mint(&token_x_program, REGULAR_USER_1, amount.into()).assert_success();
+    increase_allowance(
+        &token_x_program,
+        REGULAR_USER_1,
+        INVARIANT_ID,
+        amount.into(),
+    )
+    .assert_success();
+    deposit_single_token(
+        &invariant,
+        REGULAR_USER_1,
+        token_x,
+        amount.into(),
+        None::<&str>,
+    );
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 1).unwrap();
+    add_fee_tier(&invariant, ADMIN, fee_tier).assert_success();
+
+    let pool_key_1 = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+    let pool_key_2 = PoolKey::new(token_y, token_z, fee_tier).unwrap();
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+    let liquidity_delta = Liquidity::new((2u128.pow(63) - 1).into());
+
+    for pool_key in [pool_key_1, pool_key_2] {
+        create_pool(
+            &invariant,
+            REGULAR_USER_2,
+            pool_key.token_x,
+            pool_key.token_y,
+            fee_tier,
+            init_sqrt_price,
+            0,
+        )
+        .assert_success();
+        create_position(
+            &invariant,
+            REGULAR_USER_2,
+            pool_key,
+            -1,
+            1,
+            liquidity_delta,
+            init_sqrt_price,
+            init_sqrt_price,
+        )
+        .assert_success();
+    }
+
+    let swaps = vec![
+        SwapHop {
+            pool_key: pool_key_1,
+            x_to_y: true,
+        },
+        SwapHop {
+            pool_key: pool_key_2,
+            x_to_y: true,
+        },
+    ];
+
+    (invariant, swaps)
+}
+
+#[test]
+fn swap_route_exact_out_test() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let (invariant, swaps) = init_route_pools(&sys);
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+    let token_z = ActorId::from(TOKEN_Z_ID);
+
+    let amount_out = TokenAmount(900.into());
+    let amount_in = quote_route_exact_out(&invariant, amount_out, swaps.clone()).unwrap();
+    assert!(amount_in > amount_out && amount_in < TokenAmount(1000.into()));
+    assert!(quote_route(&invariant, amount_in, swaps.clone()).unwrap() >= amount_out);
+
+    swap_route_exact_out(
+        &invariant,
+        REGULAR_USER_1,
+        amount_out,
+        amount_in - TokenAmount(1.into()),
+        swaps.clone(),
+    )
+    .assert_panicked_with(InvariantError::AmountOverMaximumAmountIn);
+
+    let res = swap_route_exact_out(
+        &invariant,
+        REGULAR_USER_1,
+        amount_out,
+        amount_in,
+        swaps.clone(),
+    );
+    res.assert_success();
+    assert_eq!(
+        res.last_event().decoded_event::<TokenAmount>().unwrap(),
+        amount_in
+    );
+
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_x),
+        TokenAmount(1000.into()) - amount_in
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_y),
+        TokenAmount(0.into())
+    );
+    assert_eq!(
+        internal_balance(&invariant, REGULAR_USER_1, token_z),
+        amount_out
+    );
+
+    // the route can't be paid with the remaining balance
+    swap_route_exact_out(
+        &invariant,
+        REGULAR_USER_1,
+        amount_out,
+        TokenAmount(1000.into()),
+        swaps,
+    )
+    .assert_panicked_with(InvariantError::FailedToChangeTokenBalance);
+}
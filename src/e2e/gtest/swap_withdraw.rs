@@ -0,0 +1,214 @@
+use crate::invariant_service::VARA_ADDRESS;
+use crate::test_helpers::gtest::*;
+use contracts::*;
+use decimal::*;
+use gtest::*;
+use io::CalculateSwapResult;
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::calculate_sqrt_price,
+    sqrt_price::SqrtPrice, token_amount::TokenAmount, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+};
+use sails_rs::prelude::*;
+
+fn init_vara_pool(sys: &System, invariant: &Program, token_y_program: &Program) -> PoolKey {
+    let token_y = ActorId::from(TOKEN_Y_ID);
+    let amount = 10u128.pow(13);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(VARA_ADDRESS, token_y, fee_tier).unwrap();
+    let init_sqrt_price = calculate_sqrt_price(0).unwrap();
+
+    add_fee_tier(invariant, ADMIN, fee_tier).assert_success();
+    create_pool(
+        invariant,
+        REGULAR_USER_1,
+        VARA_ADDRESS,
+        token_y,
+        fee_tier,
+        init_sqrt_price,
+        0,
+    )
+    .assert_success();
+
+    sys.mint_to(REGULAR_USER_1, amount);
+    deposit_vara(invariant, REGULAR_USER_1, amount, None::<&str>).unwrap();
+    mint(token_y_program, REGULAR_USER_1, amount.into()).assert_success();
+    increase_allowance(token_y_program, REGULAR_USER_1, INVARIANT_ID, amount.into())
+        .assert_success();
+    deposit_single_token(
+        invariant,
+        REGULAR_USER_1,
+        TOKEN_Y_ID,
+        amount.into(),
+        None::<&str>,
+    )
+    .unwrap();
+
+    create_position(
+        invariant,
+        REGULAR_USER_1,
+        pool_key,
+        -1000,
+        1000,
+        Liquidity::from_integer(10u128.pow(14)),
+        init_sqrt_price,
+        init_sqrt_price,
+    )
+    .assert_success();
+
+    pool_key
+}
+
+#[test]
+fn test_swap_withdraw_output() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    let amount = U256::from(1000);
+    mint(&token_x_program, REGULAR_USER_2, amount).assert_success();
+    increase_allowance(&token_x_program, REGULAR_USER_2, INVARIANT_ID, amount).assert_success();
+    deposit_single_token(&invariant, REGULAR_USER_2, TOKEN_X_ID, amount, None::<&str>).unwrap();
+
+    let res = swap_and_withdraw(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        true,
+        TokenAmount(amount),
+        true,
+        SqrtPrice::new(MIN_SQRT_PRICE.into()),
+    );
+    res.assert_success();
+    let result = res
+        .last_event()
+        .decoded_event::<CalculateSwapResult>()
+        .unwrap();
+
+    // nothing is left in the internal balance
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_2), vec![]);
+    assert_eq!(
+        balance_of(&token_y_program, REGULAR_USER_2),
+        result.amount_out.get()
+    );
+}
+
+#[test]
+fn test_swap_vara_in() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (_token_x_program, token_y_program) = init_tokens(&sys);
+    let pool_key = init_vara_pool(&sys, &invariant, &token_y_program);
+
+    let vara_amount = 10u128.pow(12);
+    sys.mint_to(REGULAR_USER_2, vara_amount);
+
+    let res = swap_vara_in(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        SqrtPrice::new(MIN_SQRT_PRICE.into()),
+        true,
+        vara_amount,
+    );
+    res.assert_success();
+    let result = res
+        .last_event()
+        .decoded_event::<CalculateSwapResult>()
+        .unwrap();
+
+    assert_eq!(result.amount_in, TokenAmount::new(vara_amount.into()));
+    assert_eq!(sys.balance_of(REGULAR_USER_2), 0);
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_2), vec![]);
+    assert_eq!(
+        balance_of(&token_y_program, REGULAR_USER_2),
+        result.amount_out.get()
+    );
+
+    // swap back with vara sent out directly
+    let amount_y = result.amount_out;
+    increase_allowance(
+        &token_y_program,
+        REGULAR_USER_2,
+        INVARIANT_ID,
+        amount_y.get(),
+    )
+    .assert_success();
+    deposit_single_token(
+        &invariant,
+        REGULAR_USER_2,
+        TOKEN_Y_ID,
+        amount_y.get(),
+        None::<&str>,
+    )
+    .unwrap();
+
+    let res = swap_and_withdraw(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        false,
+        amount_y,
+        true,
+        SqrtPrice::new(MAX_SQRT_PRICE.into()),
+    );
+    res.assert_success();
+    let result = res
+        .last_event()
+        .decoded_event::<CalculateSwapResult>()
+        .unwrap();
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_2), vec![]);
+
+    let log = Log::builder().payload_bytes(("Service", "WithdrawVara", result.amount_out).encode());
+    sys.get_mailbox(REGULAR_USER_2).claim_value(log).unwrap();
+    assert_eq!(
+        sys.balance_of(REGULAR_USER_2),
+        result.amount_out.get().as_u128()
+    );
+}
+
+#[test]
+fn test_swap_vara_in_without_vara_pool() {
+    let sys = System::new();
+    sys.init_logger();
+
+    let token_x = ActorId::from(TOKEN_X_ID);
+    let token_y = ActorId::from(TOKEN_Y_ID);
+
+    let invariant = init_invariant(&sys, Percentage::from_scale(1, 2));
+    let (token_x_program, token_y_program) = init_tokens(&sys);
+
+    init_basic_pool(&invariant, &token_x, &token_y);
+    init_basic_position(&invariant, &token_x_program, &token_y_program);
+
+    let fee_tier = FeeTier::new(Percentage::from_scale(6, 3), 10).unwrap();
+    let pool_key = PoolKey::new(token_x, token_y, fee_tier).unwrap();
+
+    let vara_amount = 10u128.pow(12);
+    sys.mint_to(REGULAR_USER_2, vara_amount);
+
+    swap_vara_in(
+        &invariant,
+        REGULAR_USER_2,
+        pool_key,
+        SqrtPrice::new(MIN_SQRT_PRICE.into()),
+        false,
+        vara_amount,
+    )
+    .assert_panicked_with(InvariantError::InvalidVaraDepositAttempt);
+
+    assert_eq!(get_user_balances(&invariant, REGULAR_USER_2), vec![]);
+}
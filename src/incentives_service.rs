@@ -0,0 +1,264 @@
+extern crate alloc;
+use crate::invariant_service::panic;
+use crate::invariant_storage::{Invariant, InvariantStorage};
+use contracts::{Campaign, InvariantError, PoolKey, PositionId, Stake};
+use gstd::exec;
+use io::*;
+use math::token_amount::TokenAmount;
+use sails_rs::{
+    gstd::{service, ExecContext},
+    prelude::*,
+};
+
+pub struct IncentivesService<TExecContext> {
+    exec_context: TExecContext,
+}
+
+#[service(events=IncentivesEvent)]
+impl<TExecContext> IncentivesService<TExecContext>
+where
+    TExecContext: ExecContext,
+{
+    pub fn new(exec_context: TExecContext) -> Self {
+        Self { exec_context }
+    }
+
+    pub fn create_campaign(
+        &mut self,
+        pool_key: PoolKey,
+        reward_token: ActorId,
+        total_reward: TokenAmount,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> u64 {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let caller = self.exec_context.actor_id();
+            let current_timestamp = exec::block_timestamp();
+
+            invariant.check_flash_loan(&caller)?;
+            invariant.pools.get(&pool_key)?;
+
+            let campaign = Campaign::create(
+                caller,
+                pool_key,
+                reward_token,
+                total_reward,
+                start_timestamp,
+                end_timestamp,
+                current_timestamp,
+            )?;
+
+            invariant.decrease_token_balance(&reward_token, &caller, Some(total_reward))?;
+
+            let campaign_id = invariant.campaigns.add(&campaign);
+
+            self.notify_on(IncentivesEvent::CampaignCreatedEvent {
+                timestamp: current_timestamp,
+                campaign_id,
+                creator: caller,
+                pool_key,
+                reward_token,
+                total_reward,
+                start_timestamp,
+                end_timestamp,
+            })
+            .expect("Failed to notify on campaign created event");
+
+            Ok(campaign_id)
+        })
+    }
+
+    pub fn end_campaign(&mut self, campaign_id: u64) -> TokenAmount {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let current_timestamp = exec::block_timestamp();
+            let campaign = invariant.campaigns.get(campaign_id)?;
+
+            if current_timestamp < campaign.end_timestamp || campaign.number_of_stakes > 0 {
+                return Err(InvariantError::CampaignStillActive);
+            }
+
+            invariant.campaigns.remove(campaign_id)?;
+
+            // leftover reward goes back to the creator
+            let refund = campaign.total_reward_unclaimed;
+            invariant.increase_token_balance(&campaign.reward_token, &campaign.creator, refund)?;
+
+            self.notify_on(IncentivesEvent::CampaignEndedEvent {
+                timestamp: current_timestamp,
+                campaign_id,
+                refund,
+            })
+            .expect("Failed to notify on campaign ended event");
+
+            Ok(refund)
+        })
+    }
+
+    pub fn get_campaign(&self, campaign_id: u64) -> Result<Campaign, InvariantError> {
+        InvariantStorage::as_ref().campaigns.get(campaign_id)
+    }
+
+    pub fn stake(&mut self, position_id: PositionId, campaign_id: u64) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let caller = self.exec_context.actor_id();
+            let current_timestamp = exec::block_timestamp();
+
+            let position = *invariant.positions.get_owned(&caller, position_id)?;
+            let mut campaign = invariant.campaigns.get(campaign_id)?;
+
+            if campaign.pool_key != position.pool_key {
+                return Err(InvariantError::InvalidCampaignPool);
+            }
+
+            if !campaign.is_active(current_timestamp) {
+                return Err(InvariantError::CampaignNotActive);
+            }
+
+            if position.liquidity.is_zero() {
+                return Err(InvariantError::ZeroLiquidity);
+            }
+
+            // converted liquidity would stop earning in the middle of the stake
+            if invariant.limit_orders.contains(position_id) {
+                return Err(InvariantError::InvalidLimitOrder);
+            }
+
+            let stake = Stake {
+                campaign_id,
+                liquidity: position.liquidity,
+                seconds_per_liquidity_inside_initial: invariant
+                    .seconds_per_liquidity_inside(&position)?,
+            };
+            invariant.stakes.add(position_id, &stake)?;
+
+            campaign.number_of_stakes += 1;
+            invariant.campaigns.update(campaign_id, &campaign)?;
+
+            self.notify_on(IncentivesEvent::PositionStakedEvent {
+                timestamp: current_timestamp,
+                address: caller,
+                position_id,
+                campaign_id,
+                liquidity: position.liquidity,
+            })
+            .expect("Failed to notify on position staked event");
+
+            Ok(())
+        })
+    }
+
+    pub fn unstake(&mut self, position_id: PositionId) -> TokenAmount {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let caller = self.exec_context.actor_id();
+            let owner = invariant.positions.get_owner(position_id)?;
+
+            // anyone can unstake once the campaign is over, so a staker can't hold back the
+            // refund of the creator, the reward still goes to the owner
+            if caller != owner {
+                let stake = invariant.stakes.get(position_id)?;
+                let campaign = invariant.campaigns.get(stake.campaign_id)?;
+
+                if exec::block_timestamp() < campaign.end_timestamp {
+                    return Err(InvariantError::PositionNotFound);
+                }
+            }
+
+            let reward = self.claim_stake_reward(invariant, &owner, position_id)?;
+
+            let stake = invariant.stakes.remove(position_id)?;
+            let mut campaign = invariant.campaigns.get(stake.campaign_id)?;
+            campaign.number_of_stakes -= 1;
+            invariant.campaigns.update(stake.campaign_id, &campaign)?;
+
+            self.notify_on(IncentivesEvent::PositionUnstakedEvent {
+                timestamp: exec::block_timestamp(),
+                address: owner,
+                position_id,
+                campaign_id: stake.campaign_id,
+            })
+            .expect("Failed to notify on position unstaked event");
+
+            Ok(reward)
+        })
+    }
+
+    pub fn claim_reward(&mut self, position_id: PositionId) -> TokenAmount {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let caller = self.exec_context.actor_id();
+
+            self.claim_stake_reward(invariant, &caller, position_id)
+        })
+    }
+
+    pub fn get_stake(&self, position_id: PositionId) -> Result<Stake, InvariantError> {
+        InvariantStorage::as_ref().stakes.get(position_id).cloned()
+    }
+
+    pub fn get_pending_reward(
+        &self,
+        position_id: PositionId,
+    ) -> Result<TokenAmount, InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+
+        let stake = invariant.stakes.get(position_id)?;
+        let campaign = invariant.campaigns.get(stake.campaign_id)?;
+        let position = invariant.positions.get(position_id)?;
+
+        let (reward, _) = campaign.calculate_reward(
+            stake,
+            invariant.seconds_per_liquidity_inside(position)?,
+            exec::block_timestamp(),
+        );
+
+        Ok(reward)
+    }
+
+    fn claim_stake_reward(
+        &mut self,
+        invariant: &mut Invariant,
+        owner: &ActorId,
+        position_id: PositionId,
+    ) -> Result<TokenAmount, InvariantError> {
+        let current_timestamp = exec::block_timestamp();
+
+        let position = *invariant.positions.get_owned(owner, position_id)?;
+        let mut stake = *invariant.stakes.get(position_id)?;
+        let mut campaign = invariant.campaigns.get(stake.campaign_id)?;
+
+        let reward = campaign.settle(
+            &mut stake,
+            invariant.seconds_per_liquidity_inside(&position)?,
+            current_timestamp,
+        );
+
+        invariant.stakes.update(position_id, &stake)?;
+        invariant.campaigns.update(stake.campaign_id, &campaign)?;
+        invariant.increase_token_balance(&campaign.reward_token, owner, reward)?;
+
+        self.notify_on(IncentivesEvent::RewardClaimedEvent {
+            timestamp: current_timestamp,
+            address: *owner,
+            campaign_id: stake.campaign_id,
+            reward_token: campaign.reward_token,
+            amount: reward,
+        })
+        .expect("Failed to notify on reward claimed event");
+
+        Ok(reward)
+    }
+}
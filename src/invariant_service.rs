@@ -3,16 +3,19 @@ use crate::invariant_storage::Invariant;
 use crate::invariant_storage::InvariantStorage;
 use contracts::PositionTick;
 use contracts::{
-    get_max_chunk, AwaitingTransfer, FeeTier, InvariantError, LiquidityTick, Pool, PoolKey,
-    Position, Tick, TransferType, LIQUIDITY_TICK_LIMIT, POSITION_TICK_LIMIT,
+    chain_state_checksum, AwaitingTransfer, DynamicFee, FeeTier, FlashLoan, InvariantError,
+    LimitOrder, LiquidityTick, Oracle, PausableAction, PauseFlags, Pool, PoolKey, PoolStatus,
+    Position, PositionId, Role, StateChecksum, StateChunk, StateCursor, Tick, TransferType,
+    LIQUIDITY_TICK_LIMIT, POSITION_TICK_LIMIT,
 };
 use decimal::*;
 use futures;
 use io::*;
-use math::calculate_min_amount_out;
+use math::{calculate_min_amount_out, calculate_sqrt_price_after_slippage};
 use math::{
-    check_tick, liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice,
-    token_amount::TokenAmount, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+    check_tick, liquidity::Liquidity, percentage::Percentage,
+    seconds_per_liquidity::SecondsPerLiquidity, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+    MAX_SQRT_PRICE, MIN_SQRT_PRICE,
 };
 // import for timestamp and porgram_id
 use gstd::exec;
@@ -58,6 +61,11 @@ pub const BALANCE_CHANGE_COST: u64 = 100_000 * 2;
 pub const TRANSFER_COST: u64 =
     TRANSFER_GAS_LIMIT + TRANSFER_REPLY_HANDLING_COST + BALANCE_CHANGE_COST;
 pub const VARA_ADDRESS: ActorId = ActorId::zero();
+pub const FLASH_LOAN_REPLY_HANDLING_COST: u64 = 10_600_000_000 * 2;
+// Number of equal parts the amount is divided into when proposing a split route
+pub const SPLIT_ROUTE_PARTS: u128 = 10;
+// Value required from non-admin callers for every additional oracle observation slot
+pub const ORACLE_SLOT_PRICE: u128 = 10_000_000_000;
 
 pub enum RouteType<'a, TExecContext> {
     Swap(&'a mut InvariantService<TExecContext>),
@@ -83,16 +91,95 @@ where
         Self { exec_context }
     }
 
-    pub fn change_protocol_fee(&mut self, protocol_fee: Percentage) -> Percentage {
+    // The new admin has to accept the role, until then the current admin stays in charge
+    pub fn propose_admin(&mut self, admin: ActorId) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            if !self.is_caller_admin(&invariant) {
+                return Err(InvariantError::NotAdmin);
+            }
+
+            invariant.pending_admin = Some(admin);
+
+            Ok(())
+        })
+    }
+
+    pub fn accept_admin(&mut self) -> ActorId {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let caller = self.exec_context.actor_id();
+
+            if invariant.pending_admin != Some(caller) {
+                return Err(InvariantError::NotPendingAdmin);
+            }
+
+            invariant.config.admin = caller;
+            invariant.pending_admin = None;
+
+            Ok(caller)
+        })
+    }
+
+    pub fn get_admin(&self) -> ActorId {
+        InvariantStorage::as_ref().config.admin
+    }
+
+    pub fn get_pending_admin(&self) -> Option<ActorId> {
+        InvariantStorage::as_ref().pending_admin
+    }
+
+    pub fn grant_role(&mut self, account: ActorId, role: Role) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            if !self.is_caller_admin(&invariant) {
+                return Err(InvariantError::NotAdmin);
+            }
+
+            invariant.roles.grant(&account, role)
+        })
+    }
+
+    pub fn revoke_role(&mut self, account: ActorId, role: Role) {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
 
             if !self.is_caller_admin(&invariant) {
                 return Err(InvariantError::NotAdmin);
             }
 
+            invariant.roles.revoke(&account, role)
+        })
+    }
+
+    pub fn get_roles(&self, account: ActorId) -> Vec<Role> {
+        InvariantStorage::as_ref().roles.get_all(&account)
+    }
+
+    pub fn change_protocol_fee(&mut self, protocol_fee: Percentage) -> Percentage {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            if !self.is_caller_authorized(&invariant, Role::ProtocolFeeManager) {
+                return Err(InvariantError::NotAdmin);
+            }
+
             invariant.config.protocol_fee = protocol_fee;
 
+            self.notify_on(InvariantEvent::ProtocolFeeChangedEvent {
+                timestamp: exec::block_timestamp(),
+                protocol_fee,
+            })
+            .expect("Failed to notify on protocol fee changed event");
+
             Ok(invariant.config.protocol_fee)
         })
     }
@@ -101,9 +188,53 @@ where
         InvariantStorage::as_ref().config.protocol_fee
     }
 
+    // Without a pool key the global flags are changed
+    pub fn change_pause_flags(
+        &mut self,
+        pool_key: Option<PoolKey>,
+        pause: PauseFlags,
+    ) -> PauseFlags {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            if !self.is_caller_authorized(&invariant, Role::Pauser) {
+                return Err(InvariantError::NotAdmin);
+            }
+
+            match pool_key {
+                Some(pool_key) => {
+                    let mut pool = invariant.pools.get(&pool_key)?;
+                    pool.pause = pause;
+                    invariant.pools.update(&pool_key, &pool)?;
+                }
+                None => invariant.config.pause = pause,
+            }
+
+            self.notify_on(InvariantEvent::PauseFlagsChangedEvent {
+                timestamp: exec::block_timestamp(),
+                pool_key,
+                pause,
+            })
+            .expect("Failed to notify on pause flags changed event");
+
+            Ok(pause)
+        })
+    }
+
+    pub fn get_pause_flags(&self, pool_key: Option<PoolKey>) -> Result<PauseFlags, InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+
+        match pool_key {
+            Some(pool_key) => Ok(invariant.pools.get(&pool_key)?.pause),
+            None => Ok(invariant.config.pause),
+        }
+    }
+
     pub fn add_fee_tier(&mut self, fee_tier: FeeTier) -> FeeTier {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
 
             if fee_tier.tick_spacing == 0 || fee_tier.tick_spacing > 100 {
                 return Err(InvariantError::InvalidTickSpacing);
@@ -113,11 +244,18 @@ where
                 return Err(InvariantError::InvalidFee);
             }
 
-            if !self.is_caller_admin(&invariant) {
+            if !self.is_caller_authorized(&invariant, Role::FeeTierManager) {
                 return Err(InvariantError::NotAdmin);
             }
 
             invariant.fee_tiers.add(&fee_tier)?;
+
+            self.notify_on(InvariantEvent::FeeTierAddedEvent {
+                timestamp: exec::block_timestamp(),
+                fee_tier,
+            })
+            .expect("Failed to notify on fee tier added event");
+
             Ok(fee_tier)
         })
     }
@@ -129,12 +267,20 @@ where
     pub fn remove_fee_tier(&mut self, fee_tier: FeeTier) -> FeeTier {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
 
-            if !self.is_caller_admin(&invariant) {
+            if !self.is_caller_authorized(&invariant, Role::FeeTierManager) {
                 return Err(InvariantError::NotAdmin);
             }
 
             invariant.fee_tiers.remove(&fee_tier)?;
+
+            self.notify_on(InvariantEvent::FeeTierRemovedEvent {
+                timestamp: exec::block_timestamp(),
+                fee_tier,
+            })
+            .expect("Failed to notify on fee tier removed event");
+
             Ok(fee_tier)
         })
     }
@@ -155,6 +301,8 @@ where
             let invariant = InvariantStorage::as_mut();
             let current_timestamp = exec::block_timestamp();
 
+            invariant.check_pause(None, PausableAction::NewLiquidity)?;
+
             if !invariant.fee_tiers.contains(&fee_tier) {
                 return Err(InvariantError::FeeTierNotFound);
             };
@@ -177,6 +325,18 @@ where
             )?;
             invariant.pools.add(&pool_key, &pool)?;
             invariant.pool_keys.add(&pool_key)?;
+            invariant
+                .oracles
+                .add(&pool_key, Oracle::new(current_timestamp))?;
+
+            self.notify_on(InvariantEvent::PoolCreatedEvent {
+                timestamp: current_timestamp,
+                address: self.exec_context.actor_id(),
+                pool_key,
+                init_sqrt_price,
+                init_tick,
+            })
+            .expect("Failed to notify on pool created event");
 
             Ok(())
         })
@@ -191,7 +351,10 @@ where
         let invariant = InvariantStorage::as_ref();
 
         let pool_key = PoolKey::new(token_x, token_y, fee_tier)?;
-        invariant.pools.get(&pool_key)
+        let mut pool = invariant.pools.get(&pool_key)?;
+        pool.protocol_fee = Some(pool.effective_protocol_fee(invariant.config.protocol_fee));
+
+        Ok(pool)
     }
 
     pub fn get_pool_keys(&self, size: u16, offset: u16) -> (Vec<PoolKey>, u16) {
@@ -204,8 +367,9 @@ where
     pub fn change_fee_receiver(&mut self, pool_key: PoolKey, fee_receiver: ActorId) {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
 
-            if !self.is_caller_admin(&invariant) {
+            if !self.is_caller_authorized(&invariant, Role::FeeReceiverManager) {
                 return Err(InvariantError::NotAdmin);
             }
 
@@ -213,728 +377,2198 @@ where
             pool.fee_receiver = fee_receiver;
             invariant.pools.update(&pool_key, &pool)?;
 
+            self.notify_on(InvariantEvent::FeeReceiverChangedEvent {
+                timestamp: exec::block_timestamp(),
+                pool_key,
+                fee_receiver,
+            })
+            .expect("Failed to notify on fee receiver changed event");
+
             Ok(())
         })
     }
 
-    pub fn create_position(
+    pub fn change_pool_protocol_fee(
         &mut self,
         pool_key: PoolKey,
-        lower_tick: i32,
-        upper_tick: i32,
-        liquidity_delta: Liquidity,
-        slippage_limit_lower: SqrtPrice,
-        slippage_limit_upper: SqrtPrice,
-    ) -> Position {
+        protocol_fee: Option<Percentage>,
+    ) -> Option<Percentage> {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
 
-            let caller = self.exec_context.actor_id();
-            let current_timestamp = exec::block_timestamp();
-            let current_block_number = exec::block_height() as u64;
-
-            // liquidity delta = 0 => return
-            if liquidity_delta == Liquidity::new(U256::from(0)) {
-                return Err(InvariantError::ZeroLiquidity);
+            if !self.is_caller_authorized(&invariant, Role::ProtocolFeeManager) {
+                return Err(InvariantError::NotAdmin);
             }
 
-            if lower_tick == upper_tick {
-                return Err(InvariantError::InvalidTickIndex);
+            let mut pool = invariant.pools.get(&pool_key)?;
+            pool.protocol_fee = protocol_fee;
+            invariant.pools.update(&pool_key, &pool)?;
+
+            self.notify_on(InvariantEvent::PoolProtocolFeeChangedEvent {
+                timestamp: exec::block_timestamp(),
+                pool_key,
+                protocol_fee,
+            })
+            .expect("Failed to notify on pool protocol fee changed event");
+
+            Ok(pool.protocol_fee)
+        })
+    }
+
+    // Reconfiguring the bounds keeps the tick movements already recorded for the pool
+    pub fn change_pool_dynamic_fee(
+        &mut self,
+        pool_key: PoolKey,
+        dynamic_fee: Option<DynamicFee>,
+    ) -> Option<DynamicFee> {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            if !self.is_caller_authorized(&invariant, Role::DynamicFeeManager) {
+                return Err(InvariantError::NotAdmin);
             }
 
             let mut pool = invariant.pools.get(&pool_key)?;
+            pool.dynamic_fee = match dynamic_fee {
+                Some(dynamic_fee) => {
+                    let mut new_dynamic_fee = DynamicFee::new(
+                        dynamic_fee.min_fee,
+                        dynamic_fee.max_fee,
+                        dynamic_fee.fee_per_tick,
+                    )?;
+                    if let Some(current) = pool.dynamic_fee {
+                        new_dynamic_fee.tick_movements = current.tick_movements;
+                    }
 
-            let (mut lower_tick, should_add_lower) =
-                invariant.get_or_create_tick(pool_key, lower_tick);
-            let (mut upper_tick, should_add_upper) =
-                invariant.get_or_create_tick(pool_key, upper_tick);
+                    Some(new_dynamic_fee)
+                }
+                None => None,
+            };
+            invariant.pools.update(&pool_key, &pool)?;
 
-            let (position, x, y) = Position::create(
-                &mut pool,
+            self.notify_on(InvariantEvent::PoolDynamicFeeChangedEvent {
+                timestamp: exec::block_timestamp(),
                 pool_key,
-                &mut lower_tick,
-                &mut upper_tick,
-                current_timestamp,
-                liquidity_delta,
-                slippage_limit_lower,
-                slippage_limit_upper,
-                current_block_number,
-                pool_key.fee_tier.tick_spacing,
-            )?;
-
-            invariant.decrease_token_balance(&pool_key.token_x, &caller, x.into())?;
-            invariant.decrease_token_balance(&pool_key.token_y, &caller, y.into())?;
+                dynamic_fee: pool.dynamic_fee.clone(),
+            })
+            .expect("Failed to notify on pool dynamic fee changed event");
 
-            invariant.pools.update(&pool_key, &pool)?;
+            Ok(pool.dynamic_fee)
+        })
+    }
 
-            invariant.positions.add(&caller, &position);
+    // Deprecation can't be undone, the pool stays open only for exits until it's removed
+    pub fn deprecate_pool(&mut self, pool_key: PoolKey) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
 
-            if should_add_lower {
-                invariant.add_tick(pool_key, lower_tick)?;
-            } else {
-                invariant
-                    .ticks
-                    .update(pool_key, lower_tick.index, lower_tick)?;
+            if !self.is_caller_authorized(&invariant, Role::PoolManager) {
+                return Err(InvariantError::NotAdmin);
             }
 
-            if should_add_upper {
-                invariant.add_tick(pool_key, upper_tick)?;
-            } else {
-                invariant
-                    .ticks
-                    .update(pool_key, upper_tick.index, upper_tick)?;
+            let mut pool = invariant.pools.get(&pool_key)?;
+            if pool.deprecated {
+                return Err(InvariantError::PoolDeprecated);
             }
 
-            self.notify_on(InvariantEvent::PositionCreatedEvent {
+            pool.deprecated = true;
+            invariant.pools.update(&pool_key, &pool)?;
+
+            self.notify_on(InvariantEvent::PoolDeprecatedEvent {
                 timestamp: exec::block_timestamp(),
-                address: self.exec_context.actor_id(),
                 pool_key,
-                liquidity_delta,
-                lower_tick: lower_tick.index,
-                upper_tick: upper_tick.index,
-                current_sqrt_price: pool.sqrt_price,
             })
-            .expect("Failed to notify on position created event");
+            .expect("Failed to notify on pool deprecated event");
 
-            Ok(position)
+            Ok(())
         })
     }
 
-    pub fn get_position(&self, owner_id: ActorId, index: u32) -> Result<Position, InvariantError> {
-        InvariantStorage::as_ref()
-            .positions
-            .get(&owner_id, index)
-            .cloned()
-    }
+    // Anyone can remove a deprecated pool once all of its positions and campaigns are closed
+    pub fn remove_pool(&mut self, pool_key: PoolKey) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
 
-    pub fn get_tick(&self, key: PoolKey, index: i32) -> Result<Tick, InvariantError> {
-        InvariantStorage::as_ref().ticks.get(key, index).cloned()
+            invariant.remove_pool(pool_key)?;
+
+            self.notify_on(InvariantEvent::PoolRemovedEvent {
+                timestamp: exec::block_timestamp(),
+                pool_key,
+            })
+            .expect("Failed to notify on pool removed event");
+
+            Ok(())
+        })
     }
 
-    pub fn is_tick_initialized(&self, key: PoolKey, index: i32) -> bool {
-        InvariantStorage::as_ref()
-            .tickmap
-            .get(index, key.fee_tier.tick_spacing, key)
+    pub fn get_pool_status(&self, pool_key: PoolKey) -> Result<PoolStatus, InvariantError> {
+        InvariantStorage::as_ref().pool_status(pool_key)
     }
-    pub fn remove_position(&mut self, index: u32) -> (TokenAmount, TokenAmount) {
+
+    // Freezes the program, so the exported chunks form a consistent snapshot of the state
+    pub fn start_state_export(&mut self) {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
 
-            let caller = self.exec_context.actor_id();
-            let current_timestamp = exec::block_timestamp();
-
-            let mut position = invariant.positions.get(&caller, index).cloned()?;
-            let Position {
-                pool_key,
-                lower_tick_index,
-                upper_tick_index,
-                liquidity: withdrawn_liquidity,
-                ..
-            } = position;
+            if !self.is_caller_admin(invariant) {
+                return Err(InvariantError::NotAdmin);
+            }
 
-            let mut lower_tick = invariant.ticks.get(pool_key, lower_tick_index).cloned()?;
+            if invariant.state_import.is_some() {
+                return Err(InvariantError::StateImportInProgress);
+            }
 
-            let mut upper_tick = invariant.ticks.get(pool_key, upper_tick_index).cloned()?;
+            invariant.state_export = true;
 
-            let pool = &mut invariant.pools.get(&pool_key)?;
+            Ok(())
+        })
+    }
 
-            let (amount_x, amount_y, remove_lower_tick, remove_upper_tick) = position.remove(
-                pool,
-                current_timestamp,
-                &mut lower_tick,
-                &mut upper_tick,
-                pool_key.fee_tier.tick_spacing,
-            );
+    // Exports the state chunk by chunk, cursor of the next chunk is returned until the last
+    // section is exported
+    pub fn export_state(&self, cursor: Option<StateCursor>) -> Result<StateExport, InvariantError> {
+        let invariant = InvariantStorage::as_ref();
 
-            invariant.pools.update(&pool_key, pool)?;
+        if !self.is_caller_admin(invariant) {
+            return Err(InvariantError::NotAdmin);
+        }
 
-            if remove_lower_tick {
-                invariant.remove_tick(pool_key, lower_tick)?;
-            } else {
-                invariant
-                    .ticks
-                    .update(pool_key, lower_tick_index, lower_tick)?;
-            }
+        if !invariant.state_export {
+            return Err(InvariantError::StateExportNotStarted);
+        }
 
-            if remove_upper_tick {
-                invariant.remove_tick(pool_key, upper_tick)?;
-            } else {
-                invariant
-                    .ticks
-                    .update(pool_key, upper_tick_index, upper_tick)?;
-            }
+        invariant.export_state(cursor.unwrap_or_default())
+    }
 
-            invariant.positions.remove(&caller, index)?;
+    pub fn finish_state_export(&mut self) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
 
-            let token_x = pool_key.token_x;
-            let token_y = pool_key.token_y;
+            if !self.is_caller_admin(invariant) {
+                return Err(InvariantError::NotAdmin);
+            }
 
-            invariant.increase_token_balance(&token_x, &caller, amount_x)?;
-            invariant.increase_token_balance(&token_y, &caller, amount_y)?;
+            if !invariant.state_export {
+                return Err(InvariantError::StateExportNotStarted);
+            }
 
-            self.notify_on(InvariantEvent::PositionRemovedEvent {
-                timestamp: exec::block_timestamp(),
-                address: self.exec_context.actor_id(),
-                pool_key,
-                liquidity: withdrawn_liquidity,
-                lower_tick_index: lower_tick.index,
-                upper_tick_index: upper_tick.index,
-                sqrt_price: pool.sqrt_price,
-            })
-            .expect("Failed to notify on position removed event");
+            invariant.state_export = false;
 
-            Ok((amount_x, amount_y))
+            Ok(())
         })
     }
 
-    pub fn transfer_position(&mut self, index: u32, receiver: ActorId) {
+    // The first chunk locks swaps, liquidity changes, deposits and withdrawals until the import
+    // is finalized, returns the checksum of all chunks imported so far
+    pub fn import_state(&mut self, chunk: StateChunk) -> StateChecksum {
         panicking!(move || {
-            InvariantStorage::as_mut().positions.transfer(
-                &self.exec_context.actor_id(),
-                index,
-                &receiver,
-            )
+            let invariant = InvariantStorage::as_mut();
+
+            if !self.is_caller_admin(invariant) {
+                return Err(InvariantError::NotAdmin);
+            }
+
+            let checksum = match invariant.state_import {
+                Some(checksum) => checksum,
+                None if invariant.is_empty() => StateChecksum::default(),
+                None => return Err(InvariantError::StateNotEmpty),
+            };
+            let checksum = chain_state_checksum(checksum, chunk.checksum());
+
+            invariant.import_state(chunk)?;
+            invariant.state_import = Some(checksum);
+
+            Ok(checksum)
         })
     }
 
-    pub fn get_all_positions(&self, owner_id: ActorId) -> Vec<Position> {
-        InvariantStorage::as_ref().positions.get_all(&owner_id)
-    }
+    // Checksum has to match the chained checksums of the exported chunks
+    pub fn finalize_state_import(&mut self, checksum: StateChecksum) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
 
-    pub fn get_positions(
-        &self,
-        owner_id: ActorId,
-        size: u32,
-        offset: u32,
-    ) -> Result<(Vec<(Pool, Vec<(Position, u32)>)>, u32), InvariantError> {
-        let invariant = InvariantStorage::as_ref();
-        let positions = invariant.positions.get_slice(&owner_id, offset, size);
-        let mut grouped_positions: Vec<(Pool, Vec<(Position, u32)>)> = vec![];
+            if !self.is_caller_admin(invariant) {
+                return Err(InvariantError::NotAdmin);
+            }
 
-        for (index, position) in positions.into_iter().enumerate() {
-            let position_index = offset + index as u32;
+            let imported_checksum = invariant
+                .state_import
+                .ok_or(InvariantError::StateImportNotStarted)?;
 
-            if let Some(entry) = grouped_positions.iter_mut().find(|(_pool, positions)| {
-                positions
-                    .first()
-                    .expect("Entires have at least one position")
-                    .0
-                    .pool_key
-                    == position.pool_key
-            }) {
-                entry.1.push((position, position_index));
-            } else {
-                let pool = invariant.pools.get(&position.pool_key)?;
-                grouped_positions.push((pool, vec![(position, position_index)]));
+            if imported_checksum != checksum {
+                return Err(InvariantError::StateChecksumMismatch);
             }
-        }
 
-        Ok((grouped_positions, invariant.positions.get_length(&owner_id)))
+            invariant.state_import = None;
+
+            Ok(())
+        })
     }
 
-    pub fn get_position_with_associates(
-        &self,
-        owner: ActorId,
-        index: u32,
-    ) -> Result<(Position, Pool, Tick, Tick), InvariantError> {
-        let invariant = InvariantStorage::as_ref();
-        let position = invariant.positions.get(&owner, index)?;
-
-        let pool = invariant.pools.get(&position.pool_key)?;
-        let tick_lower = invariant
-            .ticks
-            .get(position.pool_key, position.lower_tick_index)?;
-        let tick_upper = invariant
-            .ticks
-            .get(position.pool_key, position.upper_tick_index)?;
-        Ok((*position, pool, *tick_lower, *tick_upper))
-    }
-
-    pub fn get_user_position_amount(&self, owner_id: ActorId) -> u32 {
-        let invariant = InvariantStorage::as_ref();
-        invariant.positions.get_length(&owner_id)
-    }
-
-    pub fn get_liquidity_ticks_amount(&self, pool_key: PoolKey) -> u32 {
-        InvariantStorage::as_ref().liquidity_ticks_count(pool_key)
-    }
+    pub fn create_position(
+        &mut self,
+        pool_key: PoolKey,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+        deadline: Option<u64>,
+    ) -> Position {
+        panicking!(move || {
+            Self::check_deadline(deadline)?;
 
-    pub fn get_all_pools_for_pair(
-        &self,
-        token0: ActorId,
-        token1: ActorId,
-    ) -> Result<Vec<(FeeTier, Pool)>, InvariantError> {
-        let invariant = InvariantStorage::as_ref();
-        let fee_tiers = invariant.fee_tiers.get_all();
-        let mut pools: Vec<(FeeTier, Pool)> = vec![];
-        for fee_tier in fee_tiers {
-            let pool_key = PoolKey::new(token0, token1, fee_tier)?;
-            if let Ok(pool) = invariant.pools.get(&pool_key) {
-                pools.push((fee_tier, pool));
-            }
-        }
-        Ok(pools)
+            self.add_position(
+                pool_key,
+                lower_tick,
+                upper_tick,
+                liquidity_delta,
+                slippage_limit_lower,
+                slippage_limit_upper,
+            )
+            .map(|(_, position, _, _)| position)
+        })
     }
 
-    pub fn swap(
+    // Limit orders hold a single token, so there is no price to protect against slippage
+    pub fn create_limit_order(
         &mut self,
         pool_key: PoolKey,
-        x_to_y: bool,
-        amount: TokenAmount,
-        by_amount_in: bool,
-        sqrt_price_limit: SqrtPrice,
-    ) -> CalculateSwapResult {
+        lower_tick: i32,
+        liquidity_delta: Liquidity,
+    ) -> Position {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
 
-            let caller = self.exec_context.actor_id();
+            let upper_tick = lower_tick + pool_key.fee_tier.tick_spacing as i32;
+            let pool = invariant.pools.get(&pool_key)?;
+            let limit_order = LimitOrder::create(&pool, pool_key, lower_tick, upper_tick)?;
 
-            let calculate_swap_result = invariant.calculate_swap(
+            let (position_id, position, _, _) = self.add_position(
                 pool_key,
-                x_to_y,
-                amount,
-                by_amount_in,
-                sqrt_price_limit,
-            )?;
-
-            let mut crossed_tick_indexes: Vec<i32> = vec![];
-
-            for tick in calculate_swap_result.ticks.iter() {
-                crossed_tick_indexes.push(tick.index);
-                invariant.ticks.update(pool_key, tick.index, *tick)?;
-            }
-
-            invariant
-                .pools
-                .update(&pool_key, &calculate_swap_result.pool)?;
-
-            let (swapped_token, returned_token) = if x_to_y {
-                (&pool_key.token_x, &pool_key.token_y)
-            } else {
-                (&pool_key.token_y, &pool_key.token_x)
-            };
-
-            invariant.decrease_token_balance(
-                &swapped_token,
-                &caller,
-                calculate_swap_result.amount_in.into(),
-            )?;
-
-            invariant.increase_token_balance(
-                &returned_token,
-                &caller,
-                calculate_swap_result.amount_out.into(),
+                lower_tick,
+                upper_tick,
+                liquidity_delta,
+                pool.sqrt_price,
+                pool.sqrt_price,
             )?;
 
-            if !crossed_tick_indexes.is_empty() {
-                self.notify_on(InvariantEvent::CrossTickEvent {
-                    timestamp: exec::block_timestamp(),
-                    address: caller,
-                    pool_key,
-                    indexes: crossed_tick_indexes,
-                })
-                .expect("Failed to notify on cross tick event");
-            }
-
-            self.notify_on(InvariantEvent::SwapEvent {
-                timestamp: exec::block_timestamp(),
-                address: caller,
-                pool_key,
-                amount_in: calculate_swap_result.amount_in,
-                amount_out: calculate_swap_result.amount_out,
-                fee: calculate_swap_result.fee,
-                start_sqrt_price: calculate_swap_result.start_sqrt_price,
-                target_sqrt_price: calculate_swap_result.target_sqrt_price,
-                x_to_y,
-            })
-            .expect("Failed to notify on swap event");
+            invariant.limit_orders.add(position_id, &limit_order)?;
 
-            Ok(calculate_swap_result)
+            Ok(position)
         })
     }
 
-    pub fn swap_route(
-        &mut self,
-        amount_in: TokenAmount,
-        expected_amount_out: TokenAmount,
-        slippage: Percentage,
-        swaps: Vec<SwapHop>,
-    ) -> TokenAmount {
-        panicking!(move || {
-            let amount_out = Self::route(RouteType::Swap(self), amount_in, swaps)?;
+    pub fn get_limit_order(&self, position_id: PositionId) -> Result<LimitOrder, InvariantError> {
+        InvariantStorage::as_ref()
+            .limit_orders
+            .get(position_id)
+            .cloned()
+    }
 
-            let min_amount_out = calculate_min_amount_out(expected_amount_out, slippage);
+    pub fn get_position(&self, position_id: PositionId) -> Result<Position, InvariantError> {
+        InvariantStorage::as_ref()
+            .positions
+            .get(position_id)
+            .cloned()
+    }
 
-            if amount_out < min_amount_out {
-                return Err(InvariantError::AmountUnderMinimumAmountOut);
-            } else {
-                Ok(amount_out)
-            }
-        })
+    pub fn get_position_owner(&self, position_id: PositionId) -> Result<ActorId, InvariantError> {
+        InvariantStorage::as_ref().positions.get_owner(position_id)
     }
 
-    pub fn quote(
-        &self,
-        pool_key: PoolKey,
-        x_to_y: bool,
-        amount: TokenAmount,
-        by_amount_in: bool,
-        sqrt_price_limit: SqrtPrice,
-    ) -> Result<QuoteResult, InvariantError> {
-        let invariant = InvariantStorage::as_ref();
+    pub fn get_position_ids(&self, owner_id: ActorId) -> Vec<PositionId> {
+        InvariantStorage::as_ref()
+            .positions
+            .get_ids(&owner_id)
+            .to_vec()
+    }
 
-        let calculate_swap_result =
-            invariant.calculate_swap(pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit)?;
+    // Index based lookups are kept for compatibility, indexes change when positions are removed
+    pub fn get_position_id(
+        &self,
+        owner_id: ActorId,
+        index: u32,
+    ) -> Result<PositionId, InvariantError> {
+        InvariantStorage::as_ref()
+            .positions
+            .get_id(&owner_id, index)
+    }
 
-        Ok(QuoteResult {
-            amount_in: calculate_swap_result.amount_in,
-            amount_out: calculate_swap_result.amount_out,
-            target_sqrt_price: calculate_swap_result.pool.sqrt_price,
-            ticks: calculate_swap_result.ticks,
-        })
+    pub fn get_position_by_index(
+        &self,
+        owner_id: ActorId,
+        index: u32,
+    ) -> Result<Position, InvariantError> {
+        InvariantStorage::as_ref()
+            .positions
+            .get_by_index(&owner_id, index)
+            .cloned()
     }
 
-    pub fn quote_route(
+    // Seconds per liquidity spent in range since the position was last modified
+    pub fn get_position_seconds_inside(
         &self,
-        amount_in: TokenAmount,
-        swaps: Vec<SwapHop>,
-    ) -> Result<TokenAmount, InvariantError> {
-        Self::route(RouteType::Quote, amount_in, swaps)
+        position_id: PositionId,
+    ) -> Result<SecondsPerLiquidity, InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+        let position = invariant.positions.get(position_id)?;
+
+        Ok(invariant
+            .seconds_per_liquidity_inside(position)?
+            .unchecked_sub(position.seconds_per_liquidity_inside))
     }
 
-    fn route<'a>(
-        mut route_type: RouteType<'a, TExecContext>,
-        amount_in: TokenAmount,
-        swaps: Vec<SwapHop>,
-    ) -> Result<TokenAmount, InvariantError> {
-        let mut next_swap_amount = amount_in;
+    pub fn get_tick(&self, key: PoolKey, index: i32) -> Result<Tick, InvariantError> {
         let invariant = InvariantStorage::as_ref();
+        let pool_id = invariant.pool_id(&key)?;
 
-        for swap in swaps.iter() {
-            let SwapHop { pool_key, x_to_y } = *swap;
+        invariant.ticks.get(pool_id, index).cloned()
+    }
 
-            let sqrt_price_limit = if x_to_y {
-                SqrtPrice::new(MIN_SQRT_PRICE.into())
-            } else {
-                SqrtPrice::new(MAX_SQRT_PRICE.into())
-            };
-            let result = match &mut route_type {
-                RouteType::Swap(contract) => {
-                    contract.swap(pool_key, x_to_y, next_swap_amount, true, sqrt_price_limit)
-                }
-                RouteType::Quote => invariant.calculate_swap(
-                    pool_key,
-                    x_to_y,
-                    next_swap_amount,
-                    true,
-                    sqrt_price_limit,
-                )?,
-            };
+    pub fn is_tick_initialized(&self, key: PoolKey, index: i32) -> bool {
+        let invariant = InvariantStorage::as_ref();
 
-            next_swap_amount = result.amount_out;
-        }
+        invariant.pool_id(&key).map_or(false, |pool_id| {
+            invariant
+                .tickmap
+                .get(index, key.fee_tier.tick_spacing, pool_id)
+        })
+    }
+    pub fn remove_position(
+        &mut self,
+        position_id: PositionId,
+        deadline: Option<u64>,
+    ) -> (TokenAmount, TokenAmount) {
+        panicking!(move || {
+            Self::check_deadline(deadline)?;
 
-        Ok(next_swap_amount)
+            self.execute_remove_position(position_id)
+                .map(|(_, _, amount_x, amount_y)| (amount_x, amount_y))
+        })
     }
 
-    pub fn claim_fee(&mut self, index: u32) -> (TokenAmount, TokenAmount) {
+    pub fn increase_liquidity(
+        &mut self,
+        position_id: PositionId,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+    ) -> Position {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
 
             let caller = self.exec_context.actor_id();
             let current_timestamp = exec::block_timestamp();
 
-            let mut position = invariant.positions.get(&caller, index).cloned()?;
+            invariant.check_flash_loan(&caller)?;
+
+            let mut position = invariant
+                .positions
+                .get_owned(&caller, position_id)
+                .cloned()?;
+
+            invariant.check_pause(Some(position.pool_key), PausableAction::NewLiquidity)?;
+
+            // staked liquidity is fixed for the whole stake
+            if invariant.stakes.contains(position_id) {
+                return Err(InvariantError::PositionStaked);
+            }
+
+            if invariant.limit_orders.is_settled(position_id) {
+                return Err(InvariantError::LimitOrderSettled);
+            }
+            let pool_key = position.pool_key;
+            let pool_id = invariant.pool_id(&pool_key)?;
 
             let mut lower_tick = invariant
                 .ticks
-                .get(position.pool_key, position.lower_tick_index)
+                .get(pool_id, position.lower_tick_index)
                 .cloned()?;
-
             let mut upper_tick = invariant
                 .ticks
-                .get(position.pool_key, position.upper_tick_index)
+                .get(pool_id, position.upper_tick_index)
                 .cloned()?;
 
-            let mut pool = invariant.pools.get(&position.pool_key)?;
+            let mut pool = invariant.pools.get(&pool_key)?;
 
-            let (x, y) = position.claim_fee(
+            invariant.write_observation(pool_key, &pool)?;
+
+            let (x, y) = position.increase_liquidity(
                 &mut pool,
-                &mut upper_tick,
                 &mut lower_tick,
+                &mut upper_tick,
                 current_timestamp,
-            );
+                liquidity_delta,
+                slippage_limit_lower,
+                slippage_limit_upper,
+            )?;
 
-            invariant.positions.update(&caller, index, &position)?;
-            invariant.pools.update(&position.pool_key, &pool)?;
+            invariant.decrease_token_balance(&pool_key.token_x, &caller, x.into())?;
+            invariant.decrease_token_balance(&pool_key.token_y, &caller, y.into())?;
+
+            invariant.positions.update(position_id, &position)?;
+            invariant.pools.update(&pool_key, &pool)?;
             invariant
                 .ticks
-                .update(position.pool_key, upper_tick.index, upper_tick)?;
+                .update(pool_id, lower_tick.index, lower_tick)?;
             invariant
                 .ticks
-                .update(position.pool_key, lower_tick.index, lower_tick)?;
+                .update(pool_id, upper_tick.index, upper_tick)?;
 
-            invariant.increase_token_balance(&position.pool_key.token_x, &caller, x)?;
-            invariant.increase_token_balance(&position.pool_key.token_y, &caller, y)?;
+            self.notify_on(InvariantEvent::PositionLiquidityIncreasedEvent {
+                timestamp: current_timestamp,
+                address: caller,
+                pool_key,
+                position_id,
+                liquidity_delta,
+                lower_tick: lower_tick.index,
+                upper_tick: upper_tick.index,
+                current_sqrt_price: pool.sqrt_price,
+            })
+            .expect("Failed to notify on position liquidity increased event");
 
-            Ok((x, y))
+            Ok(position)
         })
     }
 
-    pub fn withdraw_protocol_fee(&mut self, pool_key: PoolKey) {
+    pub fn decrease_liquidity(
+        &mut self,
+        position_id: PositionId,
+        liquidity_delta: Liquidity,
+    ) -> (TokenAmount, TokenAmount) {
         panicking!(move || {
             let invariant = InvariantStorage::as_mut();
 
             let caller = self.exec_context.actor_id();
+            let current_timestamp = exec::block_timestamp();
 
-            let mut pool = invariant.pools.get(&pool_key)?;
+            let mut position = invariant
+                .positions
+                .get_owned(&caller, position_id)
+                .cloned()?;
 
-            if pool.fee_receiver != caller {
-                return Err(InvariantError::NotFeeReceiver);
+            invariant.check_pause(Some(position.pool_key), PausableAction::Withdrawal)?;
+
+            if invariant.stakes.contains(position_id) {
+                return Err(InvariantError::PositionStaked);
             }
 
-            let (amount_x, amount_y) = pool.withdraw_protocol_fee(pool_key);
-            invariant.pools.update(&pool_key, &pool)?;
+            if invariant.limit_orders.is_settled(position_id) {
+                return Err(InvariantError::LimitOrderSettled);
+            }
+            let pool_key = position.pool_key;
+            let pool_id = invariant.pool_id(&pool_key)?;
 
-            invariant.increase_token_balance(&pool_key.token_x, &caller, amount_x)?;
-            invariant.increase_token_balance(&pool_key.token_y, &caller, amount_y)?;
+            let mut lower_tick = invariant
+                .ticks
+                .get(pool_id, position.lower_tick_index)
+                .cloned()?;
+            let mut upper_tick = invariant
+                .ticks
+                .get(pool_id, position.upper_tick_index)
+                .cloned()?;
 
-            Ok(())
-        })
-    }
+            let mut pool = invariant.pools.get(&pool_key)?;
 
-    pub fn get_user_balances(&self, user: ActorId) -> Vec<(ActorId, TokenAmount)> {
-        InvariantStorage::as_ref()
-            .balances
-            .get(&user)
-            .cloned()
-            .unwrap_or_default()
-            .iter()
-            .map(|(k, v)| (*k, *v))
-            .collect()
-    }
+            invariant.write_observation(pool_key, &pool)?;
+
+            let (x, y) = position.decrease_liquidity(
+                &mut pool,
+                &mut lower_tick,
+                &mut upper_tick,
+                current_timestamp,
+                liquidity_delta,
+            )?;
+
+            invariant.positions.update(position_id, &position)?;
+            invariant.pools.update(&pool_key, &pool)?;
+            invariant
+                .ticks
+                .update(pool_id, lower_tick.index, lower_tick)?;
+            invariant
+                .ticks
+                .update(pool_id, upper_tick.index, upper_tick)?;
+
+            invariant.increase_token_balance(&pool_key.token_x, &caller, x)?;
+            invariant.increase_token_balance(&pool_key.token_y, &caller, y)?;
+
+            self.notify_on(InvariantEvent::PositionLiquidityDecreasedEvent {
+                timestamp: current_timestamp,
+                address: caller,
+                pool_key,
+                position_id,
+                liquidity_delta,
+                lower_tick: lower_tick.index,
+                upper_tick: upper_tick.index,
+                current_sqrt_price: pool.sqrt_price,
+            })
+            .expect("Failed to notify on position liquidity decreased event");
+
+            Ok((x, y))
+        })
+    }
+
+    pub fn transfer_position(&mut self, position_id: PositionId, receiver: ActorId) {
+        panicking!(move || self.execute_transfer_position(position_id, receiver))
+    }
+
+    pub fn approve_position(&mut self, position_id: PositionId, spender: ActorId) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+
+            invariant.approve_position(&self.exec_context.actor_id(), position_id, &spender)?;
+
+            self.notify_on(InvariantEvent::PositionApprovedEvent {
+                timestamp: exec::block_timestamp(),
+                position_id,
+                owner: invariant.positions.get_owner(position_id)?,
+                spender,
+            })
+            .expect("Failed to notify on position approved event");
+
+            Ok(())
+        })
+    }
+
+    pub fn set_position_operator(&mut self, operator: ActorId, approved: bool) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let owner = self.exec_context.actor_id();
+
+            invariant
+                .positions
+                .set_operator(&owner, &operator, approved);
+
+            self.notify_on(InvariantEvent::OperatorSetEvent {
+                timestamp: exec::block_timestamp(),
+                owner,
+                operator,
+                approved,
+            })
+            .expect("Failed to notify on operator set event");
+
+            Ok(())
+        })
+    }
+
+    pub fn get_position_approved(&self, position_id: PositionId) -> Option<ActorId> {
+        InvariantStorage::as_ref()
+            .positions
+            .get_approved(position_id)
+    }
+
+    pub fn is_position_operator(&self, owner_id: ActorId, operator: ActorId) -> bool {
+        InvariantStorage::as_ref()
+            .positions
+            .is_operator(&owner_id, &operator)
+    }
+
+    pub fn get_all_positions(&self, owner_id: ActorId) -> Vec<Position> {
+        InvariantStorage::as_ref().positions.get_all(&owner_id)
+    }
+
+    pub fn get_positions(
+        &self,
+        owner_id: ActorId,
+        size: u32,
+        offset: u32,
+    ) -> Result<(Vec<(Pool, Vec<(Position, PositionId)>)>, u32), InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+        let positions = invariant.positions.get_slice(&owner_id, offset, size);
+        let mut grouped_positions: Vec<(Pool, Vec<(Position, PositionId)>)> = vec![];
+
+        for (position_id, position) in positions {
+            if let Some(entry) = grouped_positions.iter_mut().find(|(_pool, positions)| {
+                positions
+                    .first()
+                    .expect("Entires have at least one position")
+                    .0
+                    .pool_key
+                    == position.pool_key
+            }) {
+                entry.1.push((position, position_id));
+            } else {
+                let pool = invariant.pools.get(&position.pool_key)?;
+                grouped_positions.push((pool, vec![(position, position_id)]));
+            }
+        }
+
+        Ok((grouped_positions, invariant.positions.get_length(&owner_id)))
+    }
+
+    pub fn get_position_with_associates(
+        &self,
+        position_id: PositionId,
+    ) -> Result<(Position, Pool, Tick, Tick), InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+        let position = invariant.positions.get(position_id)?;
+
+        let pool = invariant.pools.get(&position.pool_key)?;
+        let pool_id = invariant.pool_id(&position.pool_key)?;
+        let tick_lower = invariant.ticks.get(pool_id, position.lower_tick_index)?;
+        let tick_upper = invariant.ticks.get(pool_id, position.upper_tick_index)?;
+        Ok((*position, pool, *tick_lower, *tick_upper))
+    }
+
+    pub fn get_user_position_amount(&self, owner_id: ActorId) -> u32 {
+        let invariant = InvariantStorage::as_ref();
+        invariant.positions.get_length(&owner_id)
+    }
+
+    pub fn get_liquidity_ticks_amount(&self, pool_key: PoolKey) -> u32 {
+        InvariantStorage::as_ref().liquidity_ticks_count(pool_key)
+    }
+
+    pub fn get_all_pools_for_pair(
+        &self,
+        token0: ActorId,
+        token1: ActorId,
+    ) -> Result<Vec<(FeeTier, Pool)>, InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+        let fee_tiers = invariant.fee_tiers.get_all();
+        let mut pools: Vec<(FeeTier, Pool)> = vec![];
+        for fee_tier in fee_tiers {
+            let pool_key = PoolKey::new(token0, token1, fee_tier)?;
+            if let Ok(pool) = invariant.pools.get(&pool_key) {
+                pools.push((fee_tier, pool));
+            }
+        }
+        Ok(pools)
+    }
+
+    // Output of the swap is optionally transferred back to the caller in the same message
+    pub async fn swap(
+        &mut self,
+        pool_key: PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+        deadline: Option<u64>,
+        withdraw_output: bool,
+    ) -> CalculateSwapResult {
+        panicking_async!(|| async move {
+            Self::check_deadline(deadline)?;
+
+            let result =
+                self.execute_swap(pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit)?;
+
+            if withdraw_output {
+                let returned_token = if x_to_y {
+                    pool_key.token_y
+                } else {
+                    pool_key.token_x
+                };
+
+                self.send_output(returned_token, result.amount_out).await?;
+            }
+
+            Ok(result)
+        })
+    }
+
+    // Vara attached to the message is the input of the swap
+    pub async fn swap_vara_in(
+        &mut self,
+        pool_key: PoolKey,
+        sqrt_price_limit: SqrtPrice,
+        deadline: Option<u64>,
+        withdraw_output: bool,
+    ) -> CalculateSwapResult {
+        panicking_async!(|| async move {
+            Self::check_deadline(deadline)?;
 
-    pub fn deposit_vara(&mut self) -> TokenAmount {
-        panicking!(|| {
             let invariant = InvariantStorage::as_mut();
+            let caller = self.exec_context.actor_id();
             let value = TokenAmount(msg::value().into());
 
-            invariant
-                .increase_token_balance(&VARA_ADDRESS, &msg::source(), value)
-                .map(|_| value)
-        })
-    }
+            let x_to_y = if pool_key.token_x == VARA_ADDRESS {
+                true
+            } else if pool_key.token_y == VARA_ADDRESS {
+                false
+            } else {
+                return Err(InvariantError::InvalidVaraDepositAttempt);
+            };
+
+            invariant.increase_token_balance(&VARA_ADDRESS, &caller, value)?;
+
+            let result = self.execute_swap(pool_key, x_to_y, value, true, sqrt_price_limit)?;
+
+            if withdraw_output {
+                let returned_token = if x_to_y {
+                    pool_key.token_y
+                } else {
+                    pool_key.token_x
+                };
+
+                self.send_output(returned_token, result.amount_out).await?;
+            }
+
+            Ok(result)
+        })
+    }
+
+    // Amount limit is the minimum amount out for exact input swaps and the maximum amount in
+    // for exact output swaps, the price can move from the current one by at most the slippage
+    pub fn swap_with_slippage(
+        &mut self,
+        pool_key: PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        amount_limit: TokenAmount,
+        slippage: Percentage,
+        deadline: Option<u64>,
+    ) -> CalculateSwapResult {
+        panicking!(move || {
+            Self::check_deadline(deadline)?;
+
+            let pool = InvariantStorage::as_ref().pools.get(&pool_key)?;
+            let sqrt_price_limit =
+                Self::slippage_sqrt_price_limit(pool.sqrt_price, slippage, x_to_y)?;
+
+            let result =
+                self.execute_swap(pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit)?;
+
+            if by_amount_in && result.amount_out < amount_limit {
+                return Err(InvariantError::AmountUnderMinimumAmountOut);
+            }
+
+            if !by_amount_in && result.amount_in > amount_limit {
+                return Err(InvariantError::AmountOverMaximumAmountIn);
+            }
+
+            Ok(result)
+        })
+    }
+
+    // Output of the route is optionally transferred back to the caller in the same message
+    pub async fn swap_route(
+        &mut self,
+        amount_in: TokenAmount,
+        expected_amount_out: TokenAmount,
+        slippage: Percentage,
+        swaps: Vec<SwapHop>,
+        deadline: Option<u64>,
+        withdraw_output: bool,
+    ) -> TokenAmount {
+        panicking_async!(|| async move {
+            Self::check_deadline(deadline)?;
+
+            let returned_token = match swaps.last() {
+                Some(SwapHop { pool_key, x_to_y }) if *x_to_y => Some(pool_key.token_y),
+                Some(SwapHop { pool_key, .. }) => Some(pool_key.token_x),
+                None => None,
+            };
+
+            let amount_out =
+                self.execute_swap_route(amount_in, expected_amount_out, slippage, swaps)?;
+
+            if let (true, Some(returned_token)) = (withdraw_output, returned_token) {
+                self.send_output(returned_token, amount_out).await?;
+            }
+
+            Ok(amount_out)
+        })
+    }
+
+    fn execute_swap_route(
+        &mut self,
+        amount_in: TokenAmount,
+        expected_amount_out: TokenAmount,
+        slippage: Percentage,
+        swaps: Vec<SwapHop>,
+    ) -> Result<TokenAmount, InvariantError> {
+        let amount_out = Self::route(RouteType::Swap(self), amount_in, swaps)?;
+
+        let min_amount_out = calculate_min_amount_out(expected_amount_out, slippage);
+
+        if amount_out < min_amount_out {
+            return Err(InvariantError::AmountUnderMinimumAmountOut);
+        }
+
+        Ok(amount_out)
+    }
+
+    pub fn swap_split_route(
+        &mut self,
+        amount_in: TokenAmount,
+        expected_amount_out: TokenAmount,
+        slippage: Percentage,
+        routes: Vec<(Percentage, Vec<SwapHop>)>,
+        deadline: Option<u64>,
+    ) -> TokenAmount {
+        panicking!(move || {
+            Self::check_deadline(deadline)?;
+
+            let amount_out = Self::split_route(RouteType::Swap(self), amount_in, routes)?;
+
+            let min_amount_out = calculate_min_amount_out(expected_amount_out, slippage);
+
+            if amount_out < min_amount_out {
+                return Err(InvariantError::AmountUnderMinimumAmountOut);
+            } else {
+                Ok(amount_out)
+            }
+        })
+    }
+
+    pub fn swap_route_exact_out(
+        &mut self,
+        amount_out: TokenAmount,
+        max_amount_in: TokenAmount,
+        swaps: Vec<SwapHop>,
+        deadline: Option<u64>,
+    ) -> TokenAmount {
+        panicking!(move || {
+            Self::check_deadline(deadline)?;
+
+            let amount_in = Self::route_exact_out(RouteType::Swap(self), amount_out, swaps)?;
+
+            if amount_in > max_amount_in {
+                return Err(InvariantError::AmountOverMaximumAmountIn);
+            } else {
+                Ok(amount_in)
+            }
+        })
+    }
+
+    pub fn quote(
+        &self,
+        pool_key: PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    ) -> Result<QuoteResult, InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+
+        let calculate_swap_result =
+            invariant.calculate_swap(pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit)?;
+
+        Ok(QuoteResult {
+            amount_in: calculate_swap_result.amount_in,
+            amount_out: calculate_swap_result.amount_out,
+            target_sqrt_price: calculate_swap_result.pool.sqrt_price,
+            ticks: calculate_swap_result.ticks,
+        })
+    }
+
+    pub fn quote_route(
+        &self,
+        amount_in: TokenAmount,
+        swaps: Vec<SwapHop>,
+    ) -> Result<TokenAmount, InvariantError> {
+        Self::route(RouteType::Quote, amount_in, swaps)
+    }
+
+    // The result is an estimate, every path is quoted on the current state as if it was the only
+    // one. When paths share a pool, executing one of them moves the price for the others, so the
+    // actual output of the route can be lower than quoted
+    pub fn quote_split_route(
+        &self,
+        amount_in: TokenAmount,
+        routes: Vec<(Percentage, Vec<SwapHop>)>,
+    ) -> Result<TokenAmount, InvariantError> {
+        Self::split_route(RouteType::Quote, amount_in, routes)
+    }
+
+    // Greedily assigns every part of the amount to the path that gives the most for it, paths are
+    // quoted independently like in quote_split_route
+    pub fn propose_split_route(
+        &self,
+        amount_in: TokenAmount,
+        routes: Vec<Vec<SwapHop>>,
+    ) -> Result<Vec<(Percentage, Vec<SwapHop>)>, InvariantError> {
+        if routes.is_empty() {
+            return Err(InvariantError::InvalidSplitRoute);
+        }
+
+        let part = Percentage::from_integer(1).get() / SPLIT_ROUTE_PARTS;
+        let mut parts = vec![0u128; routes.len()];
+        let mut amounts_out = vec![TokenAmount::new(U256::from(0)); routes.len()];
+
+        for _ in 0..SPLIT_ROUTE_PARTS {
+            let mut best: Option<(usize, TokenAmount, TokenAmount)> = None;
+            let mut last_error = InvariantError::NoGainSwap;
+
+            for (i, swaps) in routes.iter().enumerate() {
+                let amount = amount_in.big_mul(Percentage::new(part * (parts[i] + 1)));
+
+                let amount_out = match Self::route(RouteType::Quote, amount, swaps.clone()) {
+                    Ok(amount_out) => amount_out,
+                    Err(e) => {
+                        last_error = e;
+                        continue;
+                    }
+                };
+
+                if let Ok(gain) = amount_out.checked_sub(amounts_out[i]) {
+                    if best.map_or(true, |(_, best_gain, _)| gain > best_gain) {
+                        best = Some((i, gain, amount_out));
+                    }
+                }
+            }
+
+            let (i, _, amount_out) = best.ok_or(last_error)?;
+            parts[i] += 1;
+            amounts_out[i] = amount_out;
+        }
+
+        Ok(routes
+            .into_iter()
+            .zip(parts)
+            .filter(|(_, parts)| *parts > 0)
+            .map(|(swaps, parts)| (Percentage::new(part * parts), swaps))
+            .collect())
+    }
+
+    pub fn quote_route_exact_out(
+        &self,
+        amount_out: TokenAmount,
+        swaps: Vec<SwapHop>,
+    ) -> Result<TokenAmount, InvariantError> {
+        Self::route_exact_out(RouteType::Quote, amount_out, swaps)
+    }
+
+    fn route<'a>(
+        mut route_type: RouteType<'a, TExecContext>,
+        amount_in: TokenAmount,
+        swaps: Vec<SwapHop>,
+    ) -> Result<TokenAmount, InvariantError> {
+        let mut next_swap_amount = amount_in;
+        let invariant = InvariantStorage::as_ref();
+
+        for swap in swaps.iter() {
+            let SwapHop { pool_key, x_to_y } = *swap;
+
+            let sqrt_price_limit = Self::route_sqrt_price_limit(x_to_y);
+            let result = match &mut route_type {
+                RouteType::Swap(contract) => contract.execute_swap(
+                    pool_key,
+                    x_to_y,
+                    next_swap_amount,
+                    true,
+                    sqrt_price_limit,
+                )?,
+                RouteType::Quote => invariant.calculate_swap(
+                    pool_key,
+                    x_to_y,
+                    next_swap_amount,
+                    true,
+                    sqrt_price_limit,
+                )?,
+            };
+
+            next_swap_amount = result.amount_out;
+        }
+
+        Ok(next_swap_amount)
+    }
+
+    // Required inputs are found by walking the hops in reverse, then every hop is executed
+    // for exactly the amount the next one needs
+    fn route_exact_out<'a>(
+        mut route_type: RouteType<'a, TExecContext>,
+        amount_out: TokenAmount,
+        swaps: Vec<SwapHop>,
+    ) -> Result<TokenAmount, InvariantError> {
+        let mut next_swap_amount = amount_out;
+        let mut hop_amounts_out = vec![];
+        let invariant = InvariantStorage::as_ref();
+
+        for swap in swaps.iter().rev() {
+            let SwapHop { pool_key, x_to_y } = *swap;
+
+            hop_amounts_out.push(next_swap_amount);
+
+            let result = invariant.calculate_swap(
+                pool_key,
+                x_to_y,
+                next_swap_amount,
+                false,
+                Self::route_sqrt_price_limit(x_to_y),
+            )?;
+
+            next_swap_amount = result.amount_in;
+        }
+
+        if let RouteType::Swap(contract) = &mut route_type {
+            for (i, (swap, hop_amount_out)) in
+                swaps.iter().zip(hop_amounts_out.iter().rev()).enumerate()
+            {
+                let SwapHop { pool_key, x_to_y } = *swap;
+
+                let result = contract.execute_swap(
+                    pool_key,
+                    x_to_y,
+                    *hop_amount_out,
+                    false,
+                    Self::route_sqrt_price_limit(x_to_y),
+                )?;
+
+                // the input of the first hop is what the caller pays
+                if i == 0 {
+                    next_swap_amount = result.amount_in;
+                }
+            }
+        }
+
+        Ok(next_swap_amount)
+    }
+
+    fn split_route<'a>(
+        mut route_type: RouteType<'a, TExecContext>,
+        amount_in: TokenAmount,
+        routes: Vec<(Percentage, Vec<SwapHop>)>,
+    ) -> Result<TokenAmount, InvariantError> {
+        let amounts_in = Self::split_amount(amount_in, &routes)?;
+        let mut amount_out = TokenAmount::new(U256::from(0));
+
+        for ((_, swaps), path_amount_in) in routes.into_iter().zip(amounts_in) {
+            if path_amount_in.is_zero() {
+                continue;
+            }
+
+            let path_route_type = match &mut route_type {
+                RouteType::Swap(contract) => RouteType::Swap(&mut **contract),
+                RouteType::Quote => RouteType::Quote,
+            };
+
+            amount_out += Self::route(path_route_type, path_amount_in, swaps)?;
+        }
+
+        Ok(amount_out)
+    }
+
+    // The last path takes the remainder, so the parts always add up to the whole amount
+    fn split_amount(
+        amount_in: TokenAmount,
+        routes: &[(Percentage, Vec<SwapHop>)],
+    ) -> Result<Vec<TokenAmount>, InvariantError> {
+        let total_weight = routes
+            .iter()
+            .try_fold(0u128, |total, (weight, _)| total.checked_add(weight.get()))
+            .ok_or(InvariantError::InvalidSplitRoute)?;
+
+        if routes.is_empty() || total_weight != Percentage::from_integer(1).get() {
+            return Err(InvariantError::InvalidSplitRoute);
+        }
+
+        let mut remaining_amount = amount_in;
+        let mut amounts = vec![];
+
+        for (weight, _) in routes.iter().take(routes.len() - 1) {
+            let amount = amount_in.big_mul(*weight);
+            remaining_amount -= amount;
+            amounts.push(amount);
+        }
+        amounts.push(remaining_amount);
+
+        Ok(amounts)
+    }
+
+    // Price limit past which the swap moves the price by more than the slippage, kept one unit
+    // away from the current price so that a zero slippage is still a valid limit
+    fn slippage_sqrt_price_limit(
+        sqrt_price: SqrtPrice,
+        slippage: Percentage,
+        x_to_y: bool,
+    ) -> Result<SqrtPrice, InvariantError> {
+        let sqrt_price_after_slippage =
+            calculate_sqrt_price_after_slippage(sqrt_price, slippage, !x_to_y)
+                .map_err(|_| InvariantError::WrongLimit)?;
+
+        let (min_sqrt_price, max_sqrt_price) = (
+            SqrtPrice::new(MIN_SQRT_PRICE.into()),
+            SqrtPrice::new(MAX_SQRT_PRICE.into()),
+        );
+
+        let sqrt_price_limit = if x_to_y {
+            if sqrt_price_after_slippage > min_sqrt_price + SqrtPrice::new(1) {
+                sqrt_price_after_slippage - SqrtPrice::new(1)
+            } else {
+                min_sqrt_price
+            }
+        } else if sqrt_price_after_slippage < max_sqrt_price - SqrtPrice::new(1) {
+            sqrt_price_after_slippage + SqrtPrice::new(1)
+        } else {
+            max_sqrt_price
+        };
+
+        Ok(sqrt_price_limit)
+    }
+
+    fn route_sqrt_price_limit(x_to_y: bool) -> SqrtPrice {
+        if x_to_y {
+            SqrtPrice::new(MIN_SQRT_PRICE.into())
+        } else {
+            SqrtPrice::new(MAX_SQRT_PRICE.into())
+        }
+    }
+
+    pub fn observe(
+        &self,
+        pool_key: PoolKey,
+        seconds_agos: Vec<u64>,
+    ) -> Result<Vec<(i64, SecondsPerLiquidity)>, InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+        let pool = invariant.pools.get(&pool_key)?;
+
+        invariant.oracles.get(&pool_key)?.observe(
+            exec::block_timestamp(),
+            &seconds_agos,
+            pool.current_tick_index,
+            pool.liquidity,
+        )
+    }
+
+    pub fn get_oracle(&self, pool_key: PoolKey) -> Result<Oracle, InvariantError> {
+        InvariantStorage::as_ref().oracles.get(&pool_key).cloned()
+    }
+
+    pub fn increase_observation_cardinality(
+        &mut self,
+        pool_key: PoolKey,
+        cardinality_next: u16,
+    ) -> u16 {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let is_admin = self.is_caller_admin(&invariant);
+            let fee_receiver = invariant.pools.get(&pool_key)?.fee_receiver;
+            let value = msg::value();
+
+            let oracle = invariant.oracles.get_mut(&pool_key)?;
+            let new_slots = cardinality_next.saturating_sub(oracle.cardinality_next);
+
+            let fee = if is_admin {
+                0
+            } else {
+                new_slots as u128 * ORACLE_SLOT_PRICE
+            };
+
+            if value < fee {
+                return Err(InvariantError::InsufficientOracleFee);
+            }
+
+            let cardinality_next = oracle.grow(cardinality_next)?;
+
+            // the fee goes to the pool's fee receiver and anything paid above it back to the caller
+            invariant.increase_token_balance(
+                &VARA_ADDRESS,
+                &fee_receiver,
+                TokenAmount(fee.into()),
+            )?;
+
+            let excess = value - fee;
+            if excess != 0 {
+                let reply = (
+                    "Service",
+                    "IncreaseObservationCardinality",
+                    cardinality_next,
+                );
+
+                // Reply has to be hardcoded since sails
+                // doesn't allow for specifying value in the reply yet
+                #[cfg(not(feature = "test"))]
+                {
+                    msg::reply(reply, excess).expect("Failed to send message");
+                    exec::leave()
+                }
+
+                #[cfg(feature = "test")]
+                msg::send(self.exec_context.actor_id(), reply, excess)
+                    .expect("Failed to send message");
+            }
+
+            Ok(cardinality_next)
+        })
+    }
+
+    pub fn claim_fee(&mut self, position_id: PositionId) -> (TokenAmount, TokenAmount) {
+        panicking!(move || {
+            self.execute_claim_fee(position_id)
+                .map(|(_, _, x, y)| (x, y))
+        })
+    }
+
+    // Operations are executed in order and all of them are synchronous. In atomic mode the first
+    // failure panics and reverts the whole batch. Otherwise the error is returned in the failed
+    // operation's place and the batch goes on, every operation is fully checked before its first
+    // write, so a failed one leaves the state untouched
+    pub fn batch(
+        &mut self,
+        ops: Vec<InvariantOp>,
+        atomic: bool,
+    ) -> Vec<Result<InvariantOpResult, InvariantError>> {
+        let mut results = Vec::with_capacity(ops.len());
+
+        for op in ops {
+            let result = self.execute_op(op);
+
+            if atomic {
+                if let Err(e) = result {
+                    panic(e);
+                }
+            }
+
+            results.push(result);
+        }
+
+        results
+    }
+
+    pub fn withdraw_protocol_fee(&mut self, pool_key: PoolKey) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let caller = self.exec_context.actor_id();
+
+            let mut pool = invariant.pools.get(&pool_key)?;
+
+            if pool.fee_receiver != caller {
+                return Err(InvariantError::NotFeeReceiver);
+            }
+
+            let (amount_x, amount_y) = pool.withdraw_protocol_fee(pool_key);
+            invariant.pools.update(&pool_key, &pool)?;
+
+            invariant.increase_token_balance(&pool_key.token_x, &caller, amount_x)?;
+            invariant.increase_token_balance(&pool_key.token_y, &caller, amount_y)?;
+
+            self.notify_on(InvariantEvent::ProtocolFeeWithdrawnEvent {
+                timestamp: exec::block_timestamp(),
+                address: caller,
+                pool_key,
+                amount_x,
+                amount_y,
+            })
+            .expect("Failed to notify on protocol fee withdrawn event");
+
+            Ok(())
+        })
+    }
+
+    // Lends tokens of the pool to the caller and calls it back with the payload. The loan together
+    // with the fee of the pool has to be back on the internal balance of the caller when it
+    // replies, the balance can't be used for anything else until then. State committed before the
+    // call can't be reverted by a panic, so an unpaid loan is taken back in full before replying
+    // with the error.
+    pub async fn flash(
+        &mut self,
+        pool_key: PoolKey,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+        receiver: ActorId,
+        payload: Vec<u8>,
+    ) -> (TokenAmount, TokenAmount) {
+        panicking_async!(|| async move {
+            let invariant = InvariantStorage::as_mut();
+
+            if self.exec_context.actor_id() != receiver {
+                return Err(InvariantError::NotFlashLoanReceiver);
+            }
+
+            let pool = invariant.pools.get(&pool_key)?;
+            invariant.check_pause(Some(pool_key), PausableAction::Swap)?;
+
+            // fee couldn't be distributed without liquidity
+            if pool.liquidity.is_zero() {
+                return Err(InvariantError::ZeroLiquidity);
+            }
+
+            invariant.check_flash_loan(&receiver)?;
+
+            let flash_loan = FlashLoan::create(pool_key, amount_x, amount_y)?;
+
+            let (reserve_x, reserve_y) = invariant.pool_reserves(&pool_key)?;
+            if amount_x > reserve_x || amount_y > reserve_y {
+                return Err(InvariantError::InsufficientPoolReserves);
+            }
+
+            if !invariant.can_increase_token_balance(&pool_key.token_x, &receiver, amount_x)
+                || !invariant.can_increase_token_balance(&pool_key.token_y, &receiver, amount_y)
+            {
+                return Err(InvariantError::FailedToChangeTokenBalance);
+            }
+
+            invariant.increase_token_balance(&pool_key.token_x, &receiver, amount_x)?;
+            invariant.increase_token_balance(&pool_key.token_y, &receiver, amount_y)?;
+            invariant.flash_loans.insert(receiver, flash_loan);
+
+            let message =
+                msg::send_bytes_for_reply(receiver, payload, 0, FLASH_LOAN_REPLY_HANDLING_COST);
+
+            let replied = match message {
+                Ok(message) => message.await.is_ok(),
+                Err(_) => false,
+            };
+
+            let invariant = InvariantStorage::as_mut();
+            let flash_loan = match invariant.settle_flash_loan(&receiver, replied) {
+                Ok(flash_loan) => flash_loan,
+                Err(e) => {
+                    reply_with_err_and_leave(e.clone());
+                    return Err(e);
+                }
+            };
+
+            self.notify_on(InvariantEvent::FlashLoanEvent {
+                timestamp: exec::block_timestamp(),
+                address: receiver,
+                pool_key,
+                amount_x,
+                amount_y,
+                fee_x: flash_loan.fee_x,
+                fee_y: flash_loan.fee_y,
+            })
+            .expect("Failed to notify on flash loan event");
+
+            Ok((flash_loan.fee_x, flash_loan.fee_y))
+        })
+    }
+
+    pub fn get_flash_loan(&self, receiver: ActorId) -> Result<FlashLoan, InvariantError> {
+        InvariantStorage::as_ref()
+            .flash_loans
+            .get(&receiver)
+            .copied()
+            .ok_or(InvariantError::FlashLoanNotFound)
+    }
+
+    pub fn get_user_balances(&self, user: ActorId) -> Vec<(ActorId, TokenAmount)> {
+        InvariantStorage::as_ref()
+            .balances
+            .get(&user)
+            .cloned()
+            .unwrap_or_default()
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .collect()
+    }
+
+    pub fn deposit_vara(&mut self) -> TokenAmount {
+        panicking!(|| {
+            let invariant = InvariantStorage::as_mut();
+            let value = TokenAmount(msg::value().into());
+
+            invariant.check_state_migration()?;
+
+            invariant.increase_token_balance(&VARA_ADDRESS, &msg::source(), value)?;
+
+            if !value.is_zero() {
+                self.notify_token_transfer(
+                    &VARA_ADDRESS,
+                    &msg::source(),
+                    value,
+                    TransferType::Deposit,
+                );
+            }
+
+            Ok(value)
+        })
+    }
+
+    pub fn withdraw_vara(&mut self, value: Option<TokenAmount>) -> TokenAmount {
+        panicking!(|| {
+            let invariant = InvariantStorage::as_mut();
+            let token = &VARA_ADDRESS;
+            let caller = &msg::source();
+
+            invariant.check_flash_loan(caller)?;
+            invariant.check_pause(None, PausableAction::Withdrawal)?;
+
+            let value = match value {
+                Some(value) => {
+                    if value.get() == 0.into() {
+                        return Err(InvariantError::AmountIsZero);
+                    }
+
+                    invariant.decrease_token_balance(&token, &caller, Some(value))?
+                }
+                None => invariant
+                    .decrease_token_balance(&token, &caller, None)
+                    .unwrap_or(TokenAmount::new(0.into())),
+            };
+
+            if !value.is_zero() {
+                self.notify_token_transfer(token, caller, value, TransferType::Withdrawal);
+            }
+
+            // Reply has to be hardcoded since sails
+            // doesn't allow for specifying value in the reply yet
+            #[cfg(not(feature = "test"))]
+            {
+                msg::reply(("Service", "WithdrawVara", value), value.0.as_u128())
+                    .expect("Failed to send message");
+                exec::leave()
+            }
+
+            #[cfg(feature = "test")]
+            {
+                msg::send(
+                    msg::source(),
+                    ("Service", "WithdrawVara", value),
+                    value.0.as_u128(),
+                )
+                .expect("Failed to send message");
+                Ok(value)
+            }
+        })
+    }
+
+    pub async fn deposit_single_token(
+        &mut self,
+        token: ActorId,
+        amount: TokenAmount,
+    ) -> TokenAmount {
+        panicking_async!(|| async move {
+            let invariant = InvariantStorage::as_mut();
+            let caller = &self.exec_context.actor_id();
+
+            invariant.check_state_migration()?;
+
+            if !invariant.can_increase_token_balance(&token, &caller, amount) {
+                return Err(InvariantError::FailedToChangeTokenBalance);
+            }
+
+            self.transfer_single_token(invariant, &token, &caller, amount, TransferType::Deposit)
+                .await?;
+
+            Ok(amount)
+        })
+    }
+
+    pub async fn withdraw_single_token(
+        &mut self,
+        token: ActorId,
+        amount: Option<TokenAmount>,
+    ) -> TokenAmount {
+        panicking_async!(|| async move {
+            let invariant = InvariantStorage::as_mut();
+            let caller = &self.exec_context.actor_id();
+
+            invariant.check_flash_loan(caller)?;
+            invariant.check_pause(None, PausableAction::Withdrawal)?;
+
+            let amount = match amount {
+                Some(amount) => {
+                    if amount.get() == 0.into() {
+                        return Err(InvariantError::AmountIsZero);
+                    }
+
+                    invariant.decrease_token_balance(&token, &caller, Some(amount))?
+                }
+                None => invariant
+                    .decrease_token_balance(&token, &caller, None)
+                    .unwrap_or(TokenAmount::new(0.into())),
+            };
+
+            if amount.get() != 0.into() {
+                self.transfer_single_token(
+                    invariant,
+                    &token,
+                    &caller,
+                    amount,
+                    TransferType::Withdrawal,
+                )
+                .await?;
+            }
+
+            Ok(amount)
+        })
+    }
+
+    pub async fn deposit_token_pair(
+        &mut self,
+        token_x: (ActorId, TokenAmount),
+        token_y: (ActorId, TokenAmount),
+    ) -> (TokenAmount, TokenAmount) {
+        panicking_async!(|| async move {
+            let invariant = InvariantStorage::as_mut();
+            let caller = &self.exec_context.actor_id();
+
+            invariant.check_state_migration()?;
+
+            if token_x.0.eq(&token_y.0) {
+                return Err(InvariantError::TokensAreSame);
+            }
+
+            let transfer_type = TransferType::Deposit;
+
+            if !invariant.can_increase_token_balance(&token_x.0, &caller, token_x.1)
+                || !invariant.can_increase_token_balance(&token_y.0, &caller, token_y.1)
+            {
+                return Err(InvariantError::FailedToChangeTokenBalance);
+            }
+
+            self.transfer_tokens(invariant, &caller, &token_x, &token_y, transfer_type)
+                .await?;
+
+            Ok((token_x.1, token_y.1))
+        })
+    }
+
+    pub async fn withdraw_token_pair(
+        &mut self,
+        token_x: (ActorId, Option<TokenAmount>),
+        token_y: (ActorId, Option<TokenAmount>),
+    ) -> (TokenAmount, TokenAmount) {
+        panicking_async!(|| async move {
+            let invariant = InvariantStorage::as_mut();
+            let caller = &self.exec_context.actor_id();
+
+            if token_x.0.eq(&token_y.0) {
+                return Err(InvariantError::TokensAreSame);
+            }
+
+            invariant.check_flash_loan(caller)?;
+            invariant.check_pause(None, PausableAction::Withdrawal)?;
+
+            let transfer_type = TransferType::Withdrawal;
+            let amount_x = invariant.decrease_token_balance(&token_x.0, &caller, token_x.1);
+            let amount_y = invariant.decrease_token_balance(&token_y.0, &caller, token_y.1);
+
+            let amount_x = if let Err(e) = amount_x {
+                if e == InvariantError::NoBalanceForTheToken && token_x.1.is_none() {
+                    TokenAmount::new(U256::from(0))
+                } else {
+                    return Err(e);
+                }
+            } else {
+                amount_x?
+            };
+
+            let amount_y = if let Err(e) = amount_y {
+                if e == InvariantError::NoBalanceForTheToken && token_y.1.is_none() {
+                    TokenAmount::new(U256::from(0))
+                } else {
+                    return Err(e);
+                }
+            } else {
+                amount_y?
+            };
+
+            self.transfer_tokens(
+                invariant,
+                &caller,
+                &(token_x.0, amount_x),
+                &(token_y.0, amount_y),
+                transfer_type,
+            )
+            .await?;
+
+            Ok((amount_x, amount_y))
+        })
+    }
+
+    // Tokens deposited for the position stay in the internal balance of the caller
+    // when the position can't be created, the unused part is withdrawn otherwise
+    pub async fn deposit_and_create_position(
+        &mut self,
+        pool_key: PoolKey,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+        amount_x: TokenAmount,
+        amount_y: TokenAmount,
+        deadline: Option<u64>,
+    ) -> (Position, TokenAmount, TokenAmount) {
+        panicking_async!(|| async move {
+            Self::check_deadline(deadline)?;
+
+            let invariant = InvariantStorage::as_mut();
+            let caller = &self.exec_context.actor_id();
+
+            invariant.check_flash_loan(caller)?;
+            invariant.check_pause(Some(pool_key), PausableAction::NewLiquidity)?;
+
+            if !invariant.can_increase_token_balance(&pool_key.token_x, caller, amount_x)
+                || !invariant.can_increase_token_balance(&pool_key.token_y, caller, amount_y)
+            {
+                return Err(InvariantError::FailedToChangeTokenBalance);
+            }
+
+            let token_x = (pool_key.token_x, amount_x);
+            let token_y = (pool_key.token_y, amount_y);
+
+            self.transfer_tokens(invariant, caller, &token_x, &token_y, TransferType::Deposit)
+                .await?;
+
+            let (_, position, x, y) = self.add_position(
+                pool_key,
+                lower_tick,
+                upper_tick,
+                liquidity_delta,
+                slippage_limit_lower,
+                slippage_limit_upper,
+            )?;
+
+            // the position could also be paid from tokens deposited before
+            let unused_x = amount_x.checked_sub(x).unwrap_or_default();
+            let unused_y = amount_y.checked_sub(y).unwrap_or_default();
+
+            self.withdraw_amounts(
+                invariant,
+                caller,
+                (pool_key.token_x, unused_x),
+                (pool_key.token_y, unused_y),
+            )
+            .await?;
+
+            Ok((position, unused_x, unused_y))
+        })
+    }
+
+    // The position is removed even if the withdrawal fails, in that case the tokens
+    // are returned to the internal balance of the owner
+    pub async fn remove_position_and_withdraw(
+        &mut self,
+        position_id: PositionId,
+        deadline: Option<u64>,
+    ) -> (TokenAmount, TokenAmount) {
+        panicking_async!(|| async move {
+            Self::check_deadline(deadline)?;
+
+            let (owner_id, pool_key, amount_x, amount_y) =
+                self.execute_remove_position(position_id)?;
+
+            self.withdraw_amounts(
+                InvariantStorage::as_mut(),
+                &owner_id,
+                (pool_key.token_x, amount_x),
+                (pool_key.token_y, amount_y),
+            )
+            .await?;
+
+            Ok((amount_x, amount_y))
+        })
+    }
+
+    pub async fn claim_fee_and_withdraw(
+        &mut self,
+        position_id: PositionId,
+    ) -> (TokenAmount, TokenAmount) {
+        panicking_async!(|| async move {
+            let (owner_id, pool_key, x, y) = self.execute_claim_fee(position_id)?;
+
+            self.withdraw_amounts(
+                InvariantStorage::as_mut(),
+                &owner_id,
+                (pool_key.token_x, x),
+                (pool_key.token_y, y),
+            )
+            .await?;
+
+            Ok((x, y))
+        })
+    }
+
+    fn execute_swap(
+        &mut self,
+        pool_key: PoolKey,
+        x_to_y: bool,
+        amount: TokenAmount,
+        by_amount_in: bool,
+        sqrt_price_limit: SqrtPrice,
+    ) -> Result<CalculateSwapResult, InvariantError> {
+        let invariant = InvariantStorage::as_mut();
+
+        let caller = self.exec_context.actor_id();
+
+        invariant.check_flash_loan(&caller)?;
+        invariant.check_pause(Some(pool_key), PausableAction::Swap)?;
+
+        let calculate_swap_result =
+            invariant.calculate_swap(pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit)?;
+
+        let (swapped_token, returned_token) = if x_to_y {
+            (&pool_key.token_x, &pool_key.token_y)
+        } else {
+            (&pool_key.token_y, &pool_key.token_x)
+        };
+
+        if !invariant.can_increase_token_balance(
+            returned_token,
+            &caller,
+            calculate_swap_result.amount_out,
+        ) {
+            return Err(InvariantError::FailedToChangeTokenBalance);
+        }
+
+        // balance is the first write, a swap the caller can't pay for fails without touching
+        // the state
+        invariant.decrease_token_balance(
+            &swapped_token,
+            &caller,
+            calculate_swap_result.amount_in.into(),
+        )?;
+
+        let pool_before = invariant.pools.get(&pool_key)?;
+        invariant.write_observation(pool_key, &pool_before)?;
+
+        let mut crossed_tick_indexes: Vec<i32> = vec![];
+        let pool_id = invariant.pool_id(&pool_key)?;
+
+        for tick in calculate_swap_result.ticks.iter() {
+            crossed_tick_indexes.push(tick.index);
+            invariant.ticks.update(pool_id, tick.index, *tick)?;
+        }
+
+        invariant
+            .pools
+            .update(&pool_key, &calculate_swap_result.pool)?;
+
+        let settled_limit_orders =
+            invariant.settle_limit_orders(pool_key, &crossed_tick_indexes, x_to_y)?;
+
+        invariant.increase_token_balance(
+            &returned_token,
+            &caller,
+            calculate_swap_result.amount_out.into(),
+        )?;
+
+        if !crossed_tick_indexes.is_empty() {
+            self.notify_on(InvariantEvent::CrossTickEvent {
+                timestamp: exec::block_timestamp(),
+                address: caller,
+                pool_key,
+                indexes: crossed_tick_indexes,
+            })
+            .expect("Failed to notify on cross tick event");
+        }
+
+        self.notify_on(InvariantEvent::SwapEvent {
+            timestamp: exec::block_timestamp(),
+            address: caller,
+            pool_key,
+            amount_in: calculate_swap_result.amount_in,
+            amount_out: calculate_swap_result.amount_out,
+            fee: calculate_swap_result.fee,
+            start_sqrt_price: calculate_swap_result.start_sqrt_price,
+            target_sqrt_price: calculate_swap_result.target_sqrt_price,
+            x_to_y,
+        })
+        .expect("Failed to notify on swap event");
+
+        for (position_id, amount_x, amount_y) in settled_limit_orders {
+            self.notify_on(InvariantEvent::LimitOrderSettledEvent {
+                timestamp: exec::block_timestamp(),
+                pool_key,
+                position_id,
+                amount_x,
+                amount_y,
+            })
+            .expect("Failed to notify on limit order settled event");
+        }
+
+        Ok(calculate_swap_result)
+    }
+
+    fn execute_op(&mut self, op: InvariantOp) -> Result<InvariantOpResult, InvariantError> {
+        match op {
+            InvariantOp::CreatePosition {
+                pool_key,
+                lower_tick,
+                upper_tick,
+                liquidity_delta,
+                slippage_limit_lower,
+                slippage_limit_upper,
+            } => self
+                .add_position(
+                    pool_key,
+                    lower_tick,
+                    upper_tick,
+                    liquidity_delta,
+                    slippage_limit_lower,
+                    slippage_limit_upper,
+                )
+                .map(|(_, position, _, _)| InvariantOpResult::CreatePosition(position)),
+            InvariantOp::RemovePosition { position_id } => self
+                .execute_remove_position(position_id)
+                .map(|(_, _, x, y)| InvariantOpResult::RemovePosition(x, y)),
+            InvariantOp::ClaimFee { position_id } => self
+                .execute_claim_fee(position_id)
+                .map(|(_, _, x, y)| InvariantOpResult::ClaimFee(x, y)),
+            InvariantOp::Swap {
+                pool_key,
+                x_to_y,
+                amount,
+                by_amount_in,
+                sqrt_price_limit,
+            } => self
+                .execute_swap(pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit)
+                .map(InvariantOpResult::Swap),
+            InvariantOp::TransferPosition {
+                position_id,
+                receiver,
+            } => self
+                .execute_transfer_position(position_id, receiver)
+                .map(|_| InvariantOpResult::TransferPosition),
+        }
+    }
+
+    fn execute_remove_position(
+        &mut self,
+        position_id: PositionId,
+    ) -> Result<(ActorId, PoolKey, TokenAmount, TokenAmount), InvariantError> {
+        let invariant = InvariantStorage::as_mut();
+
+        let caller = self.exec_context.actor_id();
+        let current_timestamp = exec::block_timestamp();
+
+        let (owner_id, position) = invariant.positions.get_authorized(&caller, position_id)?;
+        let mut position = *position;
+
+        invariant.check_pause(Some(position.pool_key), PausableAction::Withdrawal)?;
+        let Position {
+            pool_key,
+            lower_tick_index,
+            upper_tick_index,
+            liquidity: withdrawn_liquidity,
+            ..
+        } = position;
+
+        if invariant.stakes.contains(position_id) {
+            return Err(InvariantError::PositionStaked);
+        }
+
+        let pool = &mut invariant.pools.get(&pool_key)?;
+        let pool_before = pool.clone();
+        let pool_id = invariant.pool_id(&pool_key)?;
+
+        // settled limit orders are no longer a part of the pool, the others are removed on copies
+        // and written only once the removal can't fail
+        let (amount_x, amount_y, ticks) = if invariant.limit_orders.is_settled(position_id) {
+            (position.tokens_owed_x, position.tokens_owed_y, None)
+        } else {
+            let mut lower_tick = invariant.ticks.get(pool_id, lower_tick_index).cloned()?;
+
+            let mut upper_tick = invariant.ticks.get(pool_id, upper_tick_index).cloned()?;
+
+            let (amount_x, amount_y, remove_lower_tick, remove_upper_tick) = position.remove(
+                pool,
+                current_timestamp,
+                &mut lower_tick,
+                &mut upper_tick,
+                pool_key.fee_tier.tick_spacing,
+            );
+
+            (
+                amount_x,
+                amount_y,
+                Some([
+                    (lower_tick, remove_lower_tick),
+                    (upper_tick, remove_upper_tick),
+                ]),
+            )
+        };
+
+        if !invariant.can_increase_token_balance(&pool_key.token_x, &owner_id, amount_x)
+            || !invariant.can_increase_token_balance(&pool_key.token_y, &owner_id, amount_y)
+        {
+            return Err(InvariantError::FailedToChangeTokenBalance);
+        }
+
+        if let Some(ticks) = ticks {
+            invariant.write_observation(pool_key, &pool_before)?;
+            invariant.pools.update(&pool_key, pool)?;
+
+            for (tick, remove_tick) in ticks {
+                if remove_tick {
+                    invariant.remove_tick(pool_key, tick)?;
+                } else {
+                    invariant.ticks.update(pool_id, tick.index, tick)?;
+                }
+            }
+        }
+
+        invariant.remove_position(&caller, position_id)?;
+
+        let token_x = pool_key.token_x;
+        let token_y = pool_key.token_y;
+
+        invariant.increase_token_balance(&token_x, &owner_id, amount_x)?;
+        invariant.increase_token_balance(&token_y, &owner_id, amount_y)?;
+
+        self.notify_on(InvariantEvent::PositionRemovedEvent {
+            timestamp: exec::block_timestamp(),
+            address: owner_id,
+            position_id,
+            pool_key,
+            liquidity: withdrawn_liquidity,
+            lower_tick_index,
+            upper_tick_index,
+            sqrt_price: pool.sqrt_price,
+        })
+        .expect("Failed to notify on position removed event");
+
+        Ok((owner_id, pool_key, amount_x, amount_y))
+    }
+
+    fn execute_claim_fee(
+        &mut self,
+        position_id: PositionId,
+    ) -> Result<(ActorId, PoolKey, TokenAmount, TokenAmount), InvariantError> {
+        let invariant = InvariantStorage::as_mut();
+
+        let caller = self.exec_context.actor_id();
+        let current_timestamp = exec::block_timestamp();
+
+        let (owner_id, position) = invariant.positions.get_authorized(&caller, position_id)?;
+        let mut position = *position;
+
+        invariant.check_pause(Some(position.pool_key), PausableAction::Withdrawal)?;
+
+        // proceeds of settled limit orders are only waiting to be claimed, the others are claimed
+        // on copies and written only once the claim can't fail
+        let (x, y, pool_and_ticks) = if invariant.limit_orders.is_settled(position_id) {
+            let amounts = (position.tokens_owed_x, position.tokens_owed_y);
+            position.tokens_owed_x = TokenAmount::new(U256::from(0));
+            position.tokens_owed_y = TokenAmount::new(U256::from(0));
+
+            (amounts.0, amounts.1, None)
+        } else {
+            let pool_id = invariant.pool_id(&position.pool_key)?;
+
+            let mut lower_tick = invariant
+                .ticks
+                .get(pool_id, position.lower_tick_index)
+                .cloned()?;
+
+            let mut upper_tick = invariant
+                .ticks
+                .get(pool_id, position.upper_tick_index)
+                .cloned()?;
+
+            let mut pool = invariant.pools.get(&position.pool_key)?;
+
+            let (x, y) = position.claim_fee(
+                &mut pool,
+                &mut upper_tick,
+                &mut lower_tick,
+                current_timestamp,
+            );
 
-    pub fn withdraw_vara(&mut self, value: Option<TokenAmount>) -> TokenAmount {
-        panicking!(|| {
-            let invariant = InvariantStorage::as_mut();
-            let token = &VARA_ADDRESS;
-            let caller = &msg::source();
+            (x, y, Some((pool_id, pool, [upper_tick, lower_tick])))
+        };
 
-            let value = match value {
-                Some(value) => {
-                    if value.get() == 0.into() {
-                        return Err(InvariantError::AmountIsZero);
-                    }
+        if !invariant.can_increase_token_balance(&position.pool_key.token_x, &owner_id, x)
+            || !invariant.can_increase_token_balance(&position.pool_key.token_y, &owner_id, y)
+        {
+            return Err(InvariantError::FailedToChangeTokenBalance);
+        }
 
-                    invariant.decrease_token_balance(&token, &caller, Some(value))?
-                }
-                None => invariant
-                    .decrease_token_balance(&token, &caller, None)
-                    .unwrap_or(TokenAmount::new(0.into())),
-            };
+        invariant.positions.update(position_id, &position)?;
 
-            // Reply has to be hardcoded since sails
-            // doesn't allow for specifying value in the reply yet
-            #[cfg(not(feature = "test"))]
-            {
-                msg::reply(("Service", "WithdrawVara", value), value.0.as_u128())
-                    .expect("Failed to send message");
-                exec::leave()
-            }
+        if let Some((pool_id, pool, ticks)) = pool_and_ticks {
+            invariant.pools.update(&position.pool_key, &pool)?;
 
-            #[cfg(feature = "test")]
-            {
-                msg::send(
-                    msg::source(),
-                    ("Service", "WithdrawVara", value),
-                    value.0.as_u128(),
-                )
-                .expect("Failed to send message");
-                Ok(value)
+            for tick in ticks {
+                invariant.ticks.update(pool_id, tick.index, tick)?;
             }
+        }
+
+        invariant.increase_token_balance(&position.pool_key.token_x, &owner_id, x)?;
+        invariant.increase_token_balance(&position.pool_key.token_y, &owner_id, y)?;
+
+        self.notify_on(InvariantEvent::FeeClaimedEvent {
+            timestamp: current_timestamp,
+            address: owner_id,
+            position_id,
+            pool_key: position.pool_key,
+            amount_x: x,
+            amount_y: y,
         })
+        .expect("Failed to notify on fee claimed event");
+
+        Ok((owner_id, position.pool_key, x, y))
     }
 
-    pub async fn deposit_single_token(
+    fn execute_transfer_position(
         &mut self,
-        token: ActorId,
-        amount: TokenAmount,
-    ) -> TokenAmount {
-        panicking_async!(|| async move {
-            let invariant = InvariantStorage::as_mut();
-            let caller = &self.exec_context.actor_id();
-
-            if !invariant.can_increase_token_balance(&token, &caller, amount) {
-                return Err(InvariantError::FailedToChangeTokenBalance);
-            }
+        position_id: PositionId,
+        receiver: ActorId,
+    ) -> Result<(), InvariantError> {
+        let invariant = InvariantStorage::as_mut();
 
-            self.transfer_single_token(invariant, &token, &caller, amount, TransferType::Deposit)
-                .await?;
+        let from =
+            invariant.transfer_position(&self.exec_context.actor_id(), position_id, &receiver)?;
 
-            Ok(amount)
+        self.notify_on(InvariantEvent::PositionTransferredEvent {
+            timestamp: exec::block_timestamp(),
+            position_id,
+            from,
+            to: receiver,
         })
+        .expect("Failed to notify on position transferred event");
+
+        Ok(())
     }
 
-    pub async fn withdraw_single_token(
+    fn add_position(
         &mut self,
-        token: ActorId,
-        amount: Option<TokenAmount>,
-    ) -> TokenAmount {
-        panicking_async!(|| async move {
-            let invariant = InvariantStorage::as_mut();
-            let caller = &self.exec_context.actor_id();
+        pool_key: PoolKey,
+        lower_tick: i32,
+        upper_tick: i32,
+        liquidity_delta: Liquidity,
+        slippage_limit_lower: SqrtPrice,
+        slippage_limit_upper: SqrtPrice,
+    ) -> Result<(PositionId, Position, TokenAmount, TokenAmount), InvariantError> {
+        let invariant = InvariantStorage::as_mut();
 
-            let amount = match amount {
-                Some(amount) => {
-                    if amount.get() == 0.into() {
-                        return Err(InvariantError::AmountIsZero);
-                    }
+        let caller = self.exec_context.actor_id();
+        let current_timestamp = exec::block_timestamp();
+        let current_block_number = exec::block_height() as u64;
 
-                    invariant.decrease_token_balance(&token, &caller, Some(amount))?
-                }
-                None => invariant
-                    .decrease_token_balance(&token, &caller, None)
-                    .unwrap_or(TokenAmount::new(0.into())),
-            };
+        invariant.check_flash_loan(&caller)?;
+        invariant.check_pause(Some(pool_key), PausableAction::NewLiquidity)?;
 
-            if amount.get() != 0.into() {
-                self.transfer_single_token(
-                    invariant,
-                    &token,
-                    &caller,
-                    amount,
-                    TransferType::Withdrawal,
-                )
-                .await?;
-            }
+        // liquidity delta = 0 => return
+        if liquidity_delta == Liquidity::new(U256::from(0)) {
+            return Err(InvariantError::ZeroLiquidity);
+        }
 
-            Ok(amount)
-        })
-    }
+        if lower_tick == upper_tick {
+            return Err(InvariantError::InvalidTickIndex);
+        }
 
-    pub async fn deposit_token_pair(
-        &mut self,
-        token_x: (ActorId, TokenAmount),
-        token_y: (ActorId, TokenAmount),
-    ) -> (TokenAmount, TokenAmount) {
-        panicking_async!(|| async move {
-            let invariant = InvariantStorage::as_mut();
-            let caller = &self.exec_context.actor_id();
+        let mut pool = invariant.pools.get(&pool_key)?;
+        let pool_before = pool.clone();
+        let pool_id = invariant.pool_id(&pool_key)?;
+
+        let (mut lower_tick, should_add_lower) = invariant.get_or_create_tick(pool_key, lower_tick);
+        let (mut upper_tick, should_add_upper) = invariant.get_or_create_tick(pool_key, upper_tick);
+
+        let (position, x, y) = Position::create(
+            &mut pool,
+            pool_key,
+            &mut lower_tick,
+            &mut upper_tick,
+            current_timestamp,
+            liquidity_delta,
+            slippage_limit_lower,
+            slippage_limit_upper,
+            current_block_number,
+            pool_key.fee_tier.tick_spacing,
+        )?;
 
-            if token_x.0.eq(&token_y.0) {
-                return Err(InvariantError::TokensAreSame);
-            }
+        invariant.decrease_token_balance(&pool_key.token_x, &caller, x.into())?;
+        if let Err(e) = invariant.decrease_token_balance(&pool_key.token_y, &caller, y.into()) {
+            invariant.increase_token_balance(&pool_key.token_x, &caller, x)?;
+            return Err(e);
+        }
 
-            let transfer_type = TransferType::Deposit;
+        invariant.write_observation(pool_key, &pool_before)?;
+        invariant.pools.update(&pool_key, &pool)?;
 
-            if !invariant.can_increase_token_balance(&token_x.0, &caller, token_x.1)
-                || !invariant.can_increase_token_balance(&token_y.0, &caller, token_y.1)
-            {
-                return Err(InvariantError::FailedToChangeTokenBalance);
-            }
+        let position_id = invariant.positions.add(&caller, &position);
 
-            if !token_x.1.is_zero() && !token_y.1.is_zero() {
-                self.transfer_token_pair(invariant, &caller, &token_x, &token_y, transfer_type)
-                    .await?;
-            } else if !token_x.1.is_zero() {
-                self.transfer_single_token(
-                    invariant,
-                    &token_x.0,
-                    &caller,
-                    token_x.1,
-                    transfer_type,
-                )
-                .await?;
-            } else if !token_y.1.is_zero() {
-                self.transfer_single_token(
-                    invariant,
-                    &token_y.0,
-                    &caller,
-                    token_y.1,
-                    transfer_type,
-                )
-                .await?;
-            }
+        if should_add_lower {
+            invariant.add_tick(pool_key, lower_tick)?;
+        } else {
+            invariant
+                .ticks
+                .update(pool_id, lower_tick.index, lower_tick)?;
+        }
 
-            Ok((token_x.1, token_y.1))
+        if should_add_upper {
+            invariant.add_tick(pool_key, upper_tick)?;
+        } else {
+            invariant
+                .ticks
+                .update(pool_id, upper_tick.index, upper_tick)?;
+        }
+
+        self.notify_on(InvariantEvent::PositionCreatedEvent {
+            timestamp: exec::block_timestamp(),
+            address: self.exec_context.actor_id(),
+            position_id,
+            pool_key,
+            liquidity_delta,
+            lower_tick: lower_tick.index,
+            upper_tick: upper_tick.index,
+            current_sqrt_price: pool.sqrt_price,
         })
+        .expect("Failed to notify on position created event");
+
+        Ok((position_id, position, x, y))
     }
 
-    pub async fn withdraw_token_pair(
+    // Sends the swapped tokens from the internal balance of the caller back to them
+    async fn send_output(
         &mut self,
-        token_x: (ActorId, Option<TokenAmount>),
-        token_y: (ActorId, Option<TokenAmount>),
-    ) -> (TokenAmount, TokenAmount) {
-        panicking_async!(|| async move {
-            let invariant = InvariantStorage::as_mut();
-            let caller = &self.exec_context.actor_id();
+        token: ActorId,
+        amount: TokenAmount,
+    ) -> Result<(), InvariantError> {
+        let invariant = InvariantStorage::as_mut();
+        let caller = self.exec_context.actor_id();
 
-            if token_x.0.eq(&token_y.0) {
-                return Err(InvariantError::TokensAreSame);
-            }
+        invariant.check_flash_loan(&caller)?;
+        invariant.check_pause(None, PausableAction::Withdrawal)?;
 
-            let transfer_type = TransferType::Withdrawal;
-            let amount_x = invariant.decrease_token_balance(&token_x.0, &caller, token_x.1);
-            let amount_y = invariant.decrease_token_balance(&token_y.0, &caller, token_y.1);
+        if amount.is_zero() {
+            return Ok(());
+        }
 
-            let amount_x = if let Err(e) = amount_x {
-                if e == InvariantError::NoBalanceForTheToken && token_x.1.is_none() {
-                    TokenAmount::new(U256::from(0))
-                } else {
-                    return Err(e);
-                }
-            } else {
-                amount_x?
-            };
+        invariant.decrease_token_balance(&token, &caller, Some(amount))?;
 
-            let amount_y = if let Err(e) = amount_y {
-                if e == InvariantError::NoBalanceForTheToken && token_y.1.is_none() {
-                    TokenAmount::new(U256::from(0))
-                } else {
-                    return Err(e);
-                }
-            } else {
-                amount_y?
-            };
+        if token == VARA_ADDRESS {
+            msg::send(
+                caller,
+                ("Service", "WithdrawVara", amount),
+                amount.0.as_u128(),
+            )
+            .map_err(|_| InvariantError::TransferError)?;
 
-            if !amount_x.is_zero() && !amount_y.is_zero() {
-                self.transfer_token_pair(
-                    invariant,
-                    &caller,
-                    &(token_x.0, amount_x),
-                    &(token_y.0, amount_y),
-                    transfer_type,
-                )
-                .await?;
-            } else if !amount_x.is_zero() {
-                self.transfer_single_token(invariant, &token_x.0, &caller, amount_x, transfer_type)
-                    .await?;
-            } else if !amount_y.is_zero() {
-                self.transfer_single_token(invariant, &token_y.0, &caller, amount_y, transfer_type)
-                    .await?;
-            }
+            self.notify_token_transfer(&token, &caller, amount, TransferType::Withdrawal);
+        } else {
+            self.transfer_single_token(
+                invariant,
+                &token,
+                &caller,
+                amount,
+                TransferType::Withdrawal,
+            )
+            .await?;
+        }
 
-            Ok((amount_x, amount_y))
-        })
+        Ok(())
+    }
+
+    fn check_deadline(deadline: Option<u64>) -> Result<(), InvariantError> {
+        match deadline {
+            Some(deadline) if exec::block_timestamp() > deadline => {
+                Err(InvariantError::DeadlineExceeded)
+            }
+            _ => Ok(()),
+        }
     }
 
     fn is_caller_admin(&self, invariant_storage: &Invariant) -> bool {
         invariant_storage.config.admin == self.exec_context.actor_id()
     }
 
+    fn is_caller_authorized(&self, invariant_storage: &Invariant, role: Role) -> bool {
+        self.is_caller_admin(invariant_storage)
+            || invariant_storage
+                .roles
+                .has(&self.exec_context.actor_id(), role)
+    }
+
     async fn transfer_single_token(
-        &self,
+        &mut self,
         invariant: &mut Invariant,
         token: &ActorId,
         caller: &ActorId,
@@ -973,11 +2607,90 @@ where
             reply_with_err_and_leave(InvariantError::ReplyHandlingFailed);
         }
 
+        if transfer_check.is_ok() {
+            self.notify_token_transfer(token, caller, amount, transfer_type);
+        }
+
         transfer_check
     }
 
+    fn notify_token_transfer(
+        &mut self,
+        token: &ActorId,
+        account: &ActorId,
+        amount: TokenAmount,
+        transfer_type: TransferType,
+    ) {
+        let timestamp = exec::block_timestamp();
+        let (address, token) = (*account, *token);
+
+        let event = match transfer_type {
+            TransferType::Deposit => InvariantEvent::TokenDepositedEvent {
+                timestamp,
+                address,
+                token,
+                amount,
+            },
+            TransferType::Withdrawal => InvariantEvent::TokenWithdrawnEvent {
+                timestamp,
+                address,
+                token,
+                amount,
+            },
+        };
+
+        self.notify_on(event)
+            .expect("Failed to notify on token transfer event");
+    }
+
+    // Transfers both tokens at once or only the ones with a non-zero amount
+    async fn transfer_tokens(
+        &mut self,
+        invariant: &mut Invariant,
+        caller: &ActorId,
+        token_x: &(ActorId, TokenAmount),
+        token_y: &(ActorId, TokenAmount),
+        transfer_type: TransferType,
+    ) -> Result<(), InvariantError> {
+        if !token_x.1.is_zero() && !token_y.1.is_zero() {
+            self.transfer_token_pair(invariant, caller, token_x, token_y, transfer_type)
+                .await
+        } else if !token_x.1.is_zero() {
+            self.transfer_single_token(invariant, &token_x.0, caller, token_x.1, transfer_type)
+                .await
+        } else if !token_y.1.is_zero() {
+            self.transfer_single_token(invariant, &token_y.0, caller, token_y.1, transfer_type)
+                .await
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn withdraw_amounts(
+        &mut self,
+        invariant: &mut Invariant,
+        account: &ActorId,
+        token_x: (ActorId, TokenAmount),
+        token_y: (ActorId, TokenAmount),
+    ) -> Result<(), InvariantError> {
+        invariant.check_flash_loan(account)?;
+        invariant.check_pause(None, PausableAction::Withdrawal)?;
+
+        invariant.decrease_token_balance(&token_x.0, account, Some(token_x.1))?;
+        invariant.decrease_token_balance(&token_y.0, account, Some(token_y.1))?;
+
+        self.transfer_tokens(
+            invariant,
+            account,
+            &token_x,
+            &token_y,
+            TransferType::Withdrawal,
+        )
+        .await
+    }
+
     async fn transfer_token_pair(
-        &self,
+        &mut self,
         invariant: &mut Invariant,
         caller: &ActorId,
         token_x: &(ActorId, TokenAmount),
@@ -1046,6 +2759,14 @@ where
             reply_with_err_and_leave(InvariantError::ReplyHandlingFailed);
         }
 
+        // a recoverable error still leaves the other transfer completed
+        if token_x_check.is_ok() {
+            self.notify_token_transfer(&token_x.0, caller, token_x.1, transfer_type);
+        }
+        if token_y_check.is_ok() {
+            self.notify_token_transfer(&token_y.0, caller, token_y.1, transfer_type);
+        }
+
         match transfer_type {
             TransferType::Deposit => match (token_x_check, token_y_check) {
                 (Err(_), Ok(_)) | (Ok(_), Err(_)) => Err(InvariantError::RecoverableTransferError),
@@ -1060,12 +2781,7 @@ where
     }
 
     pub fn get_tickmap(&self, pool_key: PoolKey) -> Vec<(u16, u64)> {
-        let tick_spacing = pool_key.fee_tier.tick_spacing;
-
-        let max_chunk_index = get_max_chunk(tick_spacing);
-        let min_chunk_index = 0;
-
-        InvariantStorage::as_ref().tickmap_slice(min_chunk_index..=max_chunk_index, pool_key)
+        InvariantStorage::as_ref().get_tickmap(pool_key)
     }
 
     pub fn get_liquidity_ticks(
@@ -1078,9 +2794,11 @@ where
         }
 
         let invariant = InvariantStorage::as_ref();
+        let pool_id = invariant.pool_id(&pool_key)?;
+
         Ok(tickmap
             .iter()
-            .map(|tick| invariant.ticks.get(pool_key, *tick).unwrap())
+            .map(|tick| invariant.ticks.get(pool_id, *tick).unwrap())
             .map(|tick| LiquidityTick::from(tick))
             .collect::<Vec<LiquidityTick>>())
     }
@@ -1093,11 +2811,13 @@ where
         for i in offset..positions_length {
             invariant
                 .positions
-                .get(&owner, i)
-                .map(|position| {
+                .get_by_index(&owner, i)
+                .and_then(|position| {
+                    let pool_id = invariant.pool_id(&position.pool_key)?;
+
                     invariant
                         .ticks
-                        .get(position.pool_key, position.lower_tick_index)
+                        .get(pool_id, position.lower_tick_index)
                         .map(|tick| {
                             ticks.push(PositionTick::from(tick));
                         })
@@ -1105,11 +2825,13 @@ where
 
                     invariant
                         .ticks
-                        .get(position.pool_key, position.upper_tick_index)
+                        .get(pool_id, position.upper_tick_index)
                         .map(|tick| {
                             ticks.push(PositionTick::from(tick));
                         })
                         .ok();
+
+                    Ok(())
                 })
                 .ok();
 
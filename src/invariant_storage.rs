@@ -1,16 +1,19 @@
-use contracts::{declare_storage, get_max_chunk};
+use contracts::declare_storage;
 pub use contracts::{
-    AwaitingTransfer, FeeTiers, InvariantError, PoolKey, PoolKeys, Pools, Positions, Tick, Tickmap,
-    Ticks, UpdatePoolTick,
+    simulate_swap, AwaitingTransfer, Campaigns, FeeTiers, FlashLoan, InvariantError, LimitOrders,
+    Oracle, Oracles, PausableAction, Pool, PoolId, PoolKey, PoolKeys, PoolStatus, Pools, Position,
+    PositionId, Positions, Roles, Stakes, StateChecksum, StateChunk, StateCursor, StateSection,
+    Tick, Tickmap, Ticks, STATE_CHUNK_SIZE,
 };
 pub use decimal::*;
 pub use gstd::exec;
 pub use io::*;
 pub use math::{
-    check_tick, compute_swap_step,
-    sqrt_price::{get_max_tick, get_min_tick, SqrtPrice},
+    calculate_amount_delta, check_tick,
+    liquidity::Liquidity,
+    seconds_per_liquidity::{calculate_seconds_per_liquidity_inside, SecondsPerLiquidity},
+    sqrt_price::SqrtPrice,
     token_amount::TokenAmount,
-    MAX_SQRT_PRICE, MIN_SQRT_PRICE,
 };
 pub use sails_rs::{collections::HashMap, prelude::*};
 pub use traceable_result::*;
@@ -18,14 +21,25 @@ pub use traceable_result::*;
 #[derive(Debug, Default)]
 pub struct Invariant {
     pub config: InvariantConfig,
+    pub pending_admin: Option<ActorId>,
+    pub roles: Roles,
     pub fee_tiers: FeeTiers,
     pub pools: Pools,
     pub pool_keys: PoolKeys,
+    pub oracles: Oracles,
     pub positions: Positions,
     pub ticks: Ticks,
     pub tickmap: Tickmap,
     pub balances: HashMap<ActorId, HashMap<ActorId, TokenAmount>>,
+    pub campaigns: Campaigns,
+    pub stakes: Stakes,
+    pub limit_orders: LimitOrders,
+    pub flash_loans: HashMap<ActorId, FlashLoan>,
     pub awaiting_transfers: HashMap<(MessageId, ActorId), AwaitingTransfer>,
+    // Checksum of the chunks imported so far, the program is locked while it's set
+    pub state_import: Option<StateChecksum>,
+    // The program is locked while its state is exported, so the chunks form a consistent snapshot
+    pub state_export: bool,
 }
 
 impl Invariant {
@@ -108,6 +122,12 @@ impl Invariant {
         Ok(balance)
     }
 
+    pub fn pool_id(&self, pool_key: &PoolKey) -> Result<PoolId, InvariantError> {
+        self.pool_keys
+            .get_id(pool_key)
+            .ok_or(InvariantError::PoolNotFound)
+    }
+
     pub fn calculate_swap(
         &self,
         pool_key: PoolKey,
@@ -116,204 +136,771 @@ impl Invariant {
         by_amount_in: bool,
         sqrt_price_limit: SqrtPrice,
     ) -> Result<CalculateSwapResult, InvariantError> {
-        let current_timestamp = exec::block_timestamp();
+        let pool = self.pools.get(&pool_key)?;
+        let pool_id = self.pool_id(&pool_key)?;
 
-        if amount.is_zero() {
-            return Err(InvariantError::AmountIsZero);
-        }
+        simulate_swap(
+            pool,
+            pool_key,
+            pool_id,
+            &self.ticks,
+            &self.tickmap,
+            self.config.protocol_fee,
+            exec::block_timestamp(),
+            x_to_y,
+            amount,
+            by_amount_in,
+            sqrt_price_limit,
+        )
+        .map(Into::into)
+    }
 
-        let mut ticks: Vec<Tick> = vec![];
+    pub fn create_tick(&mut self, pool_key: PoolKey, index: i32) -> Result<Tick, InvariantError> {
+        let current_timestamp = exec::block_timestamp();
+
+        check_tick(index, pool_key.fee_tier.tick_spacing)
+            .map_err(|_| InvariantError::InvalidTickIndexOrTickSpacing)?;
 
         let mut pool = self.pools.get(&pool_key)?;
+        // outside values of a new tick are taken from the up to date global state
+        unwrap!(pool.update_seconds_per_liquidity_global(current_timestamp));
 
-        if x_to_y {
-            if pool.sqrt_price <= sqrt_price_limit
-                || sqrt_price_limit > SqrtPrice::new(MAX_SQRT_PRICE.into())
-            {
-                return Err(InvariantError::WrongLimit);
-            }
-        } else if pool.sqrt_price >= sqrt_price_limit
-            || sqrt_price_limit < SqrtPrice::new(MIN_SQRT_PRICE.into())
+        let tick = Tick::create(index, &pool, current_timestamp);
+
+        Ok(tick)
+    }
+
+    pub fn get_or_create_tick(&mut self, pool_key: PoolKey, index: i32) -> (Tick, bool) {
+        if let Ok(tick) = self
+            .pool_id(&pool_key)
+            .and_then(|pool_id| self.ticks.get(pool_id, index).cloned())
         {
-            return Err(InvariantError::WrongLimit);
+            return (tick, false);
         }
 
-        let tick_limit = if x_to_y {
-            get_min_tick(pool_key.fee_tier.tick_spacing)
-        } else {
-            get_max_tick(pool_key.fee_tier.tick_spacing)
-        };
+        (self.create_tick(pool_key, index).unwrap(), true)
+    }
+
+    pub fn add_tick(&mut self, pool_key: PoolKey, tick: Tick) -> Result<(), InvariantError> {
+        let pool_id = self.pool_id(&pool_key)?;
+        self.ticks.add(pool_id, tick.index, tick)?;
 
-        let mut remaining_amount = amount;
+        self.tickmap
+            .flip(true, tick.index, pool_key.fee_tier.tick_spacing, pool_id);
 
-        let mut total_amount_in = TokenAmount::new(U256::from(0));
-        let mut total_amount_out = TokenAmount::new(U256::from(0));
+        Ok(())
+    }
 
-        let event_start_sqrt_price = pool.sqrt_price;
-        let mut event_fee_amount = TokenAmount::new(U256::from(0));
+    pub fn remove_tick(&mut self, key: PoolKey, tick: Tick) -> Result<(), InvariantError> {
+        if !tick.liquidity_gross.is_zero() {
+            return Err(InvariantError::NotEmptyTickDeinitialization);
+        }
 
-        while !remaining_amount.is_zero() {
-            let (swap_limit, limiting_tick) = self.tickmap.get_closer_limit(
-                sqrt_price_limit,
-                x_to_y,
-                pool.current_tick_index,
-                pool_key.fee_tier.tick_spacing,
-                pool_key,
-            )?;
-            let result = unwrap!(compute_swap_step(
-                pool.sqrt_price,
-                swap_limit,
-                pool.liquidity,
-                remaining_amount,
-                by_amount_in,
-                pool_key.fee_tier.fee,
-            ));
+        let pool_id = self.pool_id(&key)?;
+        self.tickmap
+            .flip(false, tick.index, key.fee_tier.tick_spacing, pool_id);
+        self.ticks.remove(pool_id, tick.index)?;
+        Ok(())
+    }
 
-            // make remaining amount smaller
-            if by_amount_in {
-                remaining_amount -= result.amount_in + result.fee_amount;
-            } else {
-                remaining_amount -= result.amount_out;
-            }
+    pub fn remove_position(
+        &mut self,
+        account_id: &ActorId,
+        position_id: PositionId,
+    ) -> Result<Position, InvariantError> {
+        self.positions.get_authorized(account_id, position_id)?;
 
-            unwrap!(pool.add_fee(result.fee_amount, x_to_y, self.config.protocol_fee));
-            event_fee_amount += result.fee_amount;
+        if self.stakes.contains(position_id) {
+            return Err(InvariantError::PositionStaked);
+        }
 
-            pool.sqrt_price = result.next_sqrt_price;
+        if self.limit_orders.contains(position_id) {
+            self.limit_orders.remove(position_id)?;
+        }
 
-            total_amount_in += result.amount_in + result.fee_amount;
-            total_amount_out += result.amount_out;
+        self.positions.remove(position_id)
+    }
 
-            // Fail if price would go over swap limit
-            if pool.sqrt_price == sqrt_price_limit && !remaining_amount.is_zero() {
-                return Err(InvariantError::PriceLimitReached);
-            }
+    pub fn transfer_position(
+        &mut self,
+        account_id: &ActorId,
+        position_id: PositionId,
+        receiver: &ActorId,
+    ) -> Result<ActorId, InvariantError> {
+        self.check_state_migration()?;
 
-            let mut tick_update = {
-                if let Some((tick_index, is_initialized)) = limiting_tick {
-                    if is_initialized {
-                        let tick = self.ticks.get(pool_key, tick_index).cloned()?;
-                        UpdatePoolTick::TickInitialized(tick)
-                    } else {
-                        UpdatePoolTick::TickUninitialized(tick_index)
-                    }
-                } else {
-                    UpdatePoolTick::NoTick
-                }
-            };
+        let (owner_id, _) = self.positions.get_authorized(account_id, position_id)?;
 
-            let (amount_to_add, amount_after_tick_update, has_crossed) = pool.update_tick(
-                result,
-                swap_limit,
-                &mut tick_update,
-                remaining_amount,
-                by_amount_in,
-                x_to_y,
-                current_timestamp,
-                self.config.protocol_fee,
-                pool_key.fee_tier,
-            );
-
-            remaining_amount = amount_after_tick_update;
-            total_amount_in += amount_to_add;
-
-            if let UpdatePoolTick::TickInitialized(tick) = tick_update {
-                if has_crossed {
-                    ticks.push(tick)
-                }
+        if self.stakes.contains(position_id) {
+            return Err(InvariantError::PositionStaked);
+        }
+
+        self.positions.transfer(position_id, receiver)?;
+        Ok(owner_id)
+    }
+
+    // Only the owner and its operators can change the approval of a position
+    pub fn approve_position(
+        &mut self,
+        account_id: &ActorId,
+        position_id: PositionId,
+        spender: &ActorId,
+    ) -> Result<(), InvariantError> {
+        self.check_state_migration()?;
+
+        let owner_id = self.positions.get_owner(position_id)?;
+
+        if owner_id != *account_id && !self.positions.is_operator(&owner_id, account_id) {
+            return Err(InvariantError::PositionNotFound);
+        }
+
+        self.positions.approve(position_id, spender)
+    }
+
+    // Fees the position would receive if it was claimed now
+    pub fn uncollected_fees(
+        &self,
+        position: &Position,
+    ) -> Result<(TokenAmount, TokenAmount), InvariantError> {
+        let current_timestamp = exec::block_timestamp();
+
+        // settled limit orders hold only the converted tokens
+        if position.liquidity.is_zero() {
+            return Ok((position.tokens_owed_x, position.tokens_owed_y));
+        }
+
+        let mut position = *position;
+        let mut pool = self.pools.get(&position.pool_key)?;
+        let pool_id = self.pool_id(&position.pool_key)?;
+        let mut lower_tick = *self.ticks.get(pool_id, position.lower_tick_index)?;
+        let mut upper_tick = *self.ticks.get(pool_id, position.upper_tick_index)?;
+
+        Ok(position.claim_fee(
+            &mut pool,
+            &mut upper_tick,
+            &mut lower_tick,
+            current_timestamp,
+        ))
+    }
+
+    // Converts limit orders whose settlement ticks were crossed by a swap, must be called
+    // after the result of the swap is saved. Returns the settled positions with the amounts
+    // they were converted to
+    pub fn settle_limit_orders(
+        &mut self,
+        pool_key: PoolKey,
+        crossed_tick_indexes: &[i32],
+        x_to_y: bool,
+    ) -> Result<Vec<(PositionId, TokenAmount, TokenAmount)>, InvariantError> {
+        let mut settled = vec![];
+
+        for tick_index in crossed_tick_indexes {
+            for position_id in self
+                .limit_orders
+                .get_settled_by(pool_key, *tick_index, x_to_y)
+            {
+                let (amount_x, amount_y) = self.settle_limit_order(position_id)?;
+                settled.push((position_id, amount_x, amount_y));
             }
+        }
 
-            let reached_tick_limit = match x_to_y {
-                true => pool.current_tick_index <= tick_limit,
-                false => pool.current_tick_index >= tick_limit,
-            };
+        Ok(settled)
+    }
+
+    fn settle_limit_order(
+        &mut self,
+        position_id: PositionId,
+    ) -> Result<(TokenAmount, TokenAmount), InvariantError> {
+        let current_timestamp = exec::block_timestamp();
+
+        let mut position = *self.positions.get(position_id)?;
+        let pool_key = position.pool_key;
 
-            if reached_tick_limit {
-                return Err(InvariantError::TickLimitReached);
+        let mut pool = self.pools.get(&pool_key)?;
+        let pool_id = self.pool_id(&pool_key)?;
+        let mut lower_tick = *self.ticks.get(pool_id, position.lower_tick_index)?;
+        let mut upper_tick = *self.ticks.get(pool_id, position.upper_tick_index)?;
+
+        // the price is past the range, so the liquidity is withdrawn only in the bought token
+        let (amount_x, amount_y, remove_lower_tick, remove_upper_tick) = position.remove(
+            &mut pool,
+            current_timestamp,
+            &mut lower_tick,
+            &mut upper_tick,
+            pool_key.fee_tier.tick_spacing,
+        );
+        position.tokens_owed_x = amount_x;
+        position.tokens_owed_y = amount_y;
+
+        self.pools.update(&pool_key, &pool)?;
+
+        if remove_lower_tick {
+            self.remove_tick(pool_key, lower_tick)?;
+        } else {
+            self.ticks.update(pool_id, lower_tick.index, lower_tick)?;
+        }
+
+        if remove_upper_tick {
+            self.remove_tick(pool_key, upper_tick)?;
+        } else {
+            self.ticks.update(pool_id, upper_tick.index, upper_tick)?;
+        }
+
+        self.positions.update(position_id, &position)?;
+        self.limit_orders.settle(position_id)?;
+
+        Ok((amount_x, amount_y))
+    }
+
+    // Flags of the pool are checked on top of the global ones
+    pub fn check_pause(
+        &self,
+        pool_key: Option<PoolKey>,
+        action: PausableAction,
+    ) -> Result<(), InvariantError> {
+        self.check_state_migration()?;
+        self.config.pause.check(action)?;
+
+        if let Some(pool_key) = pool_key {
+            let pool = self.pools.get(&pool_key)?;
+            pool.pause.check(action)?;
+
+            if pool.deprecated && action != PausableAction::Withdrawal {
+                return Err(InvariantError::PoolDeprecated);
             }
         }
 
-        if total_amount_out.get() == U256::from(0) {
-            return Err(InvariantError::NoGainSwap);
+        Ok(())
+    }
+
+    pub fn check_state_migration(&self) -> Result<(), InvariantError> {
+        if self.state_import.is_some() {
+            return Err(InvariantError::StateImportInProgress);
         }
 
-        Ok(CalculateSwapResult {
-            amount_in: total_amount_in,
-            amount_out: total_amount_out,
-            start_sqrt_price: event_start_sqrt_price,
-            target_sqrt_price: pool.sqrt_price,
-            fee: event_fee_amount,
-            pool,
-            ticks,
+        if self.state_export {
+            return Err(InvariantError::StateExportInProgress);
+        }
+
+        Ok(())
+    }
+
+    // State can only be imported into a program that holds none of the exported sections
+    pub fn is_empty(&self) -> bool {
+        self.fee_tiers.get_all().is_empty()
+            && self.pool_keys.count() == 0
+            && self.positions.is_empty()
+            && self.balances.is_empty()
+            && self.roles.is_empty()
+            && self.campaigns.is_empty()
+    }
+
+    // Flash loans and awaiting transfers only live within a single message, so they aren't a part
+    // of the exported state
+    pub fn export_state(&self, cursor: StateCursor) -> Result<StateExport, InvariantError> {
+        let StateCursor { section, offset } = cursor;
+        let size = STATE_CHUNK_SIZE as usize;
+
+        let chunk =
+            match section {
+                StateSection::Config => StateChunk::Config {
+                    admin: self.config.admin,
+                    protocol_fee: self.config.protocol_fee,
+                    pause: self.config.pause,
+                    pending_admin: self.pending_admin,
+                    next_position_id: self.positions.next_position_id(),
+                    next_campaign_id: self.campaigns.next_campaign_id(),
+                },
+                StateSection::FeeTiers => StateChunk::FeeTiers(
+                    self.fee_tiers
+                        .get_all()
+                        .into_iter()
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Pools => {
+                    let offset = offset.min(self.pool_keys.count() as u32) as u16;
+                    let size = (STATE_CHUNK_SIZE as u16).min(u16::MAX - offset);
+
+                    StateChunk::Pools(
+                        self.pool_keys
+                            .get_all(size, offset)
+                            .into_iter()
+                            .map(|pool_key| Ok((pool_key, self.pools.get(&pool_key)?)))
+                            .collect::<Result<Vec<_>, InvariantError>>()?,
+                    )
+                }
+                StateSection::Ticks => StateChunk::Ticks(
+                    self.get_pool_ids()
+                        .into_iter()
+                        .flat_map(|(pool_key, pool_id)| {
+                            self.ticks
+                                .get_all(pool_id)
+                                .map(move |tick| (pool_key, *tick))
+                        })
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Tickmap => StateChunk::Tickmap(
+                    self.get_pool_ids()
+                        .into_iter()
+                        .flat_map(|(pool_key, pool_id)| {
+                            self.tickmap
+                                .get_chunks(pool_id)
+                                .map(move |(chunk_index, chunk)| (pool_key, chunk_index, chunk))
+                        })
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Positions => {
+                    StateChunk::Positions(self.positions.get_all_slice(offset, STATE_CHUNK_SIZE))
+                }
+                StateSection::Balances => StateChunk::Balances(
+                    self.balances
+                        .iter()
+                        .flat_map(|(account, tokens)| {
+                            tokens
+                                .iter()
+                                .map(|(token, amount)| (*account, *token, *amount))
+                        })
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Roles => {
+                    StateChunk::Roles(self.roles.iter().skip(offset as usize).take(size).collect())
+                }
+                StateSection::Approvals => StateChunk::Approvals(
+                    self.positions
+                        .approvals()
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Operators => StateChunk::Operators(
+                    self.positions
+                        .operators()
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::LimitOrders => StateChunk::LimitOrders(
+                    self.limit_orders
+                        .iter()
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Campaigns => StateChunk::Campaigns(
+                    self.campaigns
+                        .iter()
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Stakes => StateChunk::Stakes(
+                    self.stakes
+                        .iter()
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Oracles => StateChunk::Oracles(
+                    self.oracles
+                        .iter()
+                        .map(|(pool_key, oracle)| {
+                            (
+                                pool_key,
+                                oracle.index,
+                                oracle.cardinality,
+                                oracle.cardinality_next,
+                            )
+                        })
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+                StateSection::Observations => StateChunk::Observations(
+                    self.oracles
+                        .iter()
+                        .flat_map(|(pool_key, oracle)| {
+                            oracle.observations.iter().enumerate().map(
+                                move |(slot, observation)| (pool_key, slot as u16, *observation),
+                            )
+                        })
+                        .skip(offset as usize)
+                        .take(size)
+                        .collect(),
+                ),
+            };
+
+        let next_cursor = if section != StateSection::Config && chunk.len() == STATE_CHUNK_SIZE {
+            Some(StateCursor {
+                section,
+                offset: offset + STATE_CHUNK_SIZE,
+            })
+        } else {
+            section
+                .next()
+                .map(|section| StateCursor { section, offset: 0 })
+        };
+
+        Ok(StateExport {
+            checksum: chunk.checksum(),
+            chunk,
+            next_cursor,
         })
     }
 
-    pub fn create_tick(&mut self, pool_key: PoolKey, index: i32) -> Result<Tick, InvariantError> {
+    // Pools in the order of their indexes
+    fn get_pool_ids(&self) -> Vec<(PoolKey, PoolId)> {
+        self.pool_keys
+            .get_all(self.pool_keys.count(), 0)
+            .into_iter()
+            .filter_map(|pool_key| Some((pool_key, self.pool_keys.get_id(&pool_key)?)))
+            .collect()
+    }
+
+    // Chunks have to be imported in the order they were exported
+    pub fn import_state(&mut self, chunk: StateChunk) -> Result<(), InvariantError> {
         let current_timestamp = exec::block_timestamp();
 
-        check_tick(index, pool_key.fee_tier.tick_spacing)
-            .map_err(|_| InvariantError::InvalidTickIndexOrTickSpacing)?;
+        match chunk {
+            StateChunk::Config {
+                admin,
+                protocol_fee,
+                pause,
+                pending_admin,
+                next_position_id,
+                next_campaign_id,
+            } => {
+                // the admin of the exported state takes over the rest of the import
+                self.config = InvariantConfig {
+                    admin,
+                    protocol_fee,
+                    pause,
+                };
+                self.pending_admin = pending_admin;
+                self.positions.set_next_position_id(next_position_id);
+                self.campaigns.set_next_campaign_id(next_campaign_id);
+            }
+            StateChunk::FeeTiers(fee_tiers) => {
+                for fee_tier in fee_tiers {
+                    self.fee_tiers.add(&fee_tier)?;
+                }
+            }
+            StateChunk::Pools(pools) => {
+                for (pool_key, pool) in pools {
+                    self.pools.add(&pool_key, &pool)?;
+                    self.pool_keys.add(&pool_key)?;
+                    self.oracles
+                        .add(&pool_key, Oracle::new(current_timestamp))?;
+                }
+            }
+            StateChunk::Ticks(ticks) => {
+                for (pool_key, tick) in ticks {
+                    let pool_id = self.pool_id(&pool_key)?;
+                    self.ticks.add(pool_id, tick.index, tick)?;
+                }
+            }
+            StateChunk::Tickmap(chunks) => {
+                for (pool_key, chunk_index, chunk) in chunks {
+                    let pool_id = self.pool_id(&pool_key)?;
+                    self.tickmap
+                        .bitmap
+                        .entry(pool_id)
+                        .or_default()
+                        .insert(chunk_index, chunk);
+                }
+            }
+            StateChunk::Positions(positions) => {
+                for (account_id, position_id, position) in positions {
+                    self.positions.insert(&account_id, position_id, &position)?;
+                }
+            }
+            StateChunk::Balances(balances) => {
+                for (account_id, token, amount) in balances {
+                    self.increase_token_balance(&token, &account_id, amount)?;
+                }
+            }
+            StateChunk::Roles(roles) => {
+                for (account_id, role) in roles {
+                    self.roles.grant(&account_id, role)?;
+                }
+            }
+            StateChunk::Approvals(approvals) => {
+                for (position_id, spender) in approvals {
+                    self.positions.approve(position_id, &spender)?;
+                }
+            }
+            StateChunk::Operators(operators) => {
+                for (account_id, operator) in operators {
+                    self.positions.set_operator(&account_id, &operator, true);
+                }
+            }
+            StateChunk::LimitOrders(limit_orders) => {
+                for (position_id, limit_order) in limit_orders {
+                    self.positions.get(position_id)?;
+                    self.limit_orders.add(position_id, &limit_order)?;
+                }
+            }
+            StateChunk::Campaigns(campaigns) => {
+                for (campaign_id, campaign) in campaigns {
+                    self.campaigns.insert(campaign_id, &campaign)?;
+                }
+            }
+            StateChunk::Stakes(stakes) => {
+                for (position_id, stake) in stakes {
+                    self.positions.get(position_id)?;
+                    self.campaigns.get(stake.campaign_id)?;
+                    self.stakes.add(position_id, &stake)?;
+                }
+            }
+            StateChunk::Oracles(oracles) => {
+                for (pool_key, index, cardinality, cardinality_next) in oracles {
+                    let oracle = self.oracles.get_mut(&pool_key)?;
+                    oracle.grow(cardinality_next)?;
+                    oracle.index = index;
+                    oracle.cardinality = cardinality;
+                }
+            }
+            StateChunk::Observations(observations) => {
+                for (pool_key, slot, observation) in observations {
+                    let oracle = self.oracles.get_mut(&pool_key)?;
+                    *oracle
+                        .observations
+                        .get_mut(slot as usize)
+                        .ok_or(InvariantError::InvalidOracleCardinality)? = observation;
+                }
+            }
+        }
 
+        Ok(())
+    }
+
+    pub fn pool_status(&self, pool_key: PoolKey) -> Result<PoolStatus, InvariantError> {
         let pool = self.pools.get(&pool_key)?;
 
-        let tick = Tick::create(index, &pool, current_timestamp);
+        if !pool.deprecated {
+            Ok(PoolStatus::Active)
+        } else if self.is_pool_empty(pool_key, &pool) {
+            Ok(PoolStatus::Removable)
+        } else {
+            Ok(PoolStatus::Deprecated)
+        }
+    }
 
-        Ok(tick)
+    // Settled limit orders hold no ticks, so positions and campaigns are counted on their own
+    fn is_pool_empty(&self, pool_key: PoolKey, pool: &Pool) -> bool {
+        pool.liquidity.is_zero()
+            && self.get_tickmap(pool_key).is_empty()
+            && self.positions.get_pool_length(&pool_key) == 0
+            && self.campaigns.get_pool_length(&pool_key) == 0
     }
 
-    pub fn get_or_create_tick(&mut self, pool_key: PoolKey, index: i32) -> (Tick, bool) {
-        if let Ok(tick) = self.ticks.get(pool_key, index).cloned() {
-            return (tick, false);
+    // Protocol fee left in the pool is moved to the balance of the fee receiver
+    pub fn remove_pool(&mut self, pool_key: PoolKey) -> Result<Pool, InvariantError> {
+        self.check_state_migration()?;
+
+        let pool = self.pools.get(&pool_key)?;
+
+        if !pool.deprecated {
+            return Err(InvariantError::PoolNotDeprecated);
         }
 
-        (self.create_tick(pool_key, index).unwrap(), true)
-    }
+        if !self.is_pool_empty(pool_key, &pool) {
+            return Err(InvariantError::PoolNotEmpty);
+        }
 
-    pub fn add_tick(&mut self, pool_key: PoolKey, tick: Tick) -> Result<(), InvariantError> {
-        self.ticks.add(pool_key, tick.index, tick)?;
+        let pool_id = self.pool_id(&pool_key)?;
+        self.tickmap.bitmap.remove(&pool_id);
+        self.ticks.remove_pool(pool_id);
 
-        self.tickmap
-            .flip(true, tick.index, pool_key.fee_tier.tick_spacing, pool_key);
+        if !pool.fee_protocol_token_x.is_zero() {
+            self.increase_token_balance(
+                &pool_key.token_x,
+                &pool.fee_receiver,
+                pool.fee_protocol_token_x,
+            )?;
+        }
+        if !pool.fee_protocol_token_y.is_zero() {
+            self.increase_token_balance(
+                &pool_key.token_y,
+                &pool.fee_receiver,
+                pool.fee_protocol_token_y,
+            )?;
+        }
 
-        Ok(())
+        self.pools.remove(&pool_key)?;
+        self.pool_keys.remove(&pool_key)?;
+        self.oracles.remove(&pool_key).ok();
+
+        Ok(pool)
     }
 
-    pub fn remove_tick(&mut self, key: PoolKey, tick: Tick) -> Result<(), InvariantError> {
-        if !tick.liquidity_gross.is_zero() {
-            return Err(InvariantError::NotEmptyTickDeinitialization);
+    // Balance of an account with an outstanding flash loan can only be used to repay it
+    pub fn check_flash_loan(&self, account_id: &ActorId) -> Result<(), InvariantError> {
+        if self.flash_loans.contains_key(account_id) {
+            return Err(InvariantError::FlashLoanActive);
         }
 
-        self.tickmap
-            .flip(false, tick.index, key.fee_tier.tick_spacing, key);
-        self.ticks.remove(key, tick.index)?;
         Ok(())
     }
 
-    pub fn tickmap_slice(
+    // Amounts of both tokens held by the liquidity of the pool, fees and tokens owed to the
+    // positions aren't included
+    pub fn pool_reserves(
         &self,
-        range: impl Iterator<Item = u16>,
-        pool_key: PoolKey,
-    ) -> Vec<(u16, u64)> {
-        let mut tickmap_slice: Vec<(u16, u64)> = vec![];
+        pool_key: &PoolKey,
+    ) -> Result<(TokenAmount, TokenAmount), InvariantError> {
+        let pool = self.pools.get(pool_key)?;
+        let pool_id = self.pool_id(pool_key)?;
+
+        let mut ticks: Vec<&Tick> = self.ticks.get_all(pool_id).collect();
+        ticks.sort_by_key(|tick| tick.index);
+
+        let mut reserve_x = TokenAmount::new(U256::from(0));
+        let mut reserve_y = TokenAmount::new(U256::from(0));
+        let mut liquidity = Liquidity::new(U256::from(0));
+
+        for window in ticks.windows(2) {
+            let (lower_tick, upper_tick) = (window[0], window[1]);
+
+            liquidity = if lower_tick.sign {
+                liquidity.checked_add(lower_tick.liquidity_change)
+            } else {
+                liquidity.checked_sub(lower_tick.liquidity_change)
+            }
+            .map_err(|_| InvariantError::InsufficientLiquidity)?;
 
-        for chunk_index in range {
-            if let Some(chunk) = self.tickmap.bitmap.get(&(chunk_index, pool_key)) {
-                tickmap_slice.push((chunk_index, *chunk));
+            if liquidity.is_zero() {
+                continue;
             }
+
+            let (amount_x, amount_y, _) = unwrap!(calculate_amount_delta(
+                pool.current_tick_index,
+                pool.sqrt_price,
+                liquidity,
+                false,
+                upper_tick.index,
+                lower_tick.index,
+            ));
+
+            // reserves that don't fit are more than can ever be borrowed
+            reserve_x = reserve_x
+                .checked_add(amount_x)
+                .unwrap_or(TokenAmount::max_instance());
+            reserve_y = reserve_y
+                .checked_add(amount_y)
+                .unwrap_or(TokenAmount::max_instance());
         }
 
-        tickmap_slice
+        Ok((reserve_x, reserve_y))
     }
 
-    pub fn liquidity_ticks_count(&self, pool_key: PoolKey) -> u32 {
-        let mut sum = 0;
-        for chunk_index in 0..get_max_chunk(pool_key.fee_tier.tick_spacing) {
-            if let Some(chunk) = self.tickmap.bitmap.get(&(chunk_index, pool_key)) {
-                sum += chunk.count_ones()
-            }
+    // Closes the loan of the receiver. The borrowed amount and the fee are taken when the
+    // receiver replied and has them on its balance, the fee goes to the liquidity providers of
+    // the pool. Otherwise only the lent tokens are taken back, they are still on the balance since
+    // it couldn't be used while the loan was active.
+    pub fn settle_flash_loan(
+        &mut self,
+        receiver: &ActorId,
+        replied: bool,
+    ) -> Result<FlashLoan, InvariantError> {
+        let flash_loan = self
+            .flash_loans
+            .remove(receiver)
+            .ok_or(InvariantError::FlashLoanNotFound)?;
+        let pool_key = flash_loan.pool_key;
+        let (repayment_x, repayment_y) = flash_loan.repayment()?;
+
+        let balance = |token: &ActorId| {
+            self.balances
+                .get(receiver)
+                .and_then(|balances| balances.get(token))
+                .copied()
+                .unwrap_or_default()
+        };
+        let repaid = replied
+            && balance(&pool_key.token_x) >= repayment_x
+            && balance(&pool_key.token_y) >= repayment_y;
+
+        if !repaid {
+            self.decrease_token_balance(&pool_key.token_x, receiver, flash_loan.amount_x.into())?;
+            self.decrease_token_balance(&pool_key.token_y, receiver, flash_loan.amount_y.into())?;
+
+            return Err(InvariantError::FlashLoanNotRepaid);
         }
 
-        sum
+        self.decrease_token_balance(&pool_key.token_x, receiver, repayment_x.into())?;
+        self.decrease_token_balance(&pool_key.token_y, receiver, repayment_y.into())?;
+
+        let mut pool = self.pools.get(&pool_key)?;
+        let protocol_fee = pool.effective_protocol_fee(self.config.protocol_fee);
+        unwrap!(pool.add_fee(flash_loan.fee_x, true, protocol_fee));
+        unwrap!(pool.add_fee(flash_loan.fee_y, false, protocol_fee));
+        self.pools.update(&pool_key, &pool)?;
+
+        Ok(flash_loan)
+    }
+
+    pub fn seconds_per_liquidity_inside(
+        &self,
+        position: &Position,
+    ) -> Result<SecondsPerLiquidity, InvariantError> {
+        let current_timestamp = exec::block_timestamp();
+
+        let mut pool = self.pools.get(&position.pool_key)?;
+        let pool_id = self.pool_id(&position.pool_key)?;
+        let lower_tick = self.ticks.get(pool_id, position.lower_tick_index)?;
+        let upper_tick = self.ticks.get(pool_id, position.upper_tick_index)?;
+
+        unwrap!(pool.update_seconds_per_liquidity_global(current_timestamp));
+
+        Ok(unwrap!(calculate_seconds_per_liquidity_inside(
+            lower_tick.index,
+            upper_tick.index,
+            pool.current_tick_index,
+            lower_tick.seconds_per_liquidity_outside,
+            upper_tick.seconds_per_liquidity_outside,
+            pool.seconds_per_liquidity_global,
+        )))
+    }
+
+    // Must be called with the pool state from before the change
+    pub fn write_observation(
+        &mut self,
+        pool_key: PoolKey,
+        pool: &Pool,
+    ) -> Result<(), InvariantError> {
+        let current_timestamp = exec::block_timestamp();
+
+        self.oracles.get_mut(&pool_key)?.write(
+            current_timestamp,
+            pool.current_tick_index,
+            pool.liquidity,
+        );
+
+        Ok(())
+    }
+
+    // Only the chunks with initialized ticks, in the order of the chunk index
+    pub fn get_tickmap(&self, pool_key: PoolKey) -> Vec<(u16, u64)> {
+        self.pool_id(&pool_key)
+            .map(|pool_id| self.tickmap.get_chunks(pool_id).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn liquidity_ticks_count(&self, pool_key: PoolKey) -> u32 {
+        self.pool_id(&pool_key).map_or(0, |pool_id| {
+            self.tickmap
+                .get_chunks(pool_id)
+                .map(|(_, chunk)| chunk.count_ones())
+                .sum()
+        })
     }
 }
 
@@ -7,13 +7,18 @@ mod test_helpers;
 
 use io::InvariantConfig;
 use sails_rs::gstd::{program, GStdExecContext};
+#[macro_use]
 mod invariant_service;
+mod incentives_service;
 mod invariant_storage;
+mod position_nft_service;
 pub use contracts::{
     AwaitingTransfer, FeeTier, FeeTiers, InvariantError, Pool, PoolKey, PoolKeys, Pools, Position,
     Positions, Tick, Tickmap, Ticks, TransferType, UpdatePoolTick,
 };
+use incentives_service::IncentivesService;
 use invariant_service::InvariantService;
+use position_nft_service::PositionNftService;
 
 pub struct InvariantProgram(());
 
@@ -27,4 +32,12 @@ impl InvariantProgram {
     pub fn service(&self) -> InvariantService<GStdExecContext> {
         InvariantService::new(GStdExecContext::new())
     }
+
+    pub fn incentives(&self) -> IncentivesService<GStdExecContext> {
+        IncentivesService::new(GStdExecContext::new())
+    }
+
+    pub fn position_nft(&self) -> PositionNftService<GStdExecContext> {
+        PositionNftService::new(GStdExecContext::new())
+    }
 }
@@ -0,0 +1,132 @@
+extern crate alloc;
+use crate::invariant_service::{panic, InvariantService};
+use crate::invariant_storage::InvariantStorage;
+use contracts::{InvariantError, PositionId};
+use io::*;
+use sails_rs::{
+    gstd::{service, services::Service, ExecContext},
+    prelude::*,
+};
+
+pub const NFT_NAME: &str = "Invariant Position";
+pub const NFT_SYMBOL: &str = "INV-POS";
+// Encoded route of the invariant service, transfers are exposed through it so that they are
+// announced with its events
+static INVARIANT_SERVICE_ROUTE: [u8; 8] = *b"\x1cService";
+
+pub struct PositionNftService<TExecContext> {
+    exec_context: TExecContext,
+}
+
+// Presents positions as non-fungible tokens, the token id is the position id
+#[service(events=PositionNftEvent)]
+impl<TExecContext> PositionNftService<TExecContext>
+where
+    TExecContext: ExecContext + Clone,
+{
+    pub fn new(exec_context: TExecContext) -> Self {
+        Self { exec_context }
+    }
+
+    pub fn name(&self) -> String {
+        NFT_NAME.into()
+    }
+
+    pub fn symbol(&self) -> String {
+        NFT_SYMBOL.into()
+    }
+
+    pub fn owner_of(&self, token_id: PositionId) -> Result<ActorId, InvariantError> {
+        InvariantStorage::as_ref().positions.get_owner(token_id)
+    }
+
+    pub fn balance_of(&self, owner: ActorId) -> u32 {
+        InvariantStorage::as_ref().positions.get_length(&owner)
+    }
+
+    pub fn get_approved(&self, token_id: PositionId) -> Option<ActorId> {
+        InvariantStorage::as_ref().positions.get_approved(token_id)
+    }
+
+    pub fn is_approved_for_all(&self, owner: ActorId, operator: ActorId) -> bool {
+        InvariantStorage::as_ref()
+            .positions
+            .is_operator(&owner, &operator)
+    }
+
+    pub fn token_metadata(&self, token_id: PositionId) -> Result<PositionMetadata, InvariantError> {
+        let invariant = InvariantStorage::as_ref();
+
+        let owner = invariant.positions.get_owner(token_id)?;
+        let position = invariant.positions.get(token_id)?;
+        let pool = invariant.pools.get(&position.pool_key)?;
+        let (uncollected_fee_x, uncollected_fee_y) = invariant.uncollected_fees(position)?;
+
+        Ok(PositionMetadata {
+            token_id,
+            owner,
+            pool_key: position.pool_key,
+            lower_tick_index: position.lower_tick_index,
+            upper_tick_index: position.upper_tick_index,
+            liquidity: position.liquidity,
+            uncollected_fee_x,
+            uncollected_fee_y,
+            current_sqrt_price: pool.sqrt_price,
+        })
+    }
+
+    pub fn transfer_from(&mut self, from: ActorId, to: ActorId, token_id: PositionId) {
+        panicking!(move || {
+            if InvariantStorage::as_ref().positions.get_owner(token_id)? != from {
+                return Err(InvariantError::PositionNotFound);
+            }
+
+            Ok(())
+        });
+
+        InvariantService::new(self.exec_context.clone())
+            .expose(self.exec_context.message_id(), &INVARIANT_SERVICE_ROUTE)
+            .transfer_position(token_id, to);
+
+        self.notify_on(PositionNftEvent::TransferEvent { from, to, token_id })
+            .expect("Failed to notify on transfer event");
+    }
+
+    pub fn approve(&mut self, approved: ActorId, token_id: PositionId) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+
+            invariant.approve_position(&self.exec_context.actor_id(), token_id, &approved)?;
+
+            self.notify_on(PositionNftEvent::ApprovalEvent {
+                owner: invariant.positions.get_owner(token_id)?,
+                approved,
+                token_id,
+            })
+            .expect("Failed to notify on approval event");
+
+            Ok(())
+        })
+    }
+
+    pub fn set_approval_for_all(&mut self, operator: ActorId, approved: bool) {
+        panicking!(move || {
+            let invariant = InvariantStorage::as_mut();
+            invariant.check_state_migration()?;
+
+            let owner = self.exec_context.actor_id();
+            invariant
+                .positions
+                .set_operator(&owner, &operator, approved);
+
+            self.notify_on(PositionNftEvent::ApprovalForAllEvent {
+                owner,
+                operator,
+                approved,
+            })
+            .expect("Failed to notify on approval for all event");
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,19 @@
+use crate::send_request;
+use contracts::PositionId;
+use gtest::*;
+use sails_rs::ActorId;
+
+pub fn approve_position(
+    invariant: &Program,
+    user: u64,
+    position_id: PositionId,
+    spender: impl Into<ActorId>,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "ApprovePosition",
+        payload: (position_id, spender.into())
+    )
+}
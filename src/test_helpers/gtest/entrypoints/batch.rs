@@ -0,0 +1,14 @@
+use crate::send_request;
+use gtest::*;
+use io::*;
+use sails_rs::prelude::*;
+
+pub fn batch(invariant: &Program, user: u64, ops: Vec<InvariantOp>, atomic: bool) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "Batch",
+        payload: (ops, atomic)
+    )
+}
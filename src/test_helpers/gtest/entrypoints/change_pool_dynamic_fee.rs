@@ -0,0 +1,19 @@
+use crate::send_request;
+use contracts::{DynamicFee, PoolKey};
+use gtest::*;
+use sails_rs::prelude::*;
+
+pub fn change_pool_dynamic_fee(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    dynamic_fee: Option<DynamicFee>,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "ChangePoolDynamicFee",
+        payload: (pool_key, dynamic_fee)
+    )
+}
@@ -17,3 +17,18 @@ pub fn change_protocol_fee(invariant: &Program, user: u64, protocol_fee: Percent
         payload: (protocol_fee)
     )
 }
+
+pub fn change_pool_protocol_fee(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    protocol_fee: Option<Percentage>,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "ChangePoolProtocolFee",
+        payload: (pool_key, protocol_fee)
+    )
+}
@@ -1,5 +1,5 @@
 use crate::{send_request, test_helpers::gtest::*};
-use contracts::{InvariantError, PoolKey};
+use contracts::{InvariantError, PoolKey, PositionId};
 use gtest::*;
 use io::*;
 use math::{sqrt_price::SqrtPrice, token_amount::TokenAmount};
@@ -9,7 +9,7 @@ use sails_rs::prelude::*;
 pub fn claim_fee(
     invariant: &Program,
     from: u64,
-    position_id: u32,
+    position_id: PositionId,
     expected_error: Option<impl Into<String>>,
 ) -> Option<(TokenAmount, TokenAmount)> {
     let res = send_request!(
@@ -27,7 +27,8 @@ pub fn claim_fee(
 
     res.assert_success();
     let events = res.emitted_events();
-    assert_eq!(events.len(), 1);
+    assert_eq!(events.len(), 2);
+    events[0].assert_to(EVENT_ADDRESS);
     events
         .last()
         .unwrap()
@@ -35,3 +36,17 @@ pub fn claim_fee(
         .unwrap()
         .into()
 }
+
+pub fn claim_fee_and_withdraw(
+    invariant: &Program,
+    from: u64,
+    position_id: PositionId,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: from,
+        service_name: "Service",
+        action: "ClaimFeeAndWithdraw",
+        payload: (position_id)
+    )
+}
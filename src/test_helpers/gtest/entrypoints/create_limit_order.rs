@@ -0,0 +1,20 @@
+use crate::send_request;
+use contracts::PoolKey;
+use gtest::*;
+use math::liquidity::Liquidity;
+
+pub fn create_limit_order(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    lower_tick: i32,
+    liquidity_delta: Liquidity,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "CreateLimitOrder",
+        payload: (pool_key, lower_tick, liquidity_delta)
+    )
+}
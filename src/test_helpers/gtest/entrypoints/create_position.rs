@@ -5,7 +5,9 @@ use crate::test_helpers::gtest::consts::*;
 use contracts::{pool_key, FeeTier, PoolKey};
 use gtest::*;
 use io::*;
-use math::{liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice};
+use math::{
+    liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice, token_amount::TokenAmount,
+};
 use sails_rs::ActorId;
 
 pub fn create_position(
@@ -17,12 +19,57 @@ pub fn create_position(
     liquidity_delta: Liquidity,
     slippage_limit_lower: SqrtPrice,
     slippage_limit_upper: SqrtPrice,
+) -> RunResult {
+    create_position_with_deadline(
+        invariant,
+        user,
+        pool_key,
+        lower_tick,
+        upper_tick,
+        liquidity_delta,
+        slippage_limit_lower,
+        slippage_limit_upper,
+        None,
+    )
+}
+
+pub fn create_position_with_deadline(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    lower_tick: i32,
+    upper_tick: i32,
+    liquidity_delta: Liquidity,
+    slippage_limit_lower: SqrtPrice,
+    slippage_limit_upper: SqrtPrice,
+    deadline: Option<u64>,
 ) -> RunResult {
     send_request!(
         program: invariant,
         user: user,
         service_name: "Service",
         action: "CreatePosition",
-        payload: (pool_key, lower_tick, upper_tick, liquidity_delta, slippage_limit_lower, slippage_limit_upper)
+        payload: (pool_key, lower_tick, upper_tick, liquidity_delta, slippage_limit_lower, slippage_limit_upper, deadline)
+    )
+}
+
+pub fn deposit_and_create_position(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    lower_tick: i32,
+    upper_tick: i32,
+    liquidity_delta: Liquidity,
+    slippage_limit_lower: SqrtPrice,
+    slippage_limit_upper: SqrtPrice,
+    amount_x: TokenAmount,
+    amount_y: TokenAmount,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "DepositAndCreatePosition",
+        payload: (pool_key, lower_tick, upper_tick, liquidity_delta, slippage_limit_lower, slippage_limit_upper, amount_x, amount_y, None::<u64>)
     )
 }
@@ -0,0 +1,19 @@
+use crate::send_request;
+use contracts::PositionId;
+use gtest::*;
+use math::liquidity::Liquidity;
+
+pub fn decrease_liquidity(
+    invariant: &Program,
+    user: u64,
+    position_id: PositionId,
+    liquidity_delta: Liquidity,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "DecreaseLiquidity",
+        payload: (position_id, liquidity_delta)
+    )
+}
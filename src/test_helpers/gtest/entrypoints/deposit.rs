@@ -28,13 +28,13 @@ pub fn deposit_single_token(
 
     res.assert_success();
     let events = res.emitted_events();
-    assert_eq!(events.len(), 1);
-    events
+    let amount = events
         .last()
         .unwrap()
         .decoded_event::<TokenAmount>()
-        .unwrap()
-        .into()
+        .unwrap();
+    assert_transfer_events(&events[..events.len() - 1], &[amount]);
+    amount.into()
 }
 
 #[track_caller]
@@ -62,13 +62,13 @@ pub fn deposit_token_pair(
 
     res.assert_success();
     let events = res.emitted_events();
-    assert_eq!(events.len(), 1);
-    events
+    let (amount_x, amount_y) = events
         .last()
         .unwrap()
         .decoded_event::<(TokenAmount, TokenAmount)>()
-        .unwrap()
-        .into()
+        .unwrap();
+    assert_transfer_events(&events[..events.len() - 1], &[amount_x, amount_y]);
+    (amount_x, amount_y).into()
 }
 
 pub fn deposit_vara(
@@ -93,11 +93,11 @@ pub fn deposit_vara(
 
     res.assert_success();
     let events = res.emitted_events();
-    assert_eq!(events.len(), 1);
-    events
+    let amount = events
         .last()
         .unwrap()
         .decoded_event::<TokenAmount>()
-        .unwrap()
-        .into()
+        .unwrap();
+    assert_transfer_events(&events[..events.len() - 1], &[amount]);
+    amount.into()
 }
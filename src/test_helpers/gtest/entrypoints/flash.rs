@@ -0,0 +1,37 @@
+use crate::{send_query, send_request, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::{Program, RunResult};
+use math::token_amount::TokenAmount;
+use sails_rs::prelude::*;
+
+pub fn flash(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    amount_x: TokenAmount,
+    amount_y: TokenAmount,
+    receiver: impl Into<ActorId>,
+    payload: Vec<u8>,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "Flash",
+        payload: (pool_key, amount_x, amount_y, receiver.into(), payload)
+    )
+}
+
+pub fn get_flash_loan(
+    invariant: &Program,
+    receiver: impl Into<ActorId>,
+) -> Result<FlashLoan, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetFlashLoan",
+        payload: (receiver.into()),
+        response_type: Result<FlashLoan, InvariantError>
+    )
+}
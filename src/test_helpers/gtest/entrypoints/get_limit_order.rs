@@ -0,0 +1,17 @@
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::*;
+
+pub fn get_limit_order(
+    invariant: &Program,
+    position_id: PositionId,
+) -> Result<LimitOrder, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetLimitOrder",
+        payload: (position_id),
+        response_type: Result<LimitOrder, InvariantError>
+    )
+}
@@ -0,0 +1,16 @@
+use contracts::*;
+use gtest::*;
+
+use io::*;
+
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+pub fn get_oracle(invariant: &Program, pool_key: PoolKey) -> Result<Oracle, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetOracle",
+        payload: (pool_key),
+        response_type: Result<Oracle, InvariantError>
+    )
+}
@@ -0,0 +1,15 @@
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::*;
+use sails_rs::ActorId;
+
+pub fn get_position_approved(invariant: &Program, position_id: PositionId) -> Option<ActorId> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetPositionApproved",
+        payload: (position_id),
+        response_type: Option<ActorId>
+    )
+}
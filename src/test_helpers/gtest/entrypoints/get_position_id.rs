@@ -0,0 +1,20 @@
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::*;
+use io::*;
+use sails_rs::ActorId;
+
+pub fn get_position_id(
+    invariant: &Program,
+    owner: ActorId,
+    index: u32,
+) -> Result<PositionId, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetPositionId",
+        payload: (owner, index),
+        response_type: Result<PositionId, InvariantError>
+    )
+}
@@ -0,0 +1,16 @@
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::*;
+use io::*;
+use sails_rs::{ActorId, Vec};
+
+pub fn get_position_ids(invariant: &Program, owner: ActorId) -> Vec<PositionId> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetPositionIds",
+        payload: (owner),
+        response_type: Vec<PositionId>
+    )
+}
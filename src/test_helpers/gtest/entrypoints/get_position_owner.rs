@@ -0,0 +1,19 @@
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::*;
+use io::*;
+use sails_rs::ActorId;
+
+pub fn get_position_owner(
+    invariant: &Program,
+    position_id: PositionId,
+) -> Result<ActorId, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetPositionOwner",
+        payload: (position_id),
+        response_type: Result<ActorId, InvariantError>
+    )
+}
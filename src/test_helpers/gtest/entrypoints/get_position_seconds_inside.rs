@@ -0,0 +1,20 @@
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::*;
+use io::*;
+use math::seconds_per_liquidity::SecondsPerLiquidity;
+use sails_rs::ActorId;
+
+pub fn get_position_seconds_inside(
+    invariant: &Program,
+    position_id: PositionId,
+) -> Result<SecondsPerLiquidity, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetPositionSecondsInside",
+        payload: (position_id),
+        response_type: Result<SecondsPerLiquidity, InvariantError>
+    )
+}
@@ -6,15 +6,14 @@ use sails_rs::{ActorId, Vec};
 
 pub fn get_position_with_associates(
     invariant: &Program,
-    owner: impl Into<ActorId>,
-    id: u32,
+    position_id: PositionId,
 ) -> Result<(Position, Pool, Tick, Tick), InvariantError> {
     send_query!(
         program: invariant,
         user: PROGRAM_OWNER,
         service_name: "Service",
         action: "GetPositionWithAssociates",
-        payload: (owner.into(), id),
+        payload: (position_id),
         response_type: Result<(Position, Pool, Tick, Tick), InvariantError>
     )
 }
@@ -0,0 +1,105 @@
+use crate::{send_query, send_request, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::*;
+use io::*;
+use math::token_amount::TokenAmount;
+use sails_rs::ActorId;
+
+pub fn create_campaign(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    reward_token: ActorId,
+    total_reward: TokenAmount,
+    start_timestamp: u64,
+    end_timestamp: u64,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Incentives",
+        action: "CreateCampaign",
+        payload: (pool_key, reward_token, total_reward, start_timestamp, end_timestamp)
+    )
+}
+
+pub fn end_campaign(invariant: &Program, user: u64, campaign_id: u64) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Incentives",
+        action: "EndCampaign",
+        payload: (campaign_id)
+    )
+}
+
+pub fn stake(
+    invariant: &Program,
+    user: u64,
+    position_id: PositionId,
+    campaign_id: u64,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Incentives",
+        action: "Stake",
+        payload: (position_id, campaign_id)
+    )
+}
+
+pub fn unstake(invariant: &Program, user: u64, position_id: PositionId) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Incentives",
+        action: "Unstake",
+        payload: (position_id)
+    )
+}
+
+pub fn claim_reward(invariant: &Program, user: u64, position_id: PositionId) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Incentives",
+        action: "ClaimReward",
+        payload: (position_id)
+    )
+}
+
+pub fn get_campaign(invariant: &Program, campaign_id: u64) -> Result<Campaign, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Incentives",
+        action: "GetCampaign",
+        payload: (campaign_id),
+        response_type: Result<Campaign, InvariantError>
+    )
+}
+
+pub fn get_stake(invariant: &Program, position_id: PositionId) -> Result<Stake, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Incentives",
+        action: "GetStake",
+        payload: (position_id),
+        response_type: Result<Stake, InvariantError>
+    )
+}
+
+pub fn get_pending_reward(
+    invariant: &Program,
+    position_id: PositionId,
+) -> Result<TokenAmount, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Incentives",
+        action: "GetPendingReward",
+        payload: (position_id),
+        response_type: Result<TokenAmount, InvariantError>
+    )
+}
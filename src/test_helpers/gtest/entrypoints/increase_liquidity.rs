@@ -0,0 +1,21 @@
+use crate::send_request;
+use contracts::PositionId;
+use gtest::*;
+use math::{liquidity::Liquidity, sqrt_price::SqrtPrice};
+
+pub fn increase_liquidity(
+    invariant: &Program,
+    user: u64,
+    position_id: PositionId,
+    liquidity_delta: Liquidity,
+    slippage_limit_lower: SqrtPrice,
+    slippage_limit_upper: SqrtPrice,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "IncreaseLiquidity",
+        payload: (position_id, liquidity_delta, slippage_limit_lower, slippage_limit_upper)
+    )
+}
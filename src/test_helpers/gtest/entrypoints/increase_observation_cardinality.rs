@@ -0,0 +1,20 @@
+use crate::send_request;
+use contracts::PoolKey;
+use gtest::*;
+
+pub fn increase_observation_cardinality(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    cardinality_next: u16,
+    value: u128,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "IncreaseObservationCardinality",
+        payload: (pool_key, cardinality_next),
+        value: value
+    )
+}
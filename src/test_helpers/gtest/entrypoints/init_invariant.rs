@@ -1,5 +1,6 @@
 use crate::send_request;
 use crate::test_helpers::gtest::*;
+use contracts::PauseFlags;
 use gtest::*;
 use io::*;
 use math::percentage::Percentage;
@@ -8,13 +9,18 @@ use sails_rs::prelude::*;
 use super::InvariantResult;
 
 pub fn init_invariant(sys: &System, protocol_fee: Percentage) -> Program<'_> {
+    init_invariant_with_id(sys, INVARIANT_ID, protocol_fee)
+}
+
+pub fn init_invariant_with_id(sys: &System, id: u64, protocol_fee: Percentage) -> Program<'_> {
     let bytes =
         include_bytes!("../../../../target/wasm32-unknown-unknown/release/invariant_wasm.opt.wasm");
-    let program = Program::from_binary_with_id(sys, INVARIANT_ID, bytes);
+    let program = Program::from_binary_with_id(sys, id, bytes);
 
     let init = InvariantConfig {
         admin: ADMIN.into(),
         protocol_fee,
+        pause: PauseFlags::default(),
     };
 
     let request: Vec<u8> = ["New".encode(), init.encode()].concat();
@@ -0,0 +1,18 @@
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+use gtest::*;
+use sails_rs::ActorId;
+
+pub fn is_position_operator(
+    invariant: &Program,
+    owner: impl Into<ActorId>,
+    operator: impl Into<ActorId>,
+) -> bool {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "IsPositionOperator",
+        payload: (owner.into(), operator.into()),
+        response_type: bool
+    )
+}
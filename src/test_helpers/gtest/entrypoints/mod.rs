@@ -2,20 +2,34 @@
 #![allow(dead_code)]
 
 pub mod add_fee_tier;
+pub mod approve_position;
+pub mod batch;
 pub mod change_fee_receiver;
+pub mod change_pool_dynamic_fee;
 pub mod change_protocol_fee;
 pub mod claim_fee;
+pub mod create_limit_order;
 pub mod create_pool;
 pub mod create_position;
+pub mod decrease_liquidity;
 pub mod deposit;
 pub mod fee_tier_exists;
+pub mod flash;
 pub mod get_all_positions;
 pub mod get_fee_tiers;
+pub mod get_limit_order;
 pub mod get_liquidity_ticks;
 pub mod get_liquidity_ticks_amount;
+pub mod get_oracle;
 pub mod get_pool;
 pub mod get_pool_keys;
 pub mod get_position;
+pub mod get_position_approved;
+pub mod get_position_by_index;
+pub mod get_position_id;
+pub mod get_position_ids;
+pub mod get_position_owner;
+pub mod get_position_seconds_inside;
 pub mod get_position_ticks;
 pub mod get_position_with_associates;
 pub mod get_positions;
@@ -23,12 +37,24 @@ pub mod get_protocol_fee;
 pub mod get_tick;
 pub mod get_tickmap;
 pub mod get_user_balances;
+pub mod incentives;
+pub mod increase_liquidity;
+pub mod increase_observation_cardinality;
 pub mod init_invariant;
+pub mod is_position_operator;
 pub mod is_tick_initialized;
+pub mod observe;
+pub mod pause_flags;
+pub mod pool_status;
+pub mod position_nft;
 pub mod quote;
 pub mod quote_route;
 pub mod remove_fee_tier;
 pub mod remove_position;
+pub mod roles;
+pub mod set_position_operator;
+pub mod split_route;
+pub mod state_migration;
 pub mod swap;
 pub mod swap_route;
 pub mod transfer_position;
@@ -37,20 +63,34 @@ pub mod withdraw;
 pub mod withdraw_protocol_fee;
 
 pub use add_fee_tier::*;
+pub use approve_position::*;
+pub use batch::*;
 pub use change_fee_receiver::*;
+pub use change_pool_dynamic_fee::*;
 pub use change_protocol_fee::*;
 pub use claim_fee::*;
+pub use create_limit_order::*;
 pub use create_pool::*;
 pub use create_position::*;
+pub use decrease_liquidity::*;
 pub use deposit::*;
 pub use fee_tier_exists::*;
+pub use flash::*;
 pub use get_all_positions::*;
 pub use get_fee_tiers::*;
+pub use get_limit_order::*;
 pub use get_liquidity_ticks::*;
 pub use get_liquidity_ticks_amount::*;
+pub use get_oracle::*;
 pub use get_pool::*;
 pub use get_pool_keys::*;
 pub use get_position::*;
+pub use get_position_approved::*;
+pub use get_position_by_index::*;
+pub use get_position_id::*;
+pub use get_position_ids::*;
+pub use get_position_owner::*;
+pub use get_position_seconds_inside::*;
 pub use get_position_ticks::*;
 pub use get_position_with_associates::*;
 pub use get_positions::*;
@@ -58,12 +98,24 @@ pub use get_protocol_fee::*;
 pub use get_tick::*;
 pub use get_tickmap::*;
 pub use get_user_balances::*;
+pub use incentives::*;
+pub use increase_liquidity::*;
+pub use increase_observation_cardinality::*;
 pub use init_invariant::*;
+pub use is_position_operator::*;
 pub use is_tick_initialized::*;
+pub use observe::*;
+pub use pause_flags::*;
+pub use pool_status::*;
+pub use position_nft::*;
 pub use quote::*;
 pub use quote_route::*;
 pub use remove_fee_tier::*;
 pub use remove_position::*;
+pub use roles::*;
+pub use set_position_operator::*;
+pub use split_route::*;
+pub use state_migration::*;
 pub use swap::*;
 pub use swap_route::*;
 pub use transfer_position::*;
@@ -0,0 +1,22 @@
+use contracts::*;
+use gtest::*;
+use math::seconds_per_liquidity::SecondsPerLiquidity;
+use sails_rs::Vec;
+
+use io::*;
+
+use crate::{send_query, test_helpers::gtest::PROGRAM_OWNER};
+pub fn observe(
+    invariant: &Program,
+    pool_key: PoolKey,
+    seconds_agos: Vec<u64>,
+) -> Result<Vec<(i64, SecondsPerLiquidity)>, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "Observe",
+        payload: (pool_key, seconds_agos),
+        response_type: Result<Vec<(i64, SecondsPerLiquidity)>, InvariantError>
+    )
+}
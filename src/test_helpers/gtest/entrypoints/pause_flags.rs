@@ -0,0 +1,33 @@
+use crate::{send_query, send_request, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::{InvariantError, PauseFlags, PoolKey};
+use gtest::{Program, RunResult};
+use sails_rs::prelude::*;
+
+pub fn change_pause_flags(
+    invariant: &Program,
+    user: u64,
+    pool_key: Option<PoolKey>,
+    pause: PauseFlags,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "ChangePauseFlags",
+        payload: (pool_key, pause)
+    )
+}
+
+pub fn get_pause_flags(
+    invariant: &Program,
+    pool_key: Option<PoolKey>,
+) -> Result<PauseFlags, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetPauseFlags",
+        payload: (pool_key),
+        response_type: Result<PauseFlags, InvariantError>
+    )
+}
@@ -0,0 +1,38 @@
+use crate::{send_query, send_request, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::{InvariantError, PoolKey, PoolStatus};
+use gtest::{Program, RunResult};
+use sails_rs::prelude::*;
+
+pub fn deprecate_pool(invariant: &Program, user: u64, pool_key: PoolKey) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "DeprecatePool",
+        payload: (pool_key)
+    )
+}
+
+pub fn remove_pool(invariant: &Program, user: u64, pool_key: PoolKey) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "RemovePool",
+        payload: (pool_key)
+    )
+}
+
+pub fn get_pool_status(
+    invariant: &Program,
+    pool_key: PoolKey,
+) -> Result<PoolStatus, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetPoolStatus",
+        payload: (pool_key),
+        response_type: Result<PoolStatus, InvariantError>
+    )
+}
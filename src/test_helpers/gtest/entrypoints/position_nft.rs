@@ -0,0 +1,113 @@
+use crate::{send_query, send_request, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::*;
+use gtest::*;
+use io::*;
+use sails_rs::ActorId;
+
+pub fn nft_transfer_from(
+    invariant: &Program,
+    user: u64,
+    from: impl Into<ActorId>,
+    to: impl Into<ActorId>,
+    token_id: PositionId,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "PositionNft",
+        action: "TransferFrom",
+        payload: (from.into(), to.into(), token_id)
+    )
+}
+
+pub fn nft_approve(
+    invariant: &Program,
+    user: u64,
+    approved: impl Into<ActorId>,
+    token_id: PositionId,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "PositionNft",
+        action: "Approve",
+        payload: (approved.into(), token_id)
+    )
+}
+
+pub fn nft_set_approval_for_all(
+    invariant: &Program,
+    user: u64,
+    operator: impl Into<ActorId>,
+    approved: bool,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "PositionNft",
+        action: "SetApprovalForAll",
+        payload: (operator.into(), approved)
+    )
+}
+
+pub fn nft_owner_of(invariant: &Program, token_id: PositionId) -> Result<ActorId, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "PositionNft",
+        action: "OwnerOf",
+        payload: (token_id),
+        response_type: Result<ActorId, InvariantError>
+    )
+}
+
+pub fn nft_balance_of(invariant: &Program, owner: impl Into<ActorId>) -> u32 {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "PositionNft",
+        action: "BalanceOf",
+        payload: (owner.into()),
+        response_type: u32
+    )
+}
+
+pub fn nft_get_approved(invariant: &Program, token_id: PositionId) -> Option<ActorId> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "PositionNft",
+        action: "GetApproved",
+        payload: (token_id),
+        response_type: Option<ActorId>
+    )
+}
+
+pub fn nft_is_approved_for_all(
+    invariant: &Program,
+    owner: impl Into<ActorId>,
+    operator: impl Into<ActorId>,
+) -> bool {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "PositionNft",
+        action: "IsApprovedForAll",
+        payload: (owner.into(), operator.into()),
+        response_type: bool
+    )
+}
+
+pub fn nft_token_metadata(
+    invariant: &Program,
+    token_id: PositionId,
+) -> Result<PositionMetadata, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "PositionNft",
+        action: "TokenMetadata",
+        payload: (token_id),
+        response_type: Result<PositionMetadata, InvariantError>
+    )
+}
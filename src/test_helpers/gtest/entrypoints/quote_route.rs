@@ -22,3 +22,18 @@ pub fn quote_route(
         response_type: Result<TokenAmount, InvariantError>
     )
 }
+
+pub fn quote_route_exact_out(
+    invariant: &Program,
+    amount_out: TokenAmount,
+    swaps: Vec<SwapHop>,
+) -> sails_rs::Result<TokenAmount, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "QuoteRouteExactOut",
+        payload: (amount_out, swaps),
+        response_type: Result<TokenAmount, InvariantError>
+    )
+}
@@ -2,18 +2,41 @@ use super::InvariantResult;
 use crate::send_request;
 use crate::test_helpers::consts::*;
 use crate::test_helpers::gtest::consts::*;
-use contracts::{pool_key, FeeTier, PoolKey};
+use contracts::{pool_key, FeeTier, PoolKey, PositionId};
 use gtest::*;
 use io::*;
 use math::{liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice};
 use sails_rs::ActorId;
 
-pub fn remove_position(invariant: &Program, user: u64, index: u32) -> RunResult {
+pub fn remove_position(invariant: &Program, user: u64, position_id: PositionId) -> RunResult {
+    remove_position_with_deadline(invariant, user, position_id, None)
+}
+
+pub fn remove_position_with_deadline(
+    invariant: &Program,
+    user: u64,
+    position_id: PositionId,
+    deadline: Option<u64>,
+) -> RunResult {
     send_request!(
         program: invariant,
         user: user,
         service_name: "Service",
         action: "RemovePosition",
-        payload: (index)
+        payload: (position_id, deadline)
+    )
+}
+
+pub fn remove_position_and_withdraw(
+    invariant: &Program,
+    user: u64,
+    position_id: PositionId,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "RemovePositionAndWithdraw",
+        payload: (position_id, None::<u64>)
     )
 }
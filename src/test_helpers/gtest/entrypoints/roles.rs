@@ -0,0 +1,77 @@
+use crate::{send_query, send_request, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::Role;
+use gtest::{Program, RunResult};
+use sails_rs::prelude::*;
+
+pub fn propose_admin(invariant: &Program, user: u64, admin: ActorId) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "ProposeAdmin",
+        payload: (admin)
+    )
+}
+
+pub fn accept_admin(invariant: &Program, user: u64) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "AcceptAdmin",
+        payload: ()
+    )
+}
+
+pub fn grant_role(invariant: &Program, user: u64, account: ActorId, role: Role) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "GrantRole",
+        payload: (account, role)
+    )
+}
+
+pub fn revoke_role(invariant: &Program, user: u64, account: ActorId, role: Role) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "RevokeRole",
+        payload: (account, role)
+    )
+}
+
+pub fn get_admin(invariant: &Program) -> ActorId {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetAdmin",
+        payload: (),
+        response_type: ActorId
+    )
+}
+
+pub fn get_pending_admin(invariant: &Program) -> Option<ActorId> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetPendingAdmin",
+        payload: (),
+        response_type: Option<ActorId>
+    )
+}
+
+pub fn get_roles(invariant: &Program, account: ActorId) -> Vec<Role> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "GetRoles",
+        payload: (account),
+        response_type: Vec<contracts::Role>
+    )
+}
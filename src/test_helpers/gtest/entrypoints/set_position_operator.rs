@@ -0,0 +1,18 @@
+use crate::send_request;
+use gtest::*;
+use sails_rs::ActorId;
+
+pub fn set_position_operator(
+    invariant: &Program,
+    user: u64,
+    operator: impl Into<ActorId>,
+    approved: bool,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "SetPositionOperator",
+        payload: (operator.into(), approved)
+    )
+}
@@ -0,0 +1,53 @@
+use crate::{send_query, send_request, test_helpers::gtest::PROGRAM_OWNER};
+use contracts::InvariantError;
+use gtest::{Program, RunResult};
+use io::*;
+use math::{percentage::Percentage, token_amount::TokenAmount};
+use sails_rs::Vec;
+
+pub fn swap_split_route(
+    invariant: &Program,
+    user: u64,
+    amount_in: TokenAmount,
+    expected_amount_out: TokenAmount,
+    slippage: Percentage,
+    routes: Vec<(Percentage, Vec<SwapHop>)>,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "SwapSplitRoute",
+        payload: (amount_in, expected_amount_out, slippage, routes, None::<u64>)
+    )
+}
+
+pub fn quote_split_route(
+    invariant: &Program,
+    amount_in: TokenAmount,
+    routes: Vec<(Percentage, Vec<SwapHop>)>,
+) -> Result<TokenAmount, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "QuoteSplitRoute",
+        payload: (amount_in, routes),
+        response_type: Result<TokenAmount, InvariantError>
+    )
+}
+
+pub fn propose_split_route(
+    invariant: &Program,
+    amount_in: TokenAmount,
+    routes: Vec<Vec<SwapHop>>,
+) -> Result<Vec<(Percentage, Vec<SwapHop>)>, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: PROGRAM_OWNER,
+        service_name: "Service",
+        action: "ProposeSplitRoute",
+        payload: (amount_in, routes),
+        response_type: Result<Vec<(Percentage, Vec<SwapHop>)>, InvariantError>
+    )
+}
@@ -0,0 +1,80 @@
+use crate::{send_query, send_request};
+use contracts::{chain_state_checksum, InvariantError, StateChecksum, StateChunk, StateCursor};
+use gtest::{Program, RunResult};
+use io::StateExport;
+use sails_rs::prelude::*;
+
+pub fn start_state_export(invariant: &Program, user: u64) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "StartStateExport",
+        payload: ()
+    )
+}
+
+pub fn export_state(
+    invariant: &Program,
+    user: u64,
+    cursor: Option<StateCursor>,
+) -> Result<StateExport, InvariantError> {
+    send_query!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "ExportState",
+        payload: (cursor),
+        response_type: Result<StateExport, InvariantError>
+    )
+}
+
+// Walks all of the chunks, returns them with the checksum of the whole state
+pub fn export_all_state(invariant: &Program, user: u64) -> (Vec<StateChunk>, StateChecksum) {
+    let mut chunks = vec![];
+    let mut checksum = StateChecksum::default();
+    let mut cursor = None;
+
+    loop {
+        let export = export_state(invariant, user, cursor).unwrap();
+        checksum = chain_state_checksum(checksum, export.checksum);
+        chunks.push(export.chunk);
+
+        match export.next_cursor {
+            Some(next_cursor) => cursor = Some(next_cursor),
+            None => break,
+        }
+    }
+
+    (chunks, checksum)
+}
+
+pub fn import_state(invariant: &Program, user: u64, chunk: StateChunk) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "ImportState",
+        payload: (chunk)
+    )
+}
+
+pub fn finalize_state_import(invariant: &Program, user: u64, checksum: StateChecksum) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "FinalizeStateImport",
+        payload: (checksum)
+    )
+}
+
+pub fn finish_state_export(invariant: &Program, user: u64) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "FinishStateExport",
+        payload: ()
+    )
+}
@@ -22,6 +22,80 @@ pub fn swap(
         user: user,
         service_name: "Service",
         action: "Swap",
-        payload: (pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit)
+        payload: (pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit, None::<u64>, false)
+    )
+}
+
+pub fn swap_with_deadline(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: SqrtPrice,
+    deadline: Option<u64>,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "Swap",
+        payload: (pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit, deadline, false)
+    )
+}
+
+pub fn swap_with_slippage(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    amount_limit: TokenAmount,
+    slippage: Percentage,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "SwapWithSlippage",
+        payload: (pool_key, x_to_y, amount, by_amount_in, amount_limit, slippage, None::<u64>)
+    )
+}
+
+pub fn swap_and_withdraw(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    x_to_y: bool,
+    amount: TokenAmount,
+    by_amount_in: bool,
+    sqrt_price_limit: SqrtPrice,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "Swap",
+        payload: (pool_key, x_to_y, amount, by_amount_in, sqrt_price_limit, None::<u64>, true)
+    )
+}
+
+pub fn swap_vara_in(
+    invariant: &Program,
+    user: u64,
+    pool_key: PoolKey,
+    sqrt_price_limit: SqrtPrice,
+    withdraw_output: bool,
+    value: u128,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "SwapVaraIn",
+        payload: (pool_key, sqrt_price_limit, None::<u64>, withdraw_output),
+        value: value
     )
 }
@@ -21,6 +21,40 @@ pub fn swap_route(
         user: user,
         service_name: "Service",
         action: "SwapRoute",
-        payload: (amount_in, expected_token_amount, slippage, swaps)
+        payload: (amount_in, expected_token_amount, slippage, swaps, None::<u64>, false)
+    )
+}
+
+pub fn swap_route_with_deadline(
+    invariant: &Program,
+    user: u64,
+    amount_in: TokenAmount,
+    expected_token_amount: TokenAmount,
+    slippage: Percentage,
+    swaps: Vec<SwapHop>,
+    deadline: Option<u64>,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "SwapRoute",
+        payload: (amount_in, expected_token_amount, slippage, swaps, deadline, false)
+    )
+}
+
+pub fn swap_route_exact_out(
+    invariant: &Program,
+    user: u64,
+    amount_out: TokenAmount,
+    max_amount_in: TokenAmount,
+    swaps: Vec<SwapHop>,
+) -> RunResult {
+    send_request!(
+        program: invariant,
+        user: user,
+        service_name: "Service",
+        action: "SwapRouteExactOut",
+        payload: (amount_out, max_amount_in, swaps, None::<u64>)
     )
 }
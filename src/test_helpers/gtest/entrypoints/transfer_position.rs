@@ -1,6 +1,6 @@
 use crate::send_request;
 use crate::test_helpers::gtest::*;
-use contracts::{pool_key, FeeTier, PoolKey};
+use contracts::{pool_key, FeeTier, PoolKey, PositionId};
 use gtest::*;
 use io::*;
 use math::{liquidity::Liquidity, percentage::Percentage, sqrt_price::SqrtPrice};
@@ -9,7 +9,7 @@ use sails_rs::prelude::*;
 pub fn transfer_position(
     invariant: &Program,
     user: u64,
-    index: u32,
+    position_id: PositionId,
     receiver: impl Into<ActorId>,
 ) -> RunResult {
     send_request!(
@@ -17,6 +17,6 @@ pub fn transfer_position(
         user: user,
         service_name: "Service",
         action: "TransferPosition",
-        payload: (index, receiver.into())
+        payload: (position_id, receiver.into())
     )
 }
@@ -29,6 +29,14 @@ pub trait InvariantResult {
         self.emitted_events().last().unwrap().clone()
     }
     #[track_caller]
+    fn assert_event_and_reply(&self) -> TestEvent {
+        self.assert_success();
+        let events = self.emitted_events();
+        assert_eq!(events.len(), 2);
+        events[0].assert_to(EVENT_ADDRESS);
+        events.last().unwrap().clone()
+    }
+    #[track_caller]
     fn last_event(&self) -> TestEvent {
         self.emitted_events().last().unwrap().clone()
     }
@@ -215,6 +223,16 @@ impl TestProgram for Program<'_> {
     }
 }
 
+// Every non-zero amount moved by a transfer is reported with a separate event
+#[track_caller]
+pub fn assert_transfer_events(events: &[TestEvent], amounts: &[TokenAmount]) {
+    let transfers = amounts.iter().filter(|amount| !amount.is_zero()).count();
+    assert_eq!(events.len(), transfers);
+    events.iter().for_each(|event| {
+        event.assert_to(EVENT_ADDRESS);
+    });
+}
+
 // Event structs are necessary since the enum id is dropped when encoding
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
 #[codec(crate = gstd::codec)]
@@ -222,6 +240,7 @@ impl TestProgram for Program<'_> {
 pub struct PositionCreatedEvent {
     pub timestamp: u64,
     pub address: ActorId,
+    pub position_id: PositionId,
     pub pool_key: PoolKey,
     pub liquidity_delta: Liquidity,
     pub lower_tick: i32,
@@ -234,6 +253,7 @@ pub struct PositionCreatedEvent {
 pub struct PositionRemovedEvent {
     pub timestamp: u64,
     pub address: ActorId,
+    pub position_id: PositionId,
     pub pool_key: PoolKey,
     pub liquidity_delta: Liquidity,
     pub lower_tick_index: i32,
@@ -243,6 +263,32 @@ pub struct PositionRemovedEvent {
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
 #[codec(crate = gstd::codec)]
 #[scale_info(crate = gstd::scale_info)]
+pub struct PositionLiquidityIncreasedEvent {
+    pub timestamp: u64,
+    pub address: ActorId,
+    pub pool_key: PoolKey,
+    pub position_id: PositionId,
+    pub liquidity_delta: Liquidity,
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub current_sqrt_price: SqrtPrice,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PositionLiquidityDecreasedEvent {
+    pub timestamp: u64,
+    pub address: ActorId,
+    pub pool_key: PoolKey,
+    pub position_id: PositionId,
+    pub liquidity_delta: Liquidity,
+    pub lower_tick: i32,
+    pub upper_tick: i32,
+    pub current_sqrt_price: SqrtPrice,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
 pub struct CrossTickEvent {
     pub timestamp: u64,
     pub address: ActorId,
@@ -263,3 +309,184 @@ pub struct SwapEvent {
     pub target_sqrt_price: SqrtPrice,
     pub x_to_y: bool,
 }
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct FlashLoanEvent {
+    pub timestamp: u64,
+    pub address: ActorId,
+    pub pool_key: PoolKey,
+    pub amount_x: TokenAmount,
+    pub amount_y: TokenAmount,
+    pub fee_x: TokenAmount,
+    pub fee_y: TokenAmount,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PauseFlagsChangedEvent {
+    pub timestamp: u64,
+    pub pool_key: Option<PoolKey>,
+    pub pause: PauseFlags,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PoolDeprecatedEvent {
+    pub timestamp: u64,
+    pub pool_key: PoolKey,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PoolRemovedEvent {
+    pub timestamp: u64,
+    pub pool_key: PoolKey,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PoolCreatedEvent {
+    pub timestamp: u64,
+    pub address: ActorId,
+    pub pool_key: PoolKey,
+    pub init_sqrt_price: SqrtPrice,
+    pub init_tick: i32,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct FeeTierAddedEvent {
+    pub timestamp: u64,
+    pub fee_tier: FeeTier,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct FeeTierRemovedEvent {
+    pub timestamp: u64,
+    pub fee_tier: FeeTier,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct FeeReceiverChangedEvent {
+    pub timestamp: u64,
+    pub pool_key: PoolKey,
+    pub fee_receiver: ActorId,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct FeeClaimedEvent {
+    pub timestamp: u64,
+    pub address: ActorId,
+    pub position_id: PositionId,
+    pub pool_key: PoolKey,
+    pub amount_x: TokenAmount,
+    pub amount_y: TokenAmount,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct ProtocolFeeWithdrawnEvent {
+    pub timestamp: u64,
+    pub address: ActorId,
+    pub pool_key: PoolKey,
+    pub amount_x: TokenAmount,
+    pub amount_y: TokenAmount,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PositionTransferredEvent {
+    pub timestamp: u64,
+    pub position_id: PositionId,
+    pub from: ActorId,
+    pub to: ActorId,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct OperatorSetEvent {
+    pub timestamp: u64,
+    pub owner: ActorId,
+    pub operator: ActorId,
+    pub approved: bool,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct TokenDepositedEvent {
+    pub timestamp: u64,
+    pub address: ActorId,
+    pub token: ActorId,
+    pub amount: TokenAmount,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct TokenWithdrawnEvent {
+    pub timestamp: u64,
+    pub address: ActorId,
+    pub token: ActorId,
+    pub amount: TokenAmount,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct ProtocolFeeChangedEvent {
+    pub timestamp: u64,
+    pub protocol_fee: Percentage,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PoolProtocolFeeChangedEvent {
+    pub timestamp: u64,
+    pub pool_key: PoolKey,
+    pub protocol_fee: Option<Percentage>,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PoolDynamicFeeChangedEvent {
+    pub timestamp: u64,
+    pub pool_key: PoolKey,
+    pub dynamic_fee: Option<DynamicFee>,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct LimitOrderSettledEvent {
+    pub timestamp: u64,
+    pub pool_key: PoolKey,
+    pub position_id: PositionId,
+    pub amount_x: TokenAmount,
+    pub amount_y: TokenAmount,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct PositionApprovedEvent {
+    pub timestamp: u64,
+    pub position_id: PositionId,
+    pub owner: ActorId,
+    pub spender: ActorId,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct TransferEvent {
+    pub from: ActorId,
+    pub to: ActorId,
+    pub token_id: PositionId,
+}
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[codec(crate = gstd::codec)]
+#[scale_info(crate = gstd::scale_info)]
+pub struct ApprovalEvent {
+    pub owner: ActorId,
+    pub approved: ActorId,
+    pub token_id: PositionId,
+}
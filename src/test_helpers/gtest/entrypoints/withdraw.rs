@@ -28,13 +28,13 @@ pub fn withdraw_single_token(
 
     res.assert_success();
     let events = res.emitted_events();
-    assert_eq!(events.len(), 1);
-    events
+    let amount = events
         .last()
         .unwrap()
         .decoded_event::<TokenAmount>()
-        .unwrap()
-        .into()
+        .unwrap();
+    assert_transfer_events(&events[..events.len() - 1], &[amount]);
+    amount.into()
 }
 
 #[track_caller]
@@ -62,14 +62,13 @@ pub fn withdraw_token_pair(
 
     res.assert_success();
     let events = res.emitted_events();
-    assert_eq!(events.len(), 1);
-
-    events
+    let (amount_x, amount_y) = events
         .last()
         .unwrap()
         .decoded_event::<(TokenAmount, TokenAmount)>()
-        .unwrap()
-        .into()
+        .unwrap();
+    assert_transfer_events(&events[..events.len() - 1], &[amount_x, amount_y]);
+    (amount_x, amount_y).into()
 }
 
 #[track_caller]
@@ -95,14 +94,12 @@ pub fn withdraw_vara(
 
     res.assert_success();
     let events = res.emitted_events();
-    assert_eq!(events.len(), 2);
-    (
-        res,
-        events
-            .last()
-            .unwrap()
-            .decoded_event::<TokenAmount>()
-            .unwrap(),
-    )
-        .into()
+    let amount = events
+        .last()
+        .unwrap()
+        .decoded_event::<TokenAmount>()
+        .unwrap();
+    // the value is sent in a separate message before the reply
+    assert_transfer_events(&events[..events.len() - 2], &[amount]);
+    (res, amount).into()
 }
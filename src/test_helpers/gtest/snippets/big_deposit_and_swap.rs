@@ -48,7 +48,7 @@ pub fn big_deposit_and_swap(sys: &System, x_to_y: bool) {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
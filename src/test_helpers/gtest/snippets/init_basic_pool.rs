@@ -24,7 +24,7 @@ pub fn init_basic_pool(invariant: &Program<'_>, token_x: &ActorId, token_y: &Act
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 }
@@ -3,7 +3,8 @@ use contracts::*;
 use decimal::*;
 use gtest::*;
 use math::{
-    fee_growth::FeeGrowth, liquidity::Liquidity, percentage::Percentage, token_amount::TokenAmount,
+    fee_growth::FeeGrowth, liquidity::Liquidity, percentage::Percentage,
+    seconds_per_liquidity::SecondsPerLiquidity, token_amount::TokenAmount,
 };
 use sails_rs::prelude::*;
 
@@ -81,6 +82,9 @@ pub fn init_basic_position(
         &position_created_event,
         &PositionCreatedEvent {
             address: REGULAR_USER_1.into(),
+            position_id: *get_position_ids(&invariant, REGULAR_USER_1.into())
+                .last()
+                .unwrap(),
             pool_key,
             liquidity_delta: liquidity,
             timestamp: 0,
@@ -104,6 +108,7 @@ pub fn init_basic_position(
             upper_tick_index: upper_tick,
             fee_growth_inside_x: FeeGrowth::new(0),
             fee_growth_inside_y: FeeGrowth::new(0),
+            seconds_per_liquidity_inside: SecondsPerLiquidity::new(0),
             last_block_number: 0 as u64,
             tokens_owed_x: TokenAmount::new(U256::from(0)),
             tokens_owed_y: TokenAmount::new(U256::from(0)),
@@ -4,7 +4,8 @@ use decimal::*;
 use gtest::*;
 use math::{
     fee_growth::FeeGrowth, liquidity::Liquidity, percentage::Percentage,
-    sqrt_price::calculate_sqrt_price, token_amount::TokenAmount,
+    seconds_per_liquidity::SecondsPerLiquidity, sqrt_price::calculate_sqrt_price,
+    token_amount::TokenAmount,
 };
 use sails_rs::prelude::*;
 
@@ -22,7 +23,7 @@ pub fn init_slippage_pool_with_liquidity(
     };
 
     let res = add_fee_tier(&invariant, ADMIN, fee_tier);
-    res.assert_single_event().assert_empty().assert_to(ADMIN);
+    res.assert_event_and_reply().assert_empty().assert_to(ADMIN);
 
     let init_tick = 0;
     let init_sqrt_price = calculate_sqrt_price(init_tick).unwrap();
@@ -36,7 +37,7 @@ pub fn init_slippage_pool_with_liquidity(
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
@@ -102,6 +103,9 @@ pub fn init_slippage_pool_with_liquidity(
         &position_created_event,
         &PositionCreatedEvent {
             address: REGULAR_USER_1.into(),
+            position_id: *get_position_ids(&invariant, REGULAR_USER_1.into())
+                .last()
+                .unwrap(),
             pool_key,
             liquidity_delta: liquidity,
             timestamp: 0,
@@ -125,6 +129,7 @@ pub fn init_slippage_pool_with_liquidity(
             upper_tick_index: upper_tick,
             fee_growth_inside_x: FeeGrowth::new(0),
             fee_growth_inside_y: FeeGrowth::new(0),
+            seconds_per_liquidity_inside: SecondsPerLiquidity::new(0),
             last_block_number: 0 as u64,
             tokens_owed_x: TokenAmount::new(U256::from(0)),
             tokens_owed_y: TokenAmount::new(U256::from(0)),
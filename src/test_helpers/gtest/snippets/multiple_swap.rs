@@ -43,7 +43,7 @@ pub fn multiple_swap(x_to_y: bool) {
         init_sqrt_price,
         init_tick,
     )
-    .assert_single_event()
+    .assert_event_and_reply()
     .assert_empty()
     .assert_to(REGULAR_USER_1);
 
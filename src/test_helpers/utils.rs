@@ -12,11 +12,16 @@ pub fn pools_are_identical_no_timestamp(pool: &Pool, other_pool: &Pool) {
         current_tick_index,
         fee_growth_global_x,
         fee_growth_global_y,
+        seconds_per_liquidity_global: _seconds_per_liquidity_global,
         fee_protocol_token_x,
         fee_protocol_token_y,
         start_timestamp: _start_timestamp,
         last_timestamp: _last_timestamp,
         fee_receiver,
+        pause,
+        protocol_fee: _protocol_fee,
+        dynamic_fee,
+        deprecated,
     } = pool;
     assert_eq!(*liquidity, other_pool.liquidity);
     assert_eq!(*sqrt_price, other_pool.sqrt_price);
@@ -26,6 +31,9 @@ pub fn pools_are_identical_no_timestamp(pool: &Pool, other_pool: &Pool) {
     assert_eq!(*fee_protocol_token_x, other_pool.fee_protocol_token_x);
     assert_eq!(*fee_protocol_token_y, other_pool.fee_protocol_token_y);
     assert_eq!(*fee_receiver, other_pool.fee_receiver);
+    assert_eq!(*pause, other_pool.pause);
+    assert_eq!(*dynamic_fee, other_pool.dynamic_fee);
+    assert_eq!(*deprecated, other_pool.deprecated);
 }
 
 #[track_caller]
@@ -61,6 +69,7 @@ pub fn positions_are_identical_no_timestamp(position: &Position, other_position:
         pool_key,
         fee_growth_inside_x,
         fee_growth_inside_y,
+        seconds_per_liquidity_inside: _seconds_per_liquidity_inside,
         liquidity,
         lower_tick_index,
         upper_tick_index,
@@ -86,6 +95,7 @@ pub fn position_created_events_are_identical_no_timestamp(
     let PositionCreatedEvent {
         timestamp: _timestamp,
         address,
+        position_id,
         pool_key,
         liquidity_delta,
         lower_tick,
@@ -95,6 +105,7 @@ pub fn position_created_events_are_identical_no_timestamp(
 
     assert_eq!(*pool_key, other_position_created_event.pool_key);
     assert_eq!(*address, other_position_created_event.address);
+    assert_eq!(*position_id, other_position_created_event.position_id);
     assert_eq!(
         *liquidity_delta,
         other_position_created_event.liquidity_delta
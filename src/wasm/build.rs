@@ -1,6 +1,10 @@
 use invariant::InvariantProgram;
 use sails_idl_gen::program;
-use std::{env, fs::File, path::PathBuf};
+use std::{
+    env,
+    fs::{self, File},
+    path::PathBuf,
+};
 
 fn main() {
     gear_wasm_builder::build();
@@ -9,7 +13,12 @@ fn main() {
 
     let idl_file_path = manifest_dir_path.join("invariant.idl");
 
-    let idl_file = File::create(idl_file_path).unwrap();
+    let idl_file = File::create(&idl_file_path).unwrap();
 
     program::generate_idl::<InvariantProgram>(idl_file).unwrap();
+
+    // the SDK decodes messages and events with its own copy of the IDL
+    let sdk_idl_file_path = manifest_dir_path.join("../../sdk/contracts/invariant/invariant.idl");
+
+    fs::copy(idl_file_path, sdk_idl_file_path).unwrap();
 }